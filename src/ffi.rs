@@ -55,10 +55,16 @@ impl PyInvariantEngine {
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         let after: ApplicationState = serde_json::from_str(after_json)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
-        
+
         let result = self.validator.validate_transition(&before, &after);
         Ok(PyValidationResult::from(result))
     }
+
+    /// Enable or disable OTEL tracing/metrics for this validator without
+    /// recompiling the extension.
+    pub fn set_tracing_enabled(&self, enabled: bool) {
+        self.validator.set_tracing_enabled(enabled);
+    }
 }
 
 /// Python-friendly validation result
@@ -145,6 +151,19 @@ impl PyStateTracker {
     pub fn verify_integrity(&self) -> bool {
         self.ledger.verify_integrity()
     }
+
+    /// Export every recorded ledger entry to `path` as a single Arrow IPC
+    /// file, so Python callers can load them straight into a dataframe.
+    #[cfg(feature = "arrow_export")]
+    pub fn export_ledger_arrow(&self, path: &str) -> PyResult<()> {
+        let entries = self.ledger.get_range(
+            chrono::DateTime::<chrono::Utc>::MIN_UTC,
+            chrono::Utc::now() + chrono::Duration::days(1),
+        );
+        crate::state::LedgerArrowExporter::new()
+            .write_batches(std::path::Path::new(path), &entries)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
 }
 
 /// Python wrapper for ProofEngine
@@ -183,6 +202,29 @@ impl PyProofEngine {
         
         Ok(self.causal.get_causality_confidence(&action, &effect))
     }
+
+    /// Enable or disable OTEL tracing/metrics for the causal and replay
+    /// engines without recompiling the extension.
+    pub fn set_tracing_enabled(&self, enabled: bool) {
+        self.causal.set_tracing_enabled(enabled);
+        self.replay.set_tracing_enabled(enabled);
+    }
+
+    /// Flatten `proofs_json` (each a JSON-encoded `Proof`) into rows and
+    /// stream them to a single Parquet file at `path`, for loading
+    /// straight into a dataframe for aggregation.
+    #[cfg(feature = "arrow_export")]
+    pub fn export_proofs_parquet(&self, proofs_json: Vec<String>, path: &str) -> PyResult<()> {
+        let proofs: Vec<Proof> = proofs_json
+            .iter()
+            .map(|json| serde_json::from_str(json))
+            .collect::<Result<_, _>>()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+
+        crate::proof::ArrowExporter::new()
+            .write_proofs_parquet(std::path::Path::new(path), &proofs)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
 }
 
 /// Python wrapper for Finding
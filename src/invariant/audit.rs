@@ -0,0 +1,249 @@
+//! Validation Ledger - Tamper-evident, hash-chained record of invariant verdicts
+//!
+//! Borrows the ZIP-244 style of committing to components via domain-separated
+//! hashes: the relevant state slices for each `InvariantCategory` are hashed
+//! with a unique BLAKE2b-256 personalization tag, then folded in a fixed
+//! category order into a single 32-byte "transition root". Every
+//! `SecurityInvariant::validate` verdict is then chained into the ledger via
+//! `hash(invariant_id, verdict, transition_root, prev_digest)`, so altering
+//! any past verdict or the state it was computed over breaks every digest
+//! that follows. This gives a cryptographically verifiable compliance trail
+//! in place of ephemeral booleans.
+
+use super::catalog::InvariantCategory;
+use crate::state::ApplicationState;
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// A 32-byte BLAKE2b digest.
+pub type Digest32 = [u8; 32];
+
+fn personalization(category: InvariantCategory) -> &'static [u8; 16] {
+    match category {
+        InvariantCategory::Authorization => b"SICAT_Auth______",
+        InvariantCategory::Monetary => b"SICAT_Money_____",
+        InvariantCategory::Workflow => b"SICAT_Workflow__",
+        InvariantCategory::Trust => b"SICAT_Trust_____",
+        InvariantCategory::DataIntegrity => b"SICAT_DataInt___",
+        InvariantCategory::SessionManagement => b"SICAT_Session___",
+        InvariantCategory::InputValidation => b"SICAT_Input_____",
+        InvariantCategory::RateLimiting => b"SICAT_RateLim___",
+        InvariantCategory::Custom => b"SICAT_Custom____",
+    }
+}
+
+fn blake2b_256(parts: &[&[u8]]) -> Digest32 {
+    let mut hasher = Blake2bVar::new(32).expect("32 is a valid BLAKE2b-256 output size");
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut out = [0u8; 32];
+    hasher
+        .finalize_variable(&mut out)
+        .expect("hasher was created with a 32-byte output size");
+    out
+}
+
+/// Fixed order categories are folded in, so the resulting transition root
+/// never depends on `HashMap`/registration iteration order.
+const CATEGORY_ORDER: [InvariantCategory; 9] = [
+    InvariantCategory::Authorization,
+    InvariantCategory::Monetary,
+    InvariantCategory::Workflow,
+    InvariantCategory::Trust,
+    InvariantCategory::DataIntegrity,
+    InvariantCategory::SessionManagement,
+    InvariantCategory::InputValidation,
+    InvariantCategory::RateLimiting,
+    InvariantCategory::Custom,
+];
+
+/// Hash the slice of `ApplicationState` relevant to `category`, personalized
+/// so that categories never collide even over identical underlying bytes.
+fn category_component_digest(category: InvariantCategory, state: &ApplicationState) -> Digest32 {
+    let relevant = match category {
+        InvariantCategory::Authorization => serde_json::json!({
+            "ownership": &state.ownership,
+            "authorization_events": &state.authorization_events,
+        }),
+        InvariantCategory::DataIntegrity => serde_json::json!({
+            "data_objects": &state.data_objects,
+        }),
+        InvariantCategory::Monetary => serde_json::json!({
+            "balances": &state.balances,
+            "financial_transactions": &state.financial_transactions,
+            "overdraft_permissions": &state.overdraft_permissions,
+        }),
+        InvariantCategory::Workflow => serde_json::json!({
+            "workflow_positions": &state.workflow_positions,
+            "workflow_completions": &state.workflow_completions,
+        }),
+        InvariantCategory::Trust => serde_json::json!({ "trust_decisions": &state.trust_decisions }),
+        InvariantCategory::SessionManagement => serde_json::json!({ "current_session": &state.current_session }),
+        InvariantCategory::InputValidation | InvariantCategory::RateLimiting | InvariantCategory::Custom => {
+            serde_json::json!({})
+        }
+    };
+    let bytes = serde_json::to_vec(&relevant).unwrap_or_default();
+    blake2b_256(&[personalization(category), &bytes])
+}
+
+/// Compute the 32-byte transition root for a `(before, after)` pair: the
+/// per-category component digests over both states, folded in fixed
+/// category order.
+pub fn transition_root(before: &ApplicationState, after: &ApplicationState) -> Digest32 {
+    let mut acc = [0u8; 32];
+    for category in CATEGORY_ORDER {
+        let before_digest = category_component_digest(category, before);
+        let after_digest = category_component_digest(category, after);
+        acc = blake2b_256(&[&acc, &before_digest, &after_digest]);
+    }
+    acc
+}
+
+/// One chained entry recording a single `SecurityInvariant::validate` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerRecord {
+    pub invariant_id: String,
+    pub verdict: bool,
+    #[serde(with = "hex_digest")]
+    pub transition_root: Digest32,
+    #[serde(with = "hex_digest")]
+    pub prev_digest: Digest32,
+    #[serde(with = "hex_digest")]
+    pub digest: Digest32,
+}
+
+mod hex_digest {
+    use super::Digest32;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Digest32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Digest32, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = hex::decode(&encoded).map_err(serde::de::Error::custom)?;
+        bytes
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("expected a 32-byte digest"))
+    }
+}
+
+/// Tamper-evident, hash-chained audit trail of every invariant verdict
+/// issued against a stream of state transitions.
+pub struct ValidationLedger {
+    records: RwLock<Vec<LedgerRecord>>,
+}
+
+impl ValidationLedger {
+    pub fn new() -> Self {
+        Self {
+            records: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Record a verdict for `invariant_id` against `transition_root`,
+    /// chaining it onto the current head. Returns the new head digest.
+    pub fn record(&self, invariant_id: &str, verdict: bool, transition_root: Digest32) -> Digest32 {
+        let mut records = self.records.write();
+        let prev_digest = records.last().map(|r| r.digest).unwrap_or([0u8; 32]);
+
+        let digest = blake2b_256(&[
+            invariant_id.as_bytes(),
+            &[verdict as u8],
+            &transition_root,
+            &prev_digest,
+        ]);
+
+        records.push(LedgerRecord {
+            invariant_id: invariant_id.to_string(),
+            verdict,
+            transition_root,
+            prev_digest,
+            digest,
+        });
+
+        digest
+    }
+
+    /// Walk the chain end to end, recomputing every digest, to authenticate
+    /// that no past verdict or link has been altered.
+    pub fn verify(&self) -> bool {
+        let records = self.records.read();
+        let mut prev = [0u8; 32];
+        for record in records.iter() {
+            if record.prev_digest != prev {
+                return false;
+            }
+            let expected = blake2b_256(&[
+                record.invariant_id.as_bytes(),
+                &[record.verdict as u8],
+                &record.transition_root,
+                &record.prev_digest,
+            ]);
+            if expected != record.digest {
+                return false;
+            }
+            prev = record.digest;
+        }
+        true
+    }
+
+    /// Current chain head digest, for external anchoring.
+    pub fn head_digest(&self) -> Digest32 {
+        self.records.read().last().map(|r| r.digest).unwrap_or([0u8; 32])
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.read().is_empty()
+    }
+}
+
+impl Default for ValidationLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ApplicationState;
+
+    #[test]
+    fn test_empty_ledger_verifies() {
+        let ledger = ValidationLedger::new();
+        assert!(ledger.verify());
+        assert_eq!(ledger.head_digest(), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_chain_detects_tampering() {
+        let ledger = ValidationLedger::new();
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+        let root = transition_root(&before, &after);
+
+        ledger.record("AUTH_001", true, root);
+        ledger.record("MON_001", true, root);
+        assert!(ledger.verify());
+
+        ledger.records.write()[0].verdict = false;
+        assert!(!ledger.verify());
+    }
+
+    #[test]
+    fn test_transition_root_is_deterministic() {
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+        assert_eq!(transition_root(&before, &after), transition_root(&before, &after));
+    }
+}
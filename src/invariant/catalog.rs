@@ -5,10 +5,10 @@
 //!
 //! **Requirements: 15.1, 15.2, 15.3, 15.4, 15.5, 43.1, 43.2, 43.4**
 
-use crate::state::ApplicationState;
+use crate::state::{ApplicationState, StateField};
 use crate::types::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Categories of security invariants
@@ -34,6 +34,179 @@ pub enum InvariantCategory {
     Custom,
 }
 
+/// A concrete piece of evidence that drove a verdict - the specific state
+/// elements a human (or a later proof-engine stage) needs to see to
+/// understand *why* an invariant held, was violated, or was deferred.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evidence {
+    pub field: String,
+    pub description: String,
+    pub value: Option<serde_json::Value>,
+}
+
+impl Evidence {
+    pub fn new(field: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            description: description.into(),
+            value: None,
+        }
+    }
+
+    pub fn with_value(mut self, value: serde_json::Value) -> Self {
+        self.value = Some(value);
+        self
+    }
+}
+
+/// A predicate that a *future* transition must satisfy before an invariant
+/// that could not be decided from a single before/after pair can be
+/// considered discharged (e.g. "session ID must rotate before the next
+/// authenticated action"). Left undischarged at a terminal state, it is a
+/// violation.
+#[derive(Clone)]
+pub struct ProofObligation {
+    pub id: String,
+    pub description: String,
+    pub discharge: Arc<dyn Fn(&ApplicationState, &ApplicationState) -> bool + Send + Sync>,
+}
+
+impl std::fmt::Debug for ProofObligation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProofObligation")
+            .field("id", &self.id)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl ProofObligation {
+    pub fn new(
+        id: impl Into<String>,
+        description: impl Into<String>,
+        discharge: impl Fn(&ApplicationState, &ApplicationState) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            description: description.into(),
+            discharge: Arc::new(discharge),
+        }
+    }
+
+    /// Whether `before -> after` satisfies this obligation's predicate.
+    pub fn is_discharged(&self, before: &ApplicationState, after: &ApplicationState) -> bool {
+        (self.discharge)(before, after)
+    }
+}
+
+/// Outcome of validating a single invariant against a transition.
+///
+/// `holds` is `Option<bool>` rather than `bool`: a single before/after pair
+/// is sometimes insufficient to decide an invariant outright (e.g. whether
+/// a rotated session ID will still be in effect on the *next* authenticated
+/// action). `None` defers the verdict onto the accompanying `obligations`,
+/// which a later transition must discharge.
+#[derive(Debug, Clone)]
+pub struct InvariantOutcome {
+    pub holds: Option<bool>,
+    pub evidence: Vec<Evidence>,
+    pub obligations: Vec<ProofObligation>,
+    /// Instance-specific detail (e.g. the exact discrepancy found) to
+    /// append to the invariant's static `violation_message`, rather than
+    /// leaving the caller to dig the number back out of `evidence`.
+    pub detail: Option<String>,
+}
+
+impl InvariantOutcome {
+    /// The invariant holds outright, with no open obligations.
+    pub fn holds() -> Self {
+        Self {
+            holds: Some(true),
+            evidence: Vec::new(),
+            obligations: Vec::new(),
+            detail: None,
+        }
+    }
+
+    /// The invariant is violated, with evidence explaining why.
+    pub fn violated(evidence: Vec<Evidence>) -> Self {
+        Self {
+            holds: Some(false),
+            evidence,
+            obligations: Vec::new(),
+            detail: None,
+        }
+    }
+
+    /// The invariant cannot be decided from this transition alone; it is
+    /// satisfied provisionally, pending discharge of `obligations`.
+    pub fn deferred(obligations: Vec<ProofObligation>) -> Self {
+        Self {
+            holds: None,
+            evidence: Vec::new(),
+            obligations,
+            detail: None,
+        }
+    }
+
+    /// Attach the exact, instance-specific discrepancy this verdict found,
+    /// appended onto `ViolationDetails::message` alongside the invariant's
+    /// static description.
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn is_violated(&self) -> bool {
+        self.holds == Some(false)
+    }
+
+    pub fn is_deferred(&self) -> bool {
+        self.holds.is_none()
+    }
+}
+
+/// When during the invariant's lifecycle it is allowed to execute. Cheap,
+/// always-safe checks (e.g. `INPUT_001`'s length bounds) run on every
+/// transition; deeper checks that would be too costly for a production hot
+/// path are confined to audit/CI runs, the way expensive assertions are
+/// only enabled in special builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActivationTier {
+    /// Runs on every transition, in every build. Must stay cheap.
+    Always,
+    /// Skipped on the production hot path; runs during audit/CI verification.
+    Audit,
+    /// Only runs in a debug/verification build, never in release.
+    DebugOnly,
+}
+
+impl Default for ActivationTier {
+    fn default() -> Self {
+        ActivationTier::Always
+    }
+}
+
+/// Which invariants a given `check_transition` run actually executes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecutionMode {
+    /// Production hot path: only `Always`-tier invariants run.
+    Release,
+    /// Verification/CI run: every tier runs.
+    Audit,
+}
+
+impl ActivationTier {
+    /// Whether an invariant on this tier executes under `mode`.
+    pub fn is_active_in(&self, mode: ExecutionMode) -> bool {
+        match (self, mode) {
+            (ActivationTier::Always, _) => true,
+            (_, ExecutionMode::Audit) => true,
+            (_, ExecutionMode::Release) => false,
+        }
+    }
+}
+
 /// Security invariant definition with full provenance
 #[derive(Clone)]
 pub struct SecurityInvariant {
@@ -41,10 +214,24 @@ pub struct SecurityInvariant {
     pub name: String,
     pub description: String,
     pub category: InvariantCategory,
-    pub validator: Arc<dyn Fn(&ApplicationState, &ApplicationState) -> bool + Send + Sync>,
+    pub validator: Arc<dyn Fn(&ApplicationState, &ApplicationState) -> InvariantOutcome + Send + Sync>,
     pub violation_message: String,
     /// Provenance: Why this invariant exists and what security principle it enforces
     pub provenance: InvariantProvenance,
+    /// When this invariant is allowed to execute. Defaults to `Always`.
+    pub tier: ActivationTier,
+    /// IDs of invariants this one subsumes: if this invariant is violated,
+    /// a violation of any invariant listed here is downstream noise rather
+    /// than an independent root cause.
+    pub implies: Vec<String>,
+    /// The `ApplicationState` subsystems this invariant's validator
+    /// actually reads. Empty (the default) means "unknown" — treated
+    /// conservatively as touching everything, so the invariant runs on
+    /// every transition. When non-empty,
+    /// `InvariantValidator::validate_transition` skips calling the
+    /// validator on transitions whose `StateDiff` doesn't touch any of
+    /// these fields, reporting it as checked but trivially satisfied.
+    pub touched_fields: Vec<StateField>,
 }
 
 /// Provenance information for an invariant - documents why it exists
@@ -79,7 +266,7 @@ impl SecurityInvariant {
         description: impl Into<String>,
         category: InvariantCategory,
         violation_message: impl Into<String>,
-        validator: impl Fn(&ApplicationState, &ApplicationState) -> bool + Send + Sync + 'static,
+        validator: impl Fn(&ApplicationState, &ApplicationState) -> InvariantOutcome + Send + Sync + 'static,
     ) -> Self {
         Self {
             id: id.into(),
@@ -89,6 +276,9 @@ impl SecurityInvariant {
             validator: Arc::new(validator),
             violation_message: violation_message.into(),
             provenance: InvariantProvenance::default(),
+            tier: ActivationTier::default(),
+            implies: Vec::new(),
+            touched_fields: Vec::new(),
         }
     }
 
@@ -100,7 +290,7 @@ impl SecurityInvariant {
         category: InvariantCategory,
         violation_message: impl Into<String>,
         provenance: InvariantProvenance,
-        validator: impl Fn(&ApplicationState, &ApplicationState) -> bool + Send + Sync + 'static,
+        validator: impl Fn(&ApplicationState, &ApplicationState) -> InvariantOutcome + Send + Sync + 'static,
     ) -> Self {
         Self {
             id: id.into(),
@@ -110,11 +300,38 @@ impl SecurityInvariant {
             validator: Arc::new(validator),
             violation_message: violation_message.into(),
             provenance,
+            tier: ActivationTier::default(),
+            implies: Vec::new(),
+            touched_fields: Vec::new(),
         }
     }
 
+    /// Assign an activation tier, overriding the `Always` default.
+    pub fn with_tier(mut self, tier: ActivationTier) -> Self {
+        self.tier = tier;
+        self
+    }
+
+    /// Declare that this invariant subsumes the given invariant IDs: if
+    /// this invariant is violated, a violation of any of them is reported
+    /// as a derived consequence rather than an independent root cause.
+    pub fn with_implies(mut self, implies: Vec<String>) -> Self {
+        self.implies = implies;
+        self
+    }
+
+    /// Declare the `ApplicationState` subsystems this invariant's
+    /// validator reads, so `validate_transition` can skip it on
+    /// transitions that didn't touch any of them. Only declare fields
+    /// `StateDiff` tracks (see `StateField`) — anything else makes the
+    /// invariant silently never re-checked.
+    pub fn with_touched_fields(mut self, touched_fields: Vec<StateField>) -> Self {
+        self.touched_fields = touched_fields;
+        self
+    }
+
     /// Validate state transition against this invariant
-    pub fn validate(&self, before: &ApplicationState, after: &ApplicationState) -> bool {
+    pub fn validate(&self, before: &ApplicationState, after: &ApplicationState) -> InvariantOutcome {
         (self.validator)(before, after)
     }
 }
@@ -123,6 +340,18 @@ impl SecurityInvariant {
 pub struct InvariantCatalog {
     invariants: HashMap<String, SecurityInvariant>,
     by_category: HashMap<InvariantCategory, Vec<String>>,
+    /// Implication graph: invariant ID -> IDs it subsumes.
+    implications: HashMap<String, Vec<String>>,
+    /// `StateField` region -> IDs of invariants that declared it in
+    /// `touched_fields`, built incrementally as invariants are registered
+    /// so `InvariantValidator::validate_transition` can look up "which
+    /// invariants care about this region" in O(touched regions) instead of
+    /// scanning every invariant in the catalog.
+    region_index: HashMap<StateField, Vec<String>>,
+    /// IDs of invariants with an empty `touched_fields` - unknown
+    /// dependencies are treated conservatively as "touches everything", so
+    /// these must run on every transition regardless of what changed.
+    always_run: Vec<String>,
 }
 
 impl Default for InvariantProvenance {
@@ -143,6 +372,9 @@ impl InvariantCatalog {
         let mut catalog = Self {
             invariants: HashMap::new(),
             by_category: HashMap::new(),
+            implications: HashMap::new(),
+            region_index: HashMap::new(),
+            always_run: Vec::new(),
         };
         catalog.register_default_invariants();
         catalog
@@ -185,17 +417,26 @@ impl InvariantCatalog {
                     if let Some(before_owner) = before.ownership.get(obj_id) {
                         if before_owner != owner_id {
                             if let Some(session) = &after.current_session {
-                                if session.user_id != *before_owner && 
-                                   !session.roles.iter().any(|r| r.0 == "admin") {
-                                    return false;
+                                if session.user_id != *before_owner
+                                    && !session.roles.iter().any(|r| r.0 == "admin")
+                                {
+                                    return InvariantOutcome::violated(vec![Evidence::new(
+                                        format!("ownership.{}", obj_id.0),
+                                        "object ownership changed to a non-admin session user who was not the prior owner",
+                                    )
+                                    .with_value(serde_json::json!({
+                                        "before_owner": before_owner.0,
+                                        "new_owner": owner_id.0,
+                                        "session_user": session.user_id.0,
+                                    }))]);
                                 }
                             }
                         }
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
-        ));
+        ).with_touched_fields(vec![StateField::Ownership]));
 
         self.register(SecurityInvariant::with_provenance(
             "AUTH_002",
@@ -217,22 +458,29 @@ impl InvariantCatalog {
                 last_reviewed: Some("2024-12".to_string()),
             },
             |before, after| {
-                if let (Some(before_session), Some(after_session)) = 
-                    (&before.current_session, &after.current_session) {
-                    let new_roles: Vec<_> = after_session.roles
-                        .difference(&before_session.roles)
-                        .collect();
-                    
+                if let (Some(before_session), Some(after_session)) =
+                    (&before.current_session, &after.current_session)
+                {
+                    let new_roles: Vec<_> = after_session.roles.difference(&before_session.roles).collect();
+
                     if !new_roles.is_empty() {
-                        return after.authorization_events.iter().any(|e| {
-                            e.event_type == "role_grant" && 
-                            new_roles.iter().any(|r| e.target_role.as_ref() == Some(*r))
+                        let granted = after.authorization_events.iter().any(|e| {
+                            e.event_type == "role_grant" && new_roles.iter().any(|r| e.target_role.as_ref() == Some(*r))
                         });
+                        if !granted {
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                "current_session.roles",
+                                "session gained roles with no matching role_grant authorization event",
+                            )
+                            .with_value(serde_json::json!({
+                                "new_roles": new_roles.iter().map(|r| r.0.clone()).collect::<Vec<_>>(),
+                            }))]);
+                        }
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
-        ));
+        ).with_touched_fields(vec![StateField::Session]));
 
         self.register(SecurityInvariant::with_provenance(
             "AUTH_003",
@@ -257,20 +505,22 @@ impl InvariantCatalog {
                 if let Some(session) = &after.current_session {
                     for (obj_id, _) in &after.data_objects {
                         if let Some(owner) = after.ownership.get(obj_id) {
-                            if owner != &session.user_id {
-                                // Accessing another user's object
-                                // Check if this is a new access (object wasn't accessed before)
-                                if !before.data_objects.contains_key(obj_id) {
-                                    // New object access - must be admin or have explicit permission
-                                    if !session.roles.iter().any(|r| r.0 == "admin" || r.0 == "moderator") {
-                                        return false;
-                                    }
-                                }
+                            if owner != &session.user_id && !before.data_objects.contains_key(obj_id)
+                                && !session.roles.iter().any(|r| r.0 == "admin" || r.0 == "moderator")
+                            {
+                                return InvariantOutcome::violated(vec![Evidence::new(
+                                    format!("data_objects.{}", obj_id.0),
+                                    "newly accessed object is owned by another user and session lacks admin/moderator role",
+                                )
+                                .with_value(serde_json::json!({
+                                    "owner": owner.0,
+                                    "session_user": session.user_id.0,
+                                }))]);
                             }
                         }
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
 
@@ -293,20 +543,24 @@ impl InvariantCatalog {
                 standards_reference: Some("OWASP ASVS 4.0.3 - V4.1.1 (Vertical Access Control)".to_string()),
                 last_reviewed: Some("2024-12".to_string()),
             },
-            |before, after| {
-                // Check for admin-only actions performed by non-admins
+            |_before, after| {
                 for event in &after.authorization_events {
                     if event.event_type == "admin_action" {
-                        if let Some(session) = &after.current_session {
-                            if !session.roles.iter().any(|r| r.0 == "admin") {
-                                return false;
-                            }
-                        } else {
-                            return false;
+                        let is_admin = after
+                            .current_session
+                            .as_ref()
+                            .map(|s| s.roles.iter().any(|r| r.0 == "admin"))
+                            .unwrap_or(false);
+                        if !is_admin {
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                "authorization_events",
+                                "admin_action event recorded without an admin-rolled session",
+                            )
+                            .with_value(serde_json::json!({ "event_type": event.event_type }))]);
                         }
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
     }
@@ -316,25 +570,88 @@ impl InvariantCatalog {
         self.register(SecurityInvariant::new(
             "MON_001",
             "Balance Conservation",
-            "Total system balance must be conserved (no money creation/destruction)",
+            "Total balance of each asset must be conserved (no money creation/destruction)",
             InvariantCategory::Monetary,
             "Balance conservation violation - money created or destroyed",
             |before, after| {
-                let total_before: i64 = before.balances.values()
-                    .map(|b| b.amount)
-                    .sum();
-                let total_after: i64 = after.balances.values()
-                    .map(|b| b.amount)
-                    .sum();
-                
-                let external_delta: i64 = after.financial_transactions.iter()
-                    .filter(|t| t.is_external)
-                    .map(|t| t.amount)
-                    .sum();
-                
-                total_after == total_before + external_delta
+                // Conservation is checked per currency: summing raw amounts
+                // across different currencies would conflate unrelated
+                // denominations and either mask real violations or flag
+                // none-existent ones.
+                let mut currencies: HashSet<Currency> = HashSet::new();
+                currencies.extend(before.balances.values().map(|b| b.currency));
+                currencies.extend(after.balances.values().map(|b| b.currency));
+
+                for currency in currencies {
+                    let total_before: MonetaryValue = before
+                        .balances
+                        .values()
+                        .filter(|b| b.currency == currency)
+                        .map(|b| b.amount.clone())
+                        .sum();
+                    let total_after: MonetaryValue = after
+                        .balances
+                        .values()
+                        .filter(|b| b.currency == currency)
+                        .map(|b| b.amount.clone())
+                        .sum();
+
+                    let external_delta: MonetaryValue = after
+                        .financial_transactions
+                        .iter()
+                        .filter(|t| t.is_external && t.currency == currency)
+                        .map(|t| t.amount.clone())
+                        .sum();
+
+                    // A declared cross-asset conversion moves `amount` out of
+                    // `currency` and `converted_amount` into
+                    // `converted_currency` without creating or destroying
+                    // anything - net both legs out of the per-currency total
+                    // before comparing, so a legitimate conversion (the exact
+                    // feature MON_005 validates the correctness of) isn't
+                    // flagged here as money appearing or vanishing. Only
+                    // internal transactions are netted this way; an external
+                    // leg is already accounted for by `external_delta` above.
+                    let conversion_delta: MonetaryValue = after
+                        .financial_transactions
+                        .iter()
+                        .filter(|t| !t.is_external)
+                        .filter_map(|t| match (&t.converted_amount, &t.converted_currency) {
+                            (Some(converted_amount), Some(converted_currency)) if *converted_currency != t.currency => {
+                                if t.currency == currency {
+                                    Some(-t.amount.clone())
+                                } else if *converted_currency == currency {
+                                    Some(converted_amount.clone())
+                                } else {
+                                    None
+                                }
+                            }
+                            _ => None,
+                        })
+                        .sum();
+
+                    let expected_after = total_before.clone() + external_delta.clone() + conversion_delta;
+                    if total_after != expected_after {
+                        let discrepancy = total_after.clone() - expected_after;
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("balances[{:?}]", currency),
+                            "sum of account balances for this currency changed by more than the recorded external transfers",
+                        )
+                        .with_value(serde_json::json!({
+                            "currency": format!("{:?}", currency),
+                            "total_before": total_before.0.to_string(),
+                            "total_after": total_after.0.to_string(),
+                            "external_delta": external_delta.0.to_string(),
+                        }))])
+                        .with_detail(format!(
+                            "{:?}: expected total {} after external transfers, found {} (discrepancy of {})",
+                            currency, expected_after.0, total_after.0, discrepancy.0
+                        ));
+                    }
+                }
+                InvariantOutcome::holds()
             },
-        ));
+        ).with_touched_fields(vec![StateField::Balances, StateField::FinancialTransactions]));
 
         self.register(SecurityInvariant::new(
             "MON_002",
@@ -344,11 +661,15 @@ impl InvariantCatalog {
             "Negative balance detected without overdraft permission",
             |_before, after| {
                 for (account_id, balance) in &after.balances {
-                    if balance.amount < 0 && !after.overdraft_permissions.contains(account_id) {
-                        return false;
+                    if balance.amount.is_negative() && !after.overdraft_permissions.contains(account_id) {
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("balances.{}", account_id.0),
+                            "account balance is negative with no overdraft permission on record",
+                        )
+                        .with_value(serde_json::json!({ "amount": balance.amount.0.to_string() }))]);
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
 
@@ -361,20 +682,30 @@ impl InvariantCatalog {
             |before, after| {
                 for tx in &after.financial_transactions {
                     if let (Some(from), Some(to)) = (&tx.from_account, &tx.to_account) {
-                        let from_delta = after.balances.get(from).map(|b| b.amount).unwrap_or(0)
-                            - before.balances.get(from).map(|b| b.amount).unwrap_or(0);
-                        let to_delta = after.balances.get(to).map(|b| b.amount).unwrap_or(0)
-                            - before.balances.get(to).map(|b| b.amount).unwrap_or(0);
-                        
-                        // Deltas should be equal and opposite (ignoring sign)
+                        let from_after = after.balances.get(from).map(|b| b.amount.clone()).unwrap_or_default();
+                        let from_before = before.balances.get(from).map(|b| b.amount.clone()).unwrap_or_default();
+                        let from_delta = from_after - from_before;
+
+                        let to_after = after.balances.get(to).map(|b| b.amount.clone()).unwrap_or_default();
+                        let to_before = before.balances.get(to).map(|b| b.amount.clone()).unwrap_or_default();
+                        let to_delta = to_after - to_before;
+
+                        // Deltas should be equal and opposite (ignoring sign), with no rounding drift
                         if from_delta.abs() != to_delta.abs() {
-                            return false;
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                format!("financial_transactions.{}", tx.id),
+                                "debit and credit legs of a transfer moved by different magnitudes",
+                            )
+                            .with_value(serde_json::json!({
+                                "from_delta": from_delta.0.to_string(),
+                                "to_delta": to_delta.0.to_string(),
+                            }))]);
                         }
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
-        ));
+        ).with_touched_fields(vec![StateField::Balances, StateField::FinancialTransactions]));
 
         self.register(SecurityInvariant::new(
             "MON_004",
@@ -386,20 +717,130 @@ impl InvariantCatalog {
                 // Check if any account went below what it should have
                 for (account_id, after_balance) in &after.balances {
                     if let Some(before_balance) = before.balances.get(account_id) {
-                        let total_debits: i64 = after.financial_transactions.iter()
+                        let total_debits: MonetaryValue = after
+                            .financial_transactions
+                            .iter()
                             .filter(|t| t.from_account.as_ref() == Some(account_id))
-                            .map(|t| t.amount)
+                            .map(|t| t.amount.clone())
                             .sum();
-                        
-                        let expected_balance = before_balance.amount - total_debits;
+
+                        let expected_balance = before_balance.amount.clone() - total_debits.clone();
                         if after_balance.amount < expected_balance {
-                            return false;
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                format!("balances.{}", account_id.0),
+                                "account balance fell below what recorded debits allow",
+                            )
+                            .with_value(serde_json::json!({
+                                "after_balance": after_balance.amount.0.to_string(),
+                                "total_debits": total_debits.0.to_string(),
+                            }))]);
                         }
                     }
                 }
-                true
+                InvariantOutcome::holds()
+            },
+        ).with_touched_fields(vec![StateField::Balances, StateField::FinancialTransactions]));
+
+        self.register(SecurityInvariant::new(
+            "MON_005",
+            "Cross-Asset Conversion Integrity",
+            "Cross-asset transfers must use a rate from the transition's declared exchange-rate table, applied exactly",
+            InvariantCategory::Monetary,
+            "Cross-asset transaction used an undeclared or incorrectly applied exchange rate",
+            |_before, after| {
+                // Rounding in real conversion pipelines means the credited
+                // leg need not match the rate-implied amount to the exact
+                // unit; this allows up to one millionth of a unit of drift.
+                let tolerance = MonetaryValue::from_minor_units(1, 6);
+
+                for tx in &after.financial_transactions {
+                    let (converted_amount, converted_currency) = match (&tx.converted_amount, &tx.converted_currency) {
+                        (Some(amount), Some(currency)) => (amount, currency),
+                        _ => continue,
+                    };
+
+                    if *converted_currency == tx.currency {
+                        continue;
+                    }
+
+                    let declared_rate = after
+                        .exchange_rates
+                        .iter()
+                        .find(|r| r.from == tx.currency && r.to == *converted_currency);
+
+                    let rate = match declared_rate {
+                        Some(rate) => rate,
+                        None => {
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                format!("financial_transactions.{}", tx.id),
+                                "cross-asset transaction references no declared exchange rate",
+                            )
+                            .with_value(serde_json::json!({
+                                "from": format!("{:?}", tx.currency),
+                                "to": format!("{:?}", converted_currency),
+                            }))]);
+                        }
+                    };
+
+                    let expected = tx.amount.clone() * rate.rate.clone();
+                    let drift = (expected.clone() - converted_amount.clone()).abs();
+
+                    if drift > tolerance {
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("financial_transactions.{}", tx.id),
+                            "declared exchange rate does not reconcile the debited and credited amounts",
+                        )
+                        .with_value(serde_json::json!({
+                            "expected_credit": expected.0.to_string(),
+                            "actual_credit": converted_amount.0.to_string(),
+                            "rate": rate.rate.0.to_string(),
+                        }))]);
+                    }
+                }
+                InvariantOutcome::holds()
             },
         ));
+
+        self.register(SecurityInvariant::new(
+            "MON_006",
+            "Salami Slicing Detection",
+            "Internal transfers finer than the currency's minor unit can skim value in increments too small for any ledger view to show",
+            InvariantCategory::Monetary,
+            "Internal transaction amount is finer than this currency's minor unit - possible salami slicing",
+            |before, after| {
+                // Only the transactions new to this transition: append-only,
+                // so `before`'s entries are always a prefix of `after`'s.
+                let new_txs = if after.financial_transactions.len() > before.financial_transactions.len() {
+                    &after.financial_transactions[before.financial_transactions.len()..]
+                } else {
+                    &[][..]
+                };
+
+                for tx in new_txs {
+                    if tx.is_external {
+                        continue;
+                    }
+
+                    let minor_unit = MonetaryValue::from_minor_units(1, tx.currency.minor_unit_decimals());
+                    if !tx.amount.is_zero() && tx.amount.abs() < minor_unit {
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("financial_transactions.{}", tx.id),
+                            "internal transaction amount is smaller than one minor unit of its currency",
+                        )
+                        .with_value(serde_json::json!({
+                            "currency": format!("{:?}", tx.currency),
+                            "amount": tx.amount.0.to_string(),
+                            "minor_unit": minor_unit.0.to_string(),
+                        }))])
+                        .with_detail(format!(
+                            "transaction {} moved {} {:?}, below one minor unit ({})",
+                            tx.id, tx.amount.0, tx.currency, minor_unit.0
+                        ));
+                    }
+                }
+                InvariantOutcome::holds()
+            },
+        ).with_touched_fields(vec![StateField::FinancialTransactions]));
     }
 
     /// Register workflow invariants
@@ -414,13 +855,24 @@ impl InvariantCatalog {
                 for (session_id, after_step) in &after.workflow_positions {
                     if let Some(before_step) = before.workflow_positions.get(session_id) {
                         if after_step.step_index > before_step.step_index + 1 {
-                            return false;
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                format!("workflow_positions.{}", session_id.0),
+                                "workflow step advanced by more than one position",
+                            )
+                            .with_value(serde_json::json!({
+                                "before_step": before_step.step_index,
+                                "after_step": after_step.step_index,
+                            }))]);
                         }
                     } else if after_step.step_index > 1 {
-                        return false;
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("workflow_positions.{}", session_id.0),
+                            "workflow appeared at a step beyond the first with no prior position",
+                        )
+                        .with_value(serde_json::json!({ "after_step": after_step.step_index }))]);
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
 
@@ -433,10 +885,13 @@ impl InvariantCatalog {
             |_before, after| {
                 for completion in &after.workflow_completions {
                     if completion.is_critical && !completion.all_steps_completed {
-                        return false;
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("workflow_completions.{}", completion.workflow_id),
+                            "critical workflow recorded as complete without all steps completed",
+                        )]);
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
 
@@ -451,15 +906,19 @@ impl InvariantCatalog {
                     // Completed steps should be sequential
                     let mut sorted_steps = completion.completed_steps.clone();
                     sorted_steps.sort();
-                    
+
                     for (i, step) in sorted_steps.iter().enumerate() {
                         if *step != (i as u32) && *step != (i as u32 + 1) {
                             // Gap in completed steps
-                            return false;
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                format!("workflow_completions.{}", completion.workflow_id),
+                                "gap detected in the recorded sequence of completed steps",
+                            )
+                            .with_value(serde_json::json!({ "completed_steps": sorted_steps }))]);
                         }
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
     }
@@ -475,12 +934,15 @@ impl InvariantCatalog {
             |_before, after| {
                 for decision in &after.trust_decisions {
                     if decision.based_on_client_input && !decision.input_validated {
-                        return false;
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("trust_decisions.{}", decision.decision_type),
+                            "trust decision consumed unvalidated client input",
+                        )]);
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
-        ));
+        ).with_touched_fields(vec![StateField::TrustDecisions]));
 
         self.register(SecurityInvariant::new(
             "TRUST_002",
@@ -490,17 +952,20 @@ impl InvariantCatalog {
             "Security decision made without server-side validation",
             |_before, after| {
                 for decision in &after.trust_decisions {
-                    if decision.decision_type.contains("security") || 
-                       decision.decision_type.contains("auth") ||
-                       decision.decision_type.contains("access") {
-                        if !decision.input_validated {
-                            return false;
-                        }
+                    if (decision.decision_type.contains("security")
+                        || decision.decision_type.contains("auth")
+                        || decision.decision_type.contains("access"))
+                        && !decision.input_validated
+                    {
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("trust_decisions.{}", decision.decision_type),
+                            "security-critical decision made without server-side validation",
+                        )]);
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
-        ));
+        ).with_touched_fields(vec![StateField::TrustDecisions]));
     }
 
     /// Register data integrity invariants
@@ -519,17 +984,23 @@ impl InvariantCatalog {
                                 let owner = after.ownership.get(obj_id);
                                 let is_owner = owner.map(|o| o == &session.user_id).unwrap_or(false);
                                 let is_admin = session.roles.iter().any(|r| r.0 == "admin");
-                                
+
                                 if !is_owner && !is_admin {
-                                    return false;
+                                    return InvariantOutcome::violated(vec![Evidence::new(
+                                        format!("data_objects.{}", obj_id.0),
+                                        "data object modified by a session that is neither the owner nor an admin",
+                                    )]);
                                 }
                             } else {
-                                return false;
+                                return InvariantOutcome::violated(vec![Evidence::new(
+                                    format!("data_objects.{}", obj_id.0),
+                                    "data object modified with no active session",
+                                )]);
                             }
                         }
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
 
@@ -543,11 +1014,18 @@ impl InvariantCatalog {
                 for (obj_id, after_data) in &after.data_objects {
                     if let Some(before_data) = before.data_objects.get(obj_id) {
                         if after_data.version < before_data.version {
-                            return false;
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                format!("data_objects.{}", obj_id.0),
+                                "data object version decreased",
+                            )
+                            .with_value(serde_json::json!({
+                                "before_version": before_data.version,
+                                "after_version": after_data.version,
+                            }))]);
                         }
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
     }
@@ -562,18 +1040,39 @@ impl InvariantCatalog {
             "Session fixation vulnerability - session ID not rotated after auth",
             |before, after| {
                 if before.current_session.is_none() && after.current_session.is_some() {
-                    return true;
+                    return InvariantOutcome::holds();
                 }
-                
-                if let (Some(before_session), Some(after_session)) = 
-                    (&before.current_session, &after.current_session) {
+
+                if let (Some(before_session), Some(after_session)) = (&before.current_session, &after.current_session)
+                {
                     if !before_session.authenticated && after_session.authenticated {
-                        return before_session.session_id != after_session.session_id;
+                        if before_session.session_id == after_session.session_id {
+                            return InvariantOutcome::violated(vec![Evidence::new(
+                                "current_session.session_id",
+                                "session ID was not rotated on authentication",
+                            )]);
+                        }
+
+                        // A single before/after pair cannot prove the rotated ID stays
+                        // in effect - that can only be shown by the *next* authenticated
+                        // transition still using it. Defer, and check on the transition after.
+                        let rotated_id = after_session.session_id.clone();
+                        return InvariantOutcome::deferred(vec![ProofObligation::new(
+                            "SESS_001_ROTATION_HOLDS",
+                            "rotated session ID must still be the one in use on the next authenticated transition",
+                            move |_before, after| {
+                                after
+                                    .current_session
+                                    .as_ref()
+                                    .map(|s| !s.authenticated || s.session_id == rotated_id)
+                                    .unwrap_or(true)
+                            },
+                        )]);
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
-        ));
+        ).with_touched_fields(vec![StateField::Session]));
 
         self.register(SecurityInvariant::new(
             "SESS_002",
@@ -582,16 +1081,24 @@ impl InvariantCatalog {
             InvariantCategory::SessionManagement,
             "Session user binding violation - session transferred",
             |before, after| {
-                if let (Some(before_session), Some(after_session)) = 
-                    (&before.current_session, &after.current_session) {
-                    if before_session.session_id == after_session.session_id {
-                        // Same session - user must not change
-                        return before_session.user_id == after_session.user_id;
+                if let (Some(before_session), Some(after_session)) = (&before.current_session, &after.current_session)
+                {
+                    if before_session.session_id == after_session.session_id
+                        && before_session.user_id != after_session.user_id
+                    {
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            "current_session.user_id",
+                            "same session ID now bound to a different user",
+                        )
+                        .with_value(serde_json::json!({
+                            "before_user": before_session.user_id.0,
+                            "after_user": after_session.user_id.0,
+                        }))]);
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
-        ));
+        ).with_touched_fields(vec![StateField::Session]));
     }
 
     /// Register input validation invariants
@@ -604,17 +1111,23 @@ impl InvariantCatalog {
             "Input length bounds violation detected",
             |_before, after| {
                 // Check data objects for reasonable sizes
-                for (_, data) in &after.data_objects {
+                for (obj_id, data) in &after.data_objects {
                     // Content hash should be reasonable length (SHA-256 = 64 hex chars)
                     if data.content_hash.len() > 128 {
-                        return false;
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("data_objects.{}.content_hash", obj_id.0),
+                            "content hash exceeds the maximum expected length",
+                        )]);
                     }
                     // Data type should be reasonable
                     if data.data_type.len() > 256 {
-                        return false;
+                        return InvariantOutcome::violated(vec![Evidence::new(
+                            format!("data_objects.{}.data_type", obj_id.0),
+                            "data type string exceeds the maximum expected length",
+                        )]);
                     }
                 }
-                true
+                InvariantOutcome::holds()
             },
         ));
     }
@@ -623,12 +1136,41 @@ impl InvariantCatalog {
     pub fn register(&mut self, invariant: SecurityInvariant) {
         let id = invariant.id.clone();
         let category = invariant.category;
-        
+
+        if !invariant.implies.is_empty() {
+            self.implications.insert(id.clone(), invariant.implies.clone());
+        }
+
+        if invariant.touched_fields.is_empty() {
+            self.always_run.push(id.clone());
+        } else {
+            for field in &invariant.touched_fields {
+                self.region_index.entry(*field).or_insert_with(Vec::new).push(id.clone());
+            }
+        }
+
         self.invariants.insert(id.clone(), invariant);
-        self.by_category
-            .entry(category)
-            .or_insert_with(Vec::new)
-            .push(id);
+        self.by_category.entry(category).or_insert_with(Vec::new).push(id);
+    }
+
+    /// Invariants that actually need evaluating on a transition whose diff
+    /// touched `changed`: every invariant that declared one of `changed` in
+    /// `touched_fields`, plus every invariant with no declared dependencies
+    /// (which must always run), each looked up via `region_index`/
+    /// `always_run` rather than scanning the whole catalog. Invariants
+    /// declaring more than one touched region that all changed are
+    /// returned once, not once per matching region.
+    pub fn relevant_for(&self, changed: &HashSet<StateField>) -> Vec<&SecurityInvariant> {
+        let mut ids: HashSet<&str> = HashSet::new();
+
+        for field in changed {
+            if let Some(dependents) = self.region_index.get(field) {
+                ids.extend(dependents.iter().map(String::as_str));
+            }
+        }
+        ids.extend(self.always_run.iter().map(String::as_str));
+
+        ids.into_iter().filter_map(|id| self.invariants.get(id)).collect()
     }
 
     /// Get an invariant by ID
@@ -636,6 +1178,11 @@ impl InvariantCatalog {
         self.invariants.get(id)
     }
 
+    /// IDs this invariant implies, if any were declared.
+    pub(crate) fn implications_for(&self, id: &str) -> Option<&[String]> {
+        self.implications.get(id).map(|v| v.as_slice())
+    }
+
     /// Get all invariants in a category
     pub fn get_by_category(&self, category: InvariantCategory) -> Vec<&SecurityInvariant> {
         self.by_category
@@ -649,6 +1196,24 @@ impl InvariantCatalog {
         self.invariants.values()
     }
 
+    /// Get all invariants in a category whose tier would actually execute
+    /// under `mode`.
+    pub fn get_by_category_for_mode(
+        &self,
+        category: InvariantCategory,
+        mode: ExecutionMode,
+    ) -> Vec<&SecurityInvariant> {
+        self.get_by_category(category)
+            .into_iter()
+            .filter(|invariant| invariant.tier.is_active_in(mode))
+            .collect()
+    }
+
+    /// Get every invariant whose tier would actually execute under `mode`.
+    pub fn all_for_mode(&self, mode: ExecutionMode) -> impl Iterator<Item = &SecurityInvariant> {
+        self.invariants.values().filter(move |invariant| invariant.tier.is_active_in(mode))
+    }
+
     /// Get total count of invariants
     pub fn count(&self) -> usize {
         self.invariants.len()
@@ -679,7 +1244,7 @@ mod tests {
     #[test]
     fn test_invariant_categories() {
         let catalog = InvariantCatalog::new();
-        
+
         assert!(catalog.count_by_category(InvariantCategory::Authorization) > 0);
         assert!(catalog.count_by_category(InvariantCategory::Monetary) > 0);
         assert!(catalog.count_by_category(InvariantCategory::Workflow) > 0);
@@ -690,17 +1255,228 @@ mod tests {
     fn test_custom_invariant_registration() {
         let mut catalog = InvariantCatalog::new();
         let initial_count = catalog.count();
-        
+
         catalog.register(SecurityInvariant::new(
             "CUSTOM_001",
             "Custom Test Invariant",
             "Test invariant for unit testing",
             InvariantCategory::Custom,
             "Custom invariant violated",
-            |_, _| true,
+            |_, _| InvariantOutcome::holds(),
         ));
-        
+
         assert_eq!(catalog.count(), initial_count + 1);
         assert!(catalog.get("CUSTOM_001").is_some());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_relevant_for_includes_only_dependents_of_the_changed_region_plus_always_run() {
+        let catalog = InvariantCatalog::new();
+        let changed = HashSet::from([StateField::Session]);
+
+        let relevant = catalog.relevant_for(&changed);
+        let relevant_ids: HashSet<&str> = relevant.iter().map(|i| i.id.as_str()).collect();
+
+        // SESS_001 declared Session, so it's pulled in by the index.
+        assert!(relevant_ids.contains("SESS_001"));
+        // AUTH_003 declared no touched_fields, so it always runs.
+        assert!(relevant_ids.contains("AUTH_003"));
+        // MON_001 only declared Balances/FinancialTransactions, which didn't change.
+        assert!(!relevant_ids.contains("MON_001"));
+    }
+
+    #[test]
+    fn test_session_fixation_defers_rather_than_fails_immediately() {
+        let catalog = InvariantCatalog::new();
+        let invariant = catalog.get("SESS_001").unwrap();
+
+        let mut before = ApplicationState::default();
+        before.current_session = Some(crate::state::SessionState {
+            session_id: SessionId("sess-1".to_string()),
+            user_id: UserId("alice".to_string()),
+            roles: Default::default(),
+            authenticated: false,
+            created_at: chrono::Utc::now(),
+            last_activity: chrono::Utc::now(),
+        });
+
+        let mut after = before.clone();
+        after.current_session = Some(crate::state::SessionState {
+            session_id: SessionId("sess-2".to_string()),
+            user_id: UserId("alice".to_string()),
+            roles: Default::default(),
+            authenticated: true,
+            created_at: chrono::Utc::now(),
+            last_activity: chrono::Utc::now(),
+        });
+
+        let outcome = invariant.validate(&before, &after);
+        assert!(outcome.is_deferred());
+        assert_eq!(outcome.obligations.len(), 1);
+    }
+
+    #[test]
+    fn test_balance_conservation_ignores_unrelated_currencies() {
+        let catalog = InvariantCatalog::new();
+        let invariant = catalog.get("MON_001").unwrap();
+
+        let before = ApplicationState::default();
+        let mut after = ApplicationState::default();
+        // A fresh USD balance and a fresh BTC balance appear at once, with
+        // no matching external transactions - naive summation across
+        // currencies could mistake this for conserved (equal and opposite)
+        // when it is really two independent violations.
+        after.balances.insert(AccountId("usd_acc".to_string()), Balance::new(100, Currency::USD));
+        after.balances.insert(AccountId("btc_acc".to_string()), Balance::new(-100, Currency::BTC));
+
+        let outcome = invariant.validate(&before, &after);
+        assert!(outcome.is_violated());
+    }
+
+    #[test]
+    fn test_balance_conservation_holds_across_a_legitimate_cross_asset_transfer() {
+        let catalog = InvariantCatalog::new();
+        let invariant = catalog.get("MON_001").unwrap();
+
+        let mut before = ApplicationState::default();
+        before.balances.insert(AccountId("usd_acc".to_string()), Balance::new(100, Currency::USD));
+        before.balances.insert(AccountId("btc_acc".to_string()), Balance::new(0, Currency::BTC));
+
+        let mut after = before.clone();
+        after.balances.insert(AccountId("usd_acc".to_string()), Balance::new(0, Currency::USD));
+        after.balances.insert(AccountId("btc_acc".to_string()), Balance::new(1, Currency::BTC));
+        after.financial_transactions.push(crate::state::FinancialTransaction {
+            id: "tx_1".to_string(),
+            from_account: Some(AccountId("usd_acc".to_string())),
+            to_account: Some(AccountId("btc_acc".to_string())),
+            amount: MonetaryValue::from_integer(100),
+            currency: Currency::USD,
+            converted_amount: Some(MonetaryValue::from_integer(1)),
+            converted_currency: Some(Currency::BTC),
+            is_external: false,
+            timestamp: chrono::Utc::now(),
+        });
+
+        // Both legs of the declared conversion are netted out, so this
+        // doesn't read as USD vanishing and BTC appearing from nowhere.
+        assert!(!invariant.validate(&before, &after).is_violated());
+    }
+
+    #[test]
+    fn test_cross_asset_conversion_requires_a_declared_rate() {
+        let catalog = InvariantCatalog::new();
+        let invariant = catalog.get("MON_005").unwrap();
+
+        let before = ApplicationState::default();
+        let mut after = ApplicationState::default();
+        after.financial_transactions.push(crate::state::FinancialTransaction {
+            id: "tx_1".to_string(),
+            from_account: Some(AccountId("a".to_string())),
+            to_account: Some(AccountId("b".to_string())),
+            amount: MonetaryValue::from_integer(100),
+            currency: Currency::USD,
+            converted_amount: Some(MonetaryValue::from_integer(1)),
+            converted_currency: Some(Currency::BTC),
+            is_external: false,
+            timestamp: chrono::Utc::now(),
+        });
+
+        // No exchange rate declared at all - undeclared rate.
+        assert!(invariant.validate(&before, &after).is_violated());
+
+        // Declared rate that does not reconcile the two legs.
+        after.exchange_rates.push(ExchangeRate {
+            from: Currency::USD,
+            to: Currency::BTC,
+            rate: MonetaryValue::from_minor_units(1, 6),
+        });
+        assert!(invariant.validate(&before, &after).is_violated());
+
+        // Declared rate that exactly reconciles the two legs holds.
+        after.exchange_rates.clear();
+        after.exchange_rates.push(ExchangeRate {
+            from: Currency::USD,
+            to: Currency::BTC,
+            rate: MonetaryValue::from_minor_units(1, 2),
+        });
+        assert!(!invariant.validate(&before, &after).is_violated());
+    }
+
+    fn sub_minor_unit_tx(is_external: bool) -> crate::state::FinancialTransaction {
+        crate::state::FinancialTransaction {
+            id: "tx_slice".to_string(),
+            from_account: Some(AccountId("a".to_string())),
+            to_account: Some(AccountId("b".to_string())),
+            amount: MonetaryValue::from_minor_units(1, 4), // $0.0001 - finer than USD's cent
+            currency: Currency::USD,
+            converted_amount: None,
+            converted_currency: None,
+            is_external,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_salami_slicing_flags_a_sub_minor_unit_internal_transaction() {
+        let catalog = InvariantCatalog::new();
+        let invariant = catalog.get("MON_006").unwrap();
+
+        let before = ApplicationState::default();
+        let mut after = before.clone();
+        after.financial_transactions.push(sub_minor_unit_tx(false));
+
+        let outcome = invariant.validate(&before, &after);
+        assert!(outcome.is_violated());
+    }
+
+    #[test]
+    fn test_salami_slicing_ignores_an_external_transaction_of_the_same_size() {
+        let catalog = InvariantCatalog::new();
+        let invariant = catalog.get("MON_006").unwrap();
+
+        let before = ApplicationState::default();
+        let mut after = before.clone();
+        after.financial_transactions.push(sub_minor_unit_tx(true));
+
+        let outcome = invariant.validate(&before, &after);
+        assert!(!outcome.is_violated());
+    }
+
+    #[test]
+    fn test_salami_slicing_ignores_a_normal_sized_internal_transaction() {
+        let catalog = InvariantCatalog::new();
+        let invariant = catalog.get("MON_006").unwrap();
+
+        let before = ApplicationState::default();
+        let mut after = before.clone();
+        after.financial_transactions.push(crate::state::FinancialTransaction {
+            id: "tx_normal".to_string(),
+            from_account: Some(AccountId("a".to_string())),
+            to_account: Some(AccountId("b".to_string())),
+            amount: MonetaryValue::from_integer(5),
+            currency: Currency::USD,
+            converted_amount: None,
+            converted_currency: None,
+            is_external: false,
+            timestamp: chrono::Utc::now(),
+        });
+
+        let outcome = invariant.validate(&before, &after);
+        assert!(!outcome.is_violated());
+    }
+
+    #[test]
+    fn test_salami_slicing_only_considers_transactions_new_since_before() {
+        let catalog = InvariantCatalog::new();
+        let invariant = catalog.get("MON_006").unwrap();
+
+        // The sliced transaction was already present in `before`, so this
+        // transition didn't introduce it and shouldn't be blamed for it.
+        let mut before = ApplicationState::default();
+        before.financial_transactions.push(sub_minor_unit_tx(false));
+        let after = before.clone();
+
+        let outcome = invariant.validate(&before, &after);
+        assert!(!outcome.is_violated());
+    }
+}
@@ -1,12 +1,77 @@
 //! Coverage Tracker - Maps invariant coverage and identifies gaps
 
-use super::catalog::{InvariantCatalog, InvariantCategory};
+use super::catalog::{InvariantCatalog, InvariantCategory, SecurityInvariant};
+use super::multi_catalog::MultiCatalog;
 use crate::state::StateTransition;
+use crate::telemetry::Telemetry;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::sync::Arc;
 use parking_lot::RwLock;
 
+/// Default number of invariant ids batched into a single `CoverageChunk`.
+const DEFAULT_CHUNK_SIZE: usize = 256;
+
+/// One fixed-size batch of recorded-check ids plus a checksum over them, so
+/// a snapshot can stream incrementally and a corrupt chunk doesn't take
+/// down the rest of the snapshot with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageChunk {
+    pub invariant_ids: Vec<String>,
+    pub checksum: String,
+}
+
+/// A resumable, chunked snapshot of `CoverageTracker` state: which
+/// invariants had been checked, plus a fingerprint of the catalog they were
+/// checked against so a restore can detect a catalog that has since drifted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageSnapshot {
+    pub catalog_fingerprint: String,
+    pub chunks: Vec<CoverageChunk>,
+}
+
+/// Outcome of `CoverageTracker::restore`: the rebuilt tracker, plus the
+/// indices of any chunks whose checksum didn't match and were therefore
+/// dropped rather than merged in.
+#[derive(Debug)]
+pub struct RestoreOutcome {
+    pub tracker: CoverageTracker,
+    pub corrupt_chunks: Vec<usize>,
+}
+
+/// Why a snapshot restore was refused outright.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoverageRestoreError {
+    /// The snapshot was taken against a different catalog: its invariant
+    /// set no longer matches, so merging its recorded checks in would
+    /// silently miscount coverage rather than fail loudly.
+    CatalogFingerprintMismatch,
+}
+
+impl fmt::Display for CoverageRestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoverageRestoreError::CatalogFingerprintMismatch => {
+                write!(f, "snapshot catalog fingerprint does not match the restore catalog")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CoverageRestoreError {}
+
+fn digest_ids<'a>(ids: impl Iterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    for id in ids {
+        hasher.update(id.as_bytes());
+        hasher.update(b"\0");
+    }
+    hex::encode(hasher.finalize())
+}
+
 /// Coverage gap information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoverageGap {
@@ -52,6 +117,12 @@ pub struct CoverageTracker {
     covered_invariants: RwLock<HashSet<String>>,
     observed_transitions: RwLock<Vec<TransitionType>>,
     unclassified_transitions: RwLock<Vec<String>>,
+    /// Only invariants whose `id` or `name` matches are in scope, if set.
+    include: Option<Regex>,
+    /// Invariants whose `id` or `name` matches are dropped from scope even
+    /// if `include` also matched them.
+    exclude: Option<Regex>,
+    telemetry: Telemetry,
 }
 
 /// Type of state transition observed
@@ -64,11 +135,49 @@ pub struct TransitionType {
 
 impl CoverageTracker {
     pub fn new(catalog: Arc<InvariantCatalog>) -> Self {
+        Self::with_filters(catalog, None, None)
+    }
+
+    /// Scope coverage reporting to a subset of the catalog: an invariant is
+    /// in scope when its `id` or `name` matches `include` (every invariant,
+    /// if `include` is `None`) and does not match `exclude`. `exclude`
+    /// always wins - an invariant matching both is dropped, the same
+    /// "inverse pattern" rule as an include/exclude filter pair anywhere
+    /// else in the codebase - so a deliberately out-of-scope invariant
+    /// never shows up in `covered_invariants`, the totals, or `gaps`.
+    pub fn with_filters(catalog: Arc<InvariantCatalog>, include: Option<Regex>, exclude: Option<Regex>) -> Self {
         Self {
             catalog,
             covered_invariants: RwLock::new(HashSet::new()),
             observed_transitions: RwLock::new(Vec::new()),
             unclassified_transitions: RwLock::new(Vec::new()),
+            include,
+            exclude,
+            telemetry: Telemetry::init(),
+        }
+    }
+
+    /// Build a tracker scoped to whichever era of `multi` is active at
+    /// `context` (e.g. a block height or protocol version), so coverage
+    /// and gaps are computed against the correct era's invariant set
+    /// rather than whatever catalog happens to be current.
+    pub fn for_era(multi: &MultiCatalog, context: u64) -> Self {
+        Self::new(multi.active_at(context))
+    }
+
+    /// Whether `invariant` is in scope under this tracker's filters.
+    fn in_scope(&self, invariant: &SecurityInvariant) -> bool {
+        let matches = |re: &Regex| re.is_match(&invariant.id) || re.is_match(&invariant.name);
+
+        if let Some(exclude) = &self.exclude {
+            if matches(exclude) {
+                return false;
+            }
+        }
+
+        match &self.include {
+            Some(include) => matches(include),
+            None => true,
         }
     }
 
@@ -93,14 +202,19 @@ impl CoverageTracker {
     /// Record a transition that couldn't be classified under any invariant
     pub fn record_unclassified(&self, description: String) {
         self.unclassified_transitions.write().push(description);
+        self.telemetry.record_unclassified_transition();
     }
 
     /// Generate a coverage report
     pub fn generate_report(&self) -> CoverageReport {
         let covered = self.covered_invariants.read();
-        let total = self.catalog.count();
-        let covered_count = covered.len();
-        
+        let total = self.catalog.all().filter(|inv| self.in_scope(inv)).count();
+        let covered_count = self
+            .catalog
+            .all()
+            .filter(|inv| self.in_scope(inv) && covered.contains(&inv.id))
+            .count();
+
         let coverage_percentage = if total > 0 {
             (covered_count as f64 / total as f64) * 100.0
         } else {
@@ -121,13 +235,18 @@ impl CoverageTracker {
         ];
 
         for category in categories {
-            let invariants = self.catalog.get_by_category(category);
+            let invariants: Vec<_> = self
+                .catalog
+                .get_by_category(category)
+                .into_iter()
+                .filter(|inv| self.in_scope(inv))
+                .collect();
             let total_in_cat = invariants.len();
             let covered_in_cat: Vec<_> = invariants
                 .iter()
                 .filter(|inv| covered.contains(&inv.id))
                 .collect();
-            
+
             let uncovered_ids: Vec<_> = invariants
                 .iter()
                 .filter(|inv| !covered.contains(&inv.id))
@@ -140,6 +259,7 @@ impl CoverageTracker {
                 100.0
             };
 
+            self.telemetry.record_coverage_percentage(&format!("{:?}", category), percentage);
             by_category.insert(category, CategoryCoverage {
                 total: total_in_cat,
                 covered: covered_in_cat.len(),
@@ -214,16 +334,83 @@ impl CoverageTracker {
         self.covered_invariants.read().contains(invariant_id)
     }
 
-    /// Get list of uncovered invariants
+    /// Get list of uncovered invariants, restricted to this tracker's scope
     pub fn get_uncovered(&self) -> Vec<String> {
         let covered = self.covered_invariants.read();
         self.catalog
             .all()
-            .filter(|inv| !covered.contains(&inv.id))
+            .filter(|inv| self.in_scope(inv) && !covered.contains(&inv.id))
             .map(|inv| inv.id.clone())
             .collect()
     }
 
+    /// Fingerprint of the catalog's invariant ids, order-independent, so a
+    /// restore can detect that the catalog has drifted since the snapshot
+    /// was taken.
+    fn catalog_fingerprint(catalog: &InvariantCatalog) -> String {
+        let mut ids: Vec<&str> = catalog.all().map(|inv| inv.id.as_str()).collect();
+        ids.sort_unstable();
+        digest_ids(ids.into_iter())
+    }
+
+    /// `snapshot` with the default chunk size.
+    pub fn snapshot_default(&self) -> CoverageSnapshot {
+        self.snapshot(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Serialize the recorded-checks set plus a catalog fingerprint into
+    /// fixed-size chunks of `chunk_size` invariant ids, each with its own
+    /// checksum, so a large coverage state can stream incrementally and a
+    /// partially-corrupt snapshot can still recover its valid chunks.
+    pub fn snapshot(&self, chunk_size: usize) -> CoverageSnapshot {
+        let chunk_size = chunk_size.max(1);
+        let mut ids: Vec<String> = self.covered_invariants.read().iter().cloned().collect();
+        ids.sort_unstable();
+
+        let chunks = ids
+            .chunks(chunk_size)
+            .map(|batch| {
+                let invariant_ids = batch.to_vec();
+                let checksum = digest_ids(invariant_ids.iter().map(String::as_str));
+                CoverageChunk { invariant_ids, checksum }
+            })
+            .collect();
+
+        CoverageSnapshot {
+            catalog_fingerprint: Self::catalog_fingerprint(&self.catalog),
+            chunks,
+        }
+    }
+
+    /// Rebuild a tracker from `snapshot`, validating the catalog fingerprint
+    /// and each chunk's checksum before merging its ids in. A fingerprint
+    /// mismatch fails loudly rather than risk silently miscounting coverage
+    /// against a catalog the snapshot was never actually checked against; a
+    /// chunk whose own checksum fails is dropped and reported back in
+    /// `RestoreOutcome::corrupt_chunks` rather than discarding the rest of
+    /// an otherwise-valid snapshot.
+    pub fn restore(snapshot: &CoverageSnapshot, catalog: Arc<InvariantCatalog>) -> Result<RestoreOutcome, CoverageRestoreError> {
+        if snapshot.catalog_fingerprint != Self::catalog_fingerprint(&catalog) {
+            return Err(CoverageRestoreError::CatalogFingerprintMismatch);
+        }
+
+        let tracker = Self::new(catalog);
+        let mut corrupt_chunks = Vec::new();
+        {
+            let mut covered = tracker.covered_invariants.write();
+            for (index, chunk) in snapshot.chunks.iter().enumerate() {
+                let expected = digest_ids(chunk.invariant_ids.iter().map(String::as_str));
+                if expected != chunk.checksum {
+                    corrupt_chunks.push(index);
+                    continue;
+                }
+                covered.extend(chunk.invariant_ids.iter().cloned());
+            }
+        }
+
+        Ok(RestoreOutcome { tracker, corrupt_chunks })
+    }
+
     /// Reset coverage tracking
     pub fn reset(&self) {
         self.covered_invariants.write().clear();
@@ -271,6 +458,132 @@ mod tests {
         assert!(report.coverage_percentage > 0.0);
     }
 
+    #[test]
+    fn test_include_filter_scopes_totals_and_gaps_to_matching_invariants() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let tracker = CoverageTracker::with_filters(catalog, Some(Regex::new("^AUTH_").unwrap()), None);
+
+        let report = tracker.generate_report();
+        assert_eq!(report.total_invariants, 4);
+        assert_eq!(report.by_category.get(&InvariantCategory::Monetary).map(|c| c.total).unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn test_exclude_filter_wins_over_a_matching_include() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let tracker = CoverageTracker::with_filters(
+            catalog,
+            Some(Regex::new("^AUTH_").unwrap()),
+            Some(Regex::new("^AUTH_002$").unwrap()),
+        );
+
+        let report = tracker.generate_report();
+        assert_eq!(report.total_invariants, 3);
+    }
+
+    #[test]
+    fn test_excluded_category_produces_no_gap() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let tracker = CoverageTracker::with_filters(catalog, None, Some(Regex::new("^MON_").unwrap()));
+
+        let report = tracker.generate_report();
+        assert!(!report.gaps.iter().any(|g| g.category == InvariantCategory::Monetary));
+    }
+
+    #[test]
+    fn test_for_era_computes_coverage_against_the_catalog_active_at_that_context() {
+        use crate::invariant::catalog::InvariantOutcome;
+
+        let mut genesis = InvariantCatalog::new();
+        genesis.register(SecurityInvariant::new(
+            "GEN_001",
+            "genesis invariant",
+            "genesis invariant",
+            InvariantCategory::Custom,
+            "genesis invariant violated",
+            |_, _| InvariantOutcome::holds(),
+        ));
+
+        let mut multi = MultiCatalog::new(genesis);
+        let mut upgraded = InvariantCatalog::new();
+        upgraded.register(SecurityInvariant::new(
+            "UPG_001",
+            "post-upgrade invariant",
+            "post-upgrade invariant",
+            InvariantCategory::Custom,
+            "post-upgrade invariant violated",
+            |_, _| InvariantOutcome::holds(),
+        ));
+        multi.register(1_000, upgraded).unwrap();
+
+        let before_upgrade = CoverageTracker::for_era(&multi, 500);
+        assert!(before_upgrade.get_uncovered().contains(&"GEN_001".to_string()));
+        assert!(!before_upgrade.get_uncovered().contains(&"UPG_001".to_string()));
+
+        let after_upgrade = CoverageTracker::for_era(&multi, 1_000);
+        assert!(after_upgrade.get_uncovered().contains(&"UPG_001".to_string()));
+        assert!(!after_upgrade.get_uncovered().contains(&"GEN_001".to_string()));
+    }
+
+    #[test]
+    fn test_snapshot_and_restore_round_trips_recorded_checks() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let tracker = CoverageTracker::new(Arc::clone(&catalog));
+        for inv in catalog.all().take(3) {
+            tracker.record_check(&inv.id);
+        }
+
+        let snapshot = tracker.snapshot(2);
+        assert!(snapshot.chunks.len() >= 2);
+
+        let outcome = CoverageTracker::restore(&snapshot, Arc::clone(&catalog)).unwrap();
+        assert!(outcome.corrupt_chunks.is_empty());
+        for inv in catalog.all().take(3) {
+            assert!(outcome.tracker.is_covered(&inv.id));
+        }
+    }
+
+    #[test]
+    fn test_restore_fails_loudly_on_catalog_fingerprint_mismatch() {
+        let original = Arc::new(InvariantCatalog::new());
+        let tracker = CoverageTracker::new(Arc::clone(&original));
+        tracker.record_check("AUTH_001");
+        let snapshot = tracker.snapshot_default();
+
+        let mut drifted = InvariantCatalog::new();
+        drifted.register(SecurityInvariant::new(
+            "EXTRA_001",
+            "extra invariant",
+            "extra invariant",
+            InvariantCategory::Custom,
+            "extra invariant violated",
+            |_, _| crate::invariant::catalog::InvariantOutcome::holds(),
+        ));
+
+        let err = CoverageTracker::restore(&snapshot, Arc::new(drifted)).unwrap_err();
+        assert_eq!(err, CoverageRestoreError::CatalogFingerprintMismatch);
+    }
+
+    #[test]
+    fn test_restore_drops_a_corrupt_chunk_but_recovers_the_rest() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let tracker = CoverageTracker::new(Arc::clone(&catalog));
+        for inv in catalog.all().take(4) {
+            tracker.record_check(&inv.id);
+        }
+
+        let mut snapshot = tracker.snapshot(2);
+        snapshot.chunks[0].checksum = "corrupted".to_string();
+
+        let outcome = CoverageTracker::restore(&snapshot, Arc::clone(&catalog)).unwrap();
+        assert_eq!(outcome.corrupt_chunks, vec![0]);
+        // The second, uncorrupted chunk still merged in.
+        let recovered_ids: Vec<_> = snapshot.chunks[1].invariant_ids.clone();
+        for id in recovered_ids {
+            assert!(outcome.tracker.is_covered(&id));
+        }
+    }
+
     #[test]
     fn test_unclassified_transitions() {
         let catalog = Arc::new(InvariantCatalog::new());
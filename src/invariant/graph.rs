@@ -0,0 +1,210 @@
+//! Invariant Implication Graph - lets one invariant declare that it
+//! subsumes others, so a cascade of downstream failures can be collapsed
+//! down to its root cause instead of burying operators in derived alerts.
+
+use super::catalog::InvariantCatalog;
+use std::collections::{HashMap, HashSet};
+
+/// A problem found while validating the implication graph - intended to be
+/// caught at registration time, before the graph is ever used to reduce a
+/// violation set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// An invariant implies an ID that was never registered.
+    DanglingReference { invariant_id: String, implied_id: String },
+    /// The implication graph contains a cycle.
+    Cycle(Vec<String>),
+}
+
+impl std::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::DanglingReference { invariant_id, implied_id } => {
+                write!(f, "invariant '{invariant_id}' implies unregistered invariant '{implied_id}'")
+            }
+            GraphError::Cycle(path) => write!(f, "implication cycle: {}", path.join(" -> ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    InProgress,
+    Done,
+}
+
+impl InvariantCatalog {
+    /// Validate the implication graph: every implied ID must resolve to a
+    /// registered invariant, and the graph must be acyclic.
+    pub fn validate_graph(&self) -> Result<(), Vec<GraphError>> {
+        let mut errors = Vec::new();
+
+        for invariant in self.all() {
+            if let Some(implied) = self.implications_for(&invariant.id) {
+                for target in implied {
+                    if self.get(target).is_none() {
+                        errors.push(GraphError::DanglingReference {
+                            invariant_id: invariant.id.clone(),
+                            implied_id: target.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut state: HashMap<String, VisitState> = HashMap::new();
+        for invariant in self.all() {
+            if !state.contains_key(&invariant.id) {
+                let mut path = Vec::new();
+                if let Some(cycle) = self.detect_cycle(&invariant.id, &mut state, &mut path) {
+                    errors.push(GraphError::Cycle(cycle));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn detect_cycle(
+        &self,
+        id: &str,
+        state: &mut HashMap<String, VisitState>,
+        path: &mut Vec<String>,
+    ) -> Option<Vec<String>> {
+        state.insert(id.to_string(), VisitState::InProgress);
+        path.push(id.to_string());
+
+        if let Some(implied) = self.implications_for(id) {
+            for target in implied {
+                if self.get(target).is_none() {
+                    continue;
+                }
+                match state.get(target.as_str()) {
+                    Some(VisitState::InProgress) => {
+                        let start = path.iter().position(|n| n == target).unwrap_or(0);
+                        let mut cycle = path[start..].to_vec();
+                        cycle.push(target.clone());
+                        return Some(cycle);
+                    }
+                    Some(VisitState::Done) => continue,
+                    None => {
+                        if let Some(cycle) = self.detect_cycle(target, state, path) {
+                            return Some(cycle);
+                        }
+                    }
+                }
+            }
+        }
+
+        path.pop();
+        state.insert(id.to_string(), VisitState::Done);
+        None
+    }
+
+    /// Collapse a set of violated invariant IDs down to their root causes:
+    /// if `A` implies `B` and both are present, `B` is a derived
+    /// consequence rather than an independent failure. Returns
+    /// `(root_causes, derived)`, each a subset of `violated_ids`, in the
+    /// order they were given.
+    pub fn root_causes(&self, violated_ids: &[String]) -> (Vec<String>, Vec<String>) {
+        let violated: HashSet<&str> = violated_ids.iter().map(|id| id.as_str()).collect();
+        let mut derived: HashSet<String> = HashSet::new();
+
+        for id in violated_ids {
+            if let Some(implied) = self.implications_for(id) {
+                for target in implied {
+                    if violated.contains(target.as_str()) {
+                        derived.insert(target.clone());
+                    }
+                }
+            }
+        }
+
+        let mut roots = Vec::new();
+        let mut derived_list = Vec::new();
+        for id in violated_ids {
+            if derived.contains(id) {
+                derived_list.push(id.clone());
+            } else {
+                roots.push(id.clone());
+            }
+        }
+
+        (roots, derived_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invariant::catalog::{InvariantCategory, InvariantOutcome, SecurityInvariant};
+
+    fn always_violates(id: &str, implies: Vec<String>) -> SecurityInvariant {
+        SecurityInvariant::new(
+            id.to_string(),
+            id.to_string(),
+            "test invariant",
+            InvariantCategory::Custom,
+            "violated",
+            |_, _| InvariantOutcome::violated(vec![]),
+        )
+        .with_implies(implies)
+    }
+
+    #[test]
+    fn test_validate_graph_reports_dangling_reference() {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(always_violates("A", vec!["DOES_NOT_EXIST".to_string()]));
+
+        let errors = catalog.validate_graph().unwrap_err();
+        assert!(errors.contains(&GraphError::DanglingReference {
+            invariant_id: "A".to_string(),
+            implied_id: "DOES_NOT_EXIST".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_validate_graph_detects_cycle() {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(always_violates("A", vec!["B".to_string()]));
+        catalog.register(always_violates("B", vec!["A".to_string()]));
+
+        let errors = catalog.validate_graph().unwrap_err();
+        assert!(errors.iter().any(|e| matches!(e, GraphError::Cycle(_))));
+    }
+
+    #[test]
+    fn test_root_causes_collapses_implied_violations() {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(always_violates("SESSION_BINDING", vec!["AUTH_X".to_string(), "AUTH_Y".to_string()]));
+        catalog.register(always_violates("AUTH_X", vec![]));
+        catalog.register(always_violates("AUTH_Y", vec![]));
+
+        let violated = vec!["SESSION_BINDING".to_string(), "AUTH_X".to_string(), "AUTH_Y".to_string()];
+        let (roots, derived) = catalog.root_causes(&violated);
+
+        assert_eq!(roots, vec!["SESSION_BINDING".to_string()]);
+        assert_eq!(derived.len(), 2);
+        assert!(derived.contains(&"AUTH_X".to_string()));
+        assert!(derived.contains(&"AUTH_Y".to_string()));
+    }
+
+    #[test]
+    fn test_root_causes_keeps_unrelated_violations_independent() {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(always_violates("SESSION_BINDING", vec!["AUTH_X".to_string()]));
+        catalog.register(always_violates("AUTH_X", vec![]));
+        catalog.register(always_violates("UNRELATED", vec![]));
+
+        let violated = vec!["SESSION_BINDING".to_string(), "AUTH_X".to_string(), "UNRELATED".to_string()];
+        let (roots, derived) = catalog.root_causes(&violated);
+
+        assert!(roots.contains(&"SESSION_BINDING".to_string()));
+        assert!(roots.contains(&"UNRELATED".to_string()));
+        assert_eq!(derived, vec!["AUTH_X".to_string()]);
+    }
+}
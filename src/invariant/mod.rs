@@ -4,10 +4,27 @@
 //!
 //! **Requirements: 15.1, 15.2, 15.3, 15.4, 15.5, 43.1, 43.2, 43.4**
 
+mod audit;
 mod catalog;
+mod graph;
+mod obligations;
+mod report;
 mod validator;
 mod coverage;
+mod multi_catalog;
+mod reporter;
 
-pub use catalog::{InvariantCatalog, SecurityInvariant, InvariantCategory};
-pub use validator::{InvariantValidator, ValidationResult, ViolationDetails};
-pub use coverage::{CoverageTracker, CoverageReport, CoverageGap, GapSeverity};
\ No newline at end of file
+pub use audit::{transition_root, Digest32, LedgerRecord, ValidationLedger};
+pub use catalog::{
+    ActivationTier, Evidence, ExecutionMode, InvariantCatalog, InvariantOutcome, ProofObligation,
+    SecurityInvariant, InvariantCategory,
+};
+pub use graph::GraphError;
+pub use obligations::ObligationTracker;
+pub use report::{
+    CategoryTiming, EvaluationMode, InvariantTiming, ProfileSummary, TransitionReport, ViolationRecord,
+};
+pub use validator::{InvariantValidator, SequenceResult, ValidationResult, ViolationDetails};
+pub use coverage::{CoverageTracker, CoverageReport, CoverageGap, GapSeverity};
+pub use multi_catalog::{MultiCatalog, MultiCatalogError};
+pub use reporter::{BufferReporter, InvariantViolation, JsonLinesReporter, ReportContext, StderrReporter, ViolationReporter};
\ No newline at end of file
@@ -0,0 +1,133 @@
+//! Multi-era invariant catalogs - switching rule sets at an activation point
+//!
+//! Real deployments evolve their security rules over time: an invariant
+//! that must hold after a protocol upgrade may not exist, or may be
+//! stricter or looser, beforehand. `MultiCatalog` imports the "switching
+//! validator set at transition heights" pattern into the invariant
+//! subsystem - a sorted list of `(activation_point, InvariantCatalog)`
+//! entries, where `activation_point` is a monotonic context key such as a
+//! block height or protocol version, so a single checker can validate
+//! transactions across upgrade boundaries against whichever catalog was
+//! actually in force at the time.
+
+use super::catalog::InvariantCatalog;
+use std::fmt;
+use std::sync::Arc;
+
+/// Error registering a catalog into a `MultiCatalog`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiCatalogError {
+    /// A catalog was already registered at this exact `activation_point`.
+    DuplicateActivationPoint(u64),
+}
+
+impl fmt::Display for MultiCatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultiCatalogError::DuplicateActivationPoint(point) => {
+                write!(f, "a catalog is already registered at activation point {point}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MultiCatalogError {}
+
+/// A sorted list of `(activation_point, InvariantCatalog)` entries.
+/// `active_at(point)` resolves to whichever entry has the greatest
+/// `activation_point <= point`, falling back to a configured default
+/// catalog when `point` precedes every registered entry.
+pub struct MultiCatalog {
+    /// Sorted ascending by activation point; kept sorted at registration
+    /// time so `active_at` can binary-search on lookup.
+    entries: Vec<(u64, Arc<InvariantCatalog>)>,
+    default: Arc<InvariantCatalog>,
+}
+
+impl MultiCatalog {
+    /// `default` is the catalog used for any point preceding the earliest
+    /// registered activation point.
+    pub fn new(default: InvariantCatalog) -> Self {
+        Self {
+            entries: Vec::new(),
+            default: Arc::new(default),
+        }
+    }
+
+    /// Register `catalog` to take effect at `activation_point` and every
+    /// later point, until superseded by a later registration. Rejects a
+    /// duplicate `activation_point` rather than silently overwriting or
+    /// shadowing whatever was registered there first.
+    pub fn register(&mut self, activation_point: u64, catalog: InvariantCatalog) -> Result<(), MultiCatalogError> {
+        match self.entries.binary_search_by_key(&activation_point, |(point, _)| *point) {
+            Ok(_) => Err(MultiCatalogError::DuplicateActivationPoint(activation_point)),
+            Err(index) => {
+                self.entries.insert(index, (activation_point, Arc::new(catalog)));
+                Ok(())
+            }
+        }
+    }
+
+    /// The catalog in force at `point`: the entry with the greatest
+    /// `activation_point <= point`, or the configured default if `point`
+    /// precedes every registered entry.
+    pub fn active_at(&self, point: u64) -> Arc<InvariantCatalog> {
+        match self.entries.binary_search_by_key(&point, |(activation_point, _)| *activation_point) {
+            Ok(index) => Arc::clone(&self.entries[index].1),
+            Err(0) => Arc::clone(&self.default),
+            Err(index) => Arc::clone(&self.entries[index - 1].1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invariant::InvariantCategory;
+    use crate::invariant::{InvariantOutcome, SecurityInvariant};
+
+    fn catalog_with(id: &str) -> InvariantCatalog {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(SecurityInvariant::new(
+            id,
+            "test invariant",
+            "test invariant",
+            InvariantCategory::Custom,
+            "test invariant violated",
+            |_, _| InvariantOutcome::holds(),
+        ));
+        catalog
+    }
+
+    #[test]
+    fn test_active_at_resolves_the_nearest_preceding_activation_point() {
+        let mut multi = MultiCatalog::new(catalog_with("GENESIS"));
+        multi.register(100, catalog_with("UPGRADE_A")).unwrap();
+        multi.register(200, catalog_with("UPGRADE_B")).unwrap();
+
+        assert!(multi.active_at(50).get("GENESIS").is_some());
+        assert!(multi.active_at(100).get("UPGRADE_A").is_some());
+        assert!(multi.active_at(150).get("UPGRADE_A").is_some());
+        assert!(multi.active_at(200).get("UPGRADE_B").is_some());
+        assert!(multi.active_at(1_000_000).get("UPGRADE_B").is_some());
+    }
+
+    #[test]
+    fn test_out_of_order_registration_still_resolves_correctly() {
+        let mut multi = MultiCatalog::new(catalog_with("GENESIS"));
+        multi.register(200, catalog_with("UPGRADE_B")).unwrap();
+        multi.register(100, catalog_with("UPGRADE_A")).unwrap();
+
+        assert!(multi.active_at(150).get("UPGRADE_A").is_some());
+        assert!(multi.active_at(250).get("UPGRADE_B").is_some());
+    }
+
+    #[test]
+    fn test_duplicate_activation_point_is_rejected() {
+        let mut multi = MultiCatalog::new(catalog_with("GENESIS"));
+        multi.register(100, catalog_with("UPGRADE_A")).unwrap();
+
+        let err = multi.register(100, catalog_with("UPGRADE_A_DUPLICATE")).unwrap_err();
+        assert_eq!(err, MultiCatalogError::DuplicateActivationPoint(100));
+    }
+}
@@ -0,0 +1,108 @@
+//! Obligation Tracker - accumulates open proof obligations across a sequence
+//! of transitions and flags any left undischarged at a terminal state.
+//!
+//! An invariant that returns `InvariantOutcome::deferred(...)` cannot be
+//! judged from a single before/after pair; it is provisionally satisfied,
+//! contingent on a later transition discharging its obligations. This
+//! tracker is what turns that single-step deferral into the two-phase
+//! authorization flow described by the invariant.
+
+use super::catalog::ProofObligation;
+use crate::state::ApplicationState;
+
+/// An obligation raised by a specific invariant, not yet discharged.
+struct OpenObligation {
+    obligation: ProofObligation,
+    raised_by_invariant: String,
+}
+
+/// Tracks proof obligations raised by invariants across successive
+/// `validate` calls, discharging them once a later transition satisfies
+/// their predicate.
+pub struct ObligationTracker {
+    open: Vec<OpenObligation>,
+}
+
+impl ObligationTracker {
+    pub fn new() -> Self {
+        Self { open: Vec::new() }
+    }
+
+    /// Record new obligations raised by `invariant_id` against a transition.
+    pub fn raise(&mut self, invariant_id: &str, obligations: Vec<ProofObligation>) {
+        for obligation in obligations {
+            self.open.push(OpenObligation {
+                obligation,
+                raised_by_invariant: invariant_id.to_string(),
+            });
+        }
+    }
+
+    /// Attempt to discharge all open obligations against a subsequent
+    /// transition, dropping the ones it satisfies.
+    pub fn discharge(&mut self, before: &ApplicationState, after: &ApplicationState) {
+        self.open.retain(|o| !o.obligation.is_discharged(before, after));
+    }
+
+    /// Obligations still open, as `(invariant_id, obligation_id)` pairs -
+    /// call at a terminal state to detect two-phase invariants that were
+    /// never completed.
+    pub fn open_obligations(&self) -> Vec<(String, String)> {
+        self.open
+            .iter()
+            .map(|o| (o.raised_by_invariant.clone(), o.obligation.id.clone()))
+            .collect()
+    }
+
+    pub fn has_open_obligations(&self) -> bool {
+        !self.open.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.open.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.open.is_empty()
+    }
+}
+
+impl Default for ObligationTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invariant::ProofObligation;
+
+    #[test]
+    fn test_obligation_is_discharged_by_a_later_transition() {
+        let mut tracker = ObligationTracker::new();
+        tracker.raise(
+            "SESS_001",
+            vec![ProofObligation::new("rotation_holds", "must still hold", |_, _| true)],
+        );
+        assert!(tracker.has_open_obligations());
+
+        let state = ApplicationState::default();
+        tracker.discharge(&state, &state);
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn test_obligation_stays_open_until_discharged() {
+        let mut tracker = ObligationTracker::new();
+        tracker.raise(
+            "SESS_001",
+            vec![ProofObligation::new("rotation_holds", "must still hold", |_, _| false)],
+        );
+
+        let state = ApplicationState::default();
+        tracker.discharge(&state, &state);
+        assert!(tracker.has_open_obligations());
+        assert_eq!(tracker.open_obligations(), vec![("SESS_001".to_string(), "rotation_holds".to_string())]);
+    }
+}
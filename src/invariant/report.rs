@@ -0,0 +1,422 @@
+//! Transition Evaluation - Runs the whole catalog against a `(before, after)`
+//! pair and collects a structured report, so the catalog is a usable
+//! enforcement subsystem rather than just a registry invariants can be
+//! looked up from.
+
+use super::catalog::{ExecutionMode, InvariantCatalog, InvariantCategory};
+use crate::state::ApplicationState;
+use serde::{Deserialize, Serialize};
+
+/// Whether evaluation stops at the first violation or keeps going to
+/// collect every one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvaluationMode {
+    FailFast,
+    CollectAll,
+}
+
+/// A single invariant violation found while evaluating a transition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViolationRecord {
+    pub id: String,
+    pub category: InvariantCategory,
+    pub description: String,
+    pub message: String,
+}
+
+/// Result of running every registered invariant against one transition.
+///
+/// Serializes directly to a machine-readable JSON form - one object per
+/// violation, carrying `category` and `message` - so CI and audit
+/// pipelines can filter by `InvariantCategory`. `Display` renders the
+/// human-readable console form.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionReport {
+    pub total_checked: usize,
+    pub passed: Vec<String>,
+    pub violations: Vec<ViolationRecord>,
+    /// IDs of violations that are root causes under the catalog's
+    /// implication graph - i.e. not implied by any other violation in this
+    /// report. Equal to all violation IDs when no implications apply.
+    pub root_causes: Vec<String>,
+    /// IDs of violations implied by a root cause above, surfaced
+    /// separately so operators aren't buried in cascading alerts.
+    pub derived: Vec<String>,
+}
+
+impl TransitionReport {
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Render as a JSON string for CI/audit pipelines.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl std::fmt::Display for TransitionReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.violations.is_empty() {
+            writeln!(f, "PASS - {} invariant(s) checked, no violations", self.total_checked)
+        } else {
+            writeln!(
+                f,
+                "FAIL - {} of {} invariant(s) violated ({} root cause(s), {} derived):",
+                self.violations.len(),
+                self.total_checked,
+                self.root_causes.len(),
+                self.derived.len(),
+            )?;
+            for violation in &self.violations {
+                let marker = if self.derived.contains(&violation.id) { "derived" } else { "root" };
+                writeln!(
+                    f,
+                    "  [{:?}] {} ({}): {}",
+                    violation.category, violation.id, marker, violation.message
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
+impl InvariantCatalog {
+    /// Run every invariant whose tier would actually execute under
+    /// `execution_mode` against `(before, after)`, collecting pass/fail
+    /// results into a `TransitionReport`. In `FailFast` mode, evaluation
+    /// stops at the first violation.
+    pub fn check_transition(
+        &self,
+        before: &ApplicationState,
+        after: &ApplicationState,
+        mode: EvaluationMode,
+        execution_mode: ExecutionMode,
+    ) -> TransitionReport {
+        let mut passed = Vec::new();
+        let mut violations = Vec::new();
+
+        for invariant in self.all_for_mode(execution_mode) {
+            let outcome = invariant.validate(before, after);
+
+            if outcome.is_violated() {
+                violations.push(ViolationRecord {
+                    id: invariant.id.clone(),
+                    category: invariant.category,
+                    description: invariant.description.clone(),
+                    message: invariant.violation_message.clone(),
+                });
+                if mode == EvaluationMode::FailFast {
+                    break;
+                }
+            } else {
+                passed.push(invariant.id.clone());
+            }
+        }
+
+        self.build_report(passed, violations)
+    }
+
+    /// Build a `TransitionReport` from raw pass/violation results, reducing
+    /// the violation set to root causes via the implication graph.
+    fn build_report(&self, passed: Vec<String>, violations: Vec<ViolationRecord>) -> TransitionReport {
+        let violated_ids: Vec<String> = violations.iter().map(|v| v.id.clone()).collect();
+        let (root_causes, derived) = self.root_causes(&violated_ids);
+
+        TransitionReport {
+            total_checked: passed.len() + violations.len(),
+            passed,
+            violations,
+            root_causes,
+            derived,
+        }
+    }
+
+    /// Same as `check_transition`, but times every invariant predicate and
+    /// returns a `ProfileSummary` alongside the report, so slow invariants
+    /// can be found without reaching for an external profiler.
+    pub fn check_transition_profiled(
+        &self,
+        before: &ApplicationState,
+        after: &ApplicationState,
+        mode: EvaluationMode,
+        execution_mode: ExecutionMode,
+    ) -> (TransitionReport, ProfileSummary) {
+        let mut passed = Vec::new();
+        let mut violations = Vec::new();
+        let mut timings = Vec::new();
+
+        for invariant in self.all_for_mode(execution_mode) {
+            let started = std::time::Instant::now();
+            let outcome = invariant.validate(before, after);
+            let duration_us = started.elapsed().as_micros();
+
+            timings.push(InvariantTiming {
+                invariant_id: invariant.id.clone(),
+                category: invariant.category,
+                duration_us,
+            });
+
+            if outcome.is_violated() {
+                violations.push(ViolationRecord {
+                    id: invariant.id.clone(),
+                    category: invariant.category,
+                    description: invariant.description.clone(),
+                    message: invariant.violation_message.clone(),
+                });
+                if mode == EvaluationMode::FailFast {
+                    break;
+                }
+            } else {
+                passed.push(invariant.id.clone());
+            }
+        }
+
+        let report = self.build_report(passed, violations);
+
+        (report, ProfileSummary::from_timings(timings))
+    }
+}
+
+/// Wall-clock time spent evaluating one invariant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvariantTiming {
+    pub invariant_id: String,
+    pub category: InvariantCategory,
+    pub duration_us: u128,
+}
+
+/// Total time spent evaluating every invariant in one `InvariantCategory`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryTiming {
+    pub category: InvariantCategory,
+    pub total_duration_us: u128,
+    pub percentage_of_total: f64,
+}
+
+/// Per-invariant and per-category timing for one `check_transition_profiled`
+/// run, grouped the way `InvariantCatalog::count_by_category` groups counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileSummary {
+    pub total_duration_us: u128,
+    pub per_invariant: Vec<InvariantTiming>,
+    pub per_category: Vec<CategoryTiming>,
+}
+
+impl ProfileSummary {
+    fn from_timings(per_invariant: Vec<InvariantTiming>) -> Self {
+        let total_duration_us: u128 = per_invariant.iter().map(|t| t.duration_us).sum();
+
+        let mut totals_by_category: std::collections::HashMap<InvariantCategory, u128> =
+            std::collections::HashMap::new();
+        for timing in &per_invariant {
+            *totals_by_category.entry(timing.category).or_insert(0) += timing.duration_us;
+        }
+
+        let per_category = ALL_CATEGORIES
+            .iter()
+            .filter_map(|category| {
+                totals_by_category.get(category).map(|&total| CategoryTiming {
+                    category: *category,
+                    total_duration_us: total,
+                    percentage_of_total: if total_duration_us > 0 {
+                        (total as f64 / total_duration_us as f64) * 100.0
+                    } else {
+                        0.0
+                    },
+                })
+            })
+            .collect();
+
+        Self {
+            total_duration_us,
+            per_invariant,
+            per_category,
+        }
+    }
+
+    /// The `n` slowest invariants, slowest first.
+    pub fn slowest(&self, n: usize) -> Vec<&InvariantTiming> {
+        let mut sorted: Vec<&InvariantTiming> = self.per_invariant.iter().collect();
+        sorted.sort_by(|a, b| b.duration_us.cmp(&a.duration_us));
+        sorted.truncate(n);
+        sorted
+    }
+}
+
+const ALL_CATEGORIES: [InvariantCategory; 9] = [
+    InvariantCategory::Authorization,
+    InvariantCategory::Monetary,
+    InvariantCategory::Workflow,
+    InvariantCategory::Trust,
+    InvariantCategory::DataIntegrity,
+    InvariantCategory::SessionManagement,
+    InvariantCategory::InputValidation,
+    InvariantCategory::RateLimiting,
+    InvariantCategory::Custom,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invariant::catalog::InvariantOutcome;
+    use crate::invariant::SecurityInvariant;
+
+    #[test]
+    fn test_clean_transition_reports_no_violations() {
+        let catalog = InvariantCatalog::new();
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+
+        let report = catalog.check_transition(&before, &after, EvaluationMode::CollectAll, ExecutionMode::Audit);
+        assert!(report.is_clean());
+        assert_eq!(report.total_checked, catalog.count());
+    }
+
+    #[test]
+    fn test_fail_fast_stops_at_first_violation() {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(SecurityInvariant::new(
+            "ALWAYS_FAILS",
+            "Always Fails",
+            "Test invariant that always violates",
+            InvariantCategory::Custom,
+            "always violated",
+            |_, _| InvariantOutcome::violated(vec![]),
+        ));
+        // A second failing invariant, so whichever of the two is reached
+        // first, the other is never evaluated.
+        catalog.register(SecurityInvariant::new(
+            "ALWAYS_FAILS_TOO",
+            "Always Fails Too",
+            "Second test invariant that always violates",
+            InvariantCategory::Custom,
+            "always violated",
+            |_, _| InvariantOutcome::violated(vec![]),
+        ));
+
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+
+        let report = catalog.check_transition(&before, &after, EvaluationMode::FailFast, ExecutionMode::Audit);
+        assert_eq!(report.violations.len(), 1);
+        assert!(report.total_checked < catalog.count());
+    }
+
+    #[test]
+    fn test_profiled_transition_times_every_invariant() {
+        let catalog = InvariantCatalog::new();
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+
+        let (report, profile) = catalog.check_transition_profiled(
+            &before,
+            &after,
+            EvaluationMode::CollectAll,
+            ExecutionMode::Audit,
+        );
+
+        assert!(report.is_clean());
+        assert_eq!(profile.per_invariant.len(), catalog.count());
+        let category_total: u128 = profile.per_category.iter().map(|c| c.total_duration_us).sum();
+        assert_eq!(category_total, profile.total_duration_us);
+    }
+
+    #[test]
+    fn test_slowest_returns_invariants_in_descending_duration() {
+        let catalog = InvariantCatalog::new();
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+
+        let (_, profile) = catalog.check_transition_profiled(
+            &before,
+            &after,
+            EvaluationMode::CollectAll,
+            ExecutionMode::Audit,
+        );
+
+        let slowest = profile.slowest(3);
+        assert!(slowest.len() <= 3);
+        for pair in slowest.windows(2) {
+            assert!(pair[0].duration_us >= pair[1].duration_us);
+        }
+    }
+
+    #[test]
+    fn test_report_serializes_to_json_with_category_and_message() {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(SecurityInvariant::new(
+            "ALWAYS_FAILS",
+            "Always Fails",
+            "Test invariant that always violates",
+            InvariantCategory::Custom,
+            "always violated",
+            |_, _| InvariantOutcome::violated(vec![]),
+        ));
+
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+        let report = catalog.check_transition(&before, &after, EvaluationMode::CollectAll, ExecutionMode::Audit);
+
+        let json = report.to_json().unwrap();
+        assert!(json.contains("\"category\""));
+        assert!(json.contains("\"message\""));
+    }
+
+    #[test]
+    fn test_check_transition_collapses_implied_violations_to_root_cause() {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(SecurityInvariant::new(
+            "ROOT_CAUSE",
+            "Root Cause",
+            "Test invariant that implies a downstream one",
+            InvariantCategory::Custom,
+            "always violated",
+            |_, _| InvariantOutcome::violated(vec![]),
+        ).with_implies(vec!["DOWNSTREAM".to_string()]));
+        catalog.register(SecurityInvariant::new(
+            "DOWNSTREAM",
+            "Downstream",
+            "Test invariant implied by ROOT_CAUSE",
+            InvariantCategory::Custom,
+            "always violated",
+            |_, _| InvariantOutcome::violated(vec![]),
+        ));
+
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+        let report = catalog.check_transition(&before, &after, EvaluationMode::CollectAll, ExecutionMode::Audit);
+
+        assert_eq!(report.violations.len(), 2);
+        assert_eq!(report.root_causes, vec!["ROOT_CAUSE".to_string()]);
+        assert_eq!(report.derived, vec!["DOWNSTREAM".to_string()]);
+    }
+
+    #[test]
+    fn test_release_mode_skips_debug_only_invariants() {
+        let mut catalog = InvariantCatalog::new();
+        catalog.register(
+            SecurityInvariant::new(
+                "DEBUG_ONLY_CHECK",
+                "Debug Only Check",
+                "Test invariant confined to audit/debug runs",
+                InvariantCategory::Custom,
+                "should never surface in release mode",
+                |_, _| InvariantOutcome::violated(vec![]),
+            )
+            .with_tier(crate::invariant::catalog::ActivationTier::DebugOnly),
+        );
+
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+
+        let release_report =
+            catalog.check_transition(&before, &after, EvaluationMode::CollectAll, ExecutionMode::Release);
+        assert!(release_report.is_clean());
+        assert_eq!(release_report.total_checked, catalog.count() - 1);
+
+        let audit_report =
+            catalog.check_transition(&before, &after, EvaluationMode::CollectAll, ExecutionMode::Audit);
+        assert_eq!(audit_report.violations.len(), 1);
+    }
+}
@@ -0,0 +1,163 @@
+//! Violation Reporters - Pluggable sinks for detected invariant breaks
+//!
+//! Validation has always surfaced violations by handing the caller a
+//! `Vec<ViolationDetails>` to do with as it pleases - typically an
+//! `eprintln!` over `result.violations`. A `ViolationReporter` is invoked
+//! the moment a violation is detected instead, decoupling detection from
+//! how breaches are surfaced: structured events can be streamed straight
+//! to a logging or alerting pipeline rather than parsed back out of
+//! printed strings. This mirrors the misbehavior-reporting hook added when
+//! validator sets gained reporting - the decision logic emits a
+//! structured record the instant a breach is observed.
+
+use super::catalog::{Evidence, InvariantCategory};
+use crate::types::Severity;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::sync::Mutex;
+
+/// A structured record of one detected violation, independent of whatever
+/// printed message a human would see.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvariantViolation {
+    pub invariant_id: String,
+    pub category: InvariantCategory,
+    pub severity: Severity,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Context surrounding a detected violation, passed alongside the event
+/// itself so a reporter can enrich structured logging without the event
+/// having to carry everything `ViolationDetails` does.
+#[derive(Debug, Clone)]
+pub struct ReportContext {
+    pub invariant_name: String,
+    pub confidence: f64,
+    pub evidence: Vec<Evidence>,
+}
+
+/// A destination violations are forwarded to as they are detected.
+pub trait ViolationReporter: Send + Sync {
+    /// Called once per detected violation, in detection order.
+    fn report(&self, violation: &InvariantViolation, context: &ReportContext);
+}
+
+/// Prints each violation to stderr, one line per violation.
+#[derive(Debug, Default)]
+pub struct StderrReporter;
+
+impl StderrReporter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ViolationReporter for StderrReporter {
+    fn report(&self, violation: &InvariantViolation, context: &ReportContext) {
+        eprintln!(
+            "[{}] {:?} {:?} {} ({}): {}",
+            violation.timestamp, violation.severity, violation.category, violation.invariant_id,
+            context.invariant_name, violation.message
+        );
+    }
+}
+
+/// Collects every reported violation in memory, for tests and short-lived
+/// runs that want to inspect what was reported rather than parse stderr.
+#[derive(Default)]
+pub struct BufferReporter {
+    events: RwLock<Vec<InvariantViolation>>,
+}
+
+impl BufferReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every violation reported so far, in report order.
+    pub fn events(&self) -> Vec<InvariantViolation> {
+        self.events.read().clone()
+    }
+}
+
+impl ViolationReporter for BufferReporter {
+    fn report(&self, violation: &InvariantViolation, _context: &ReportContext) {
+        self.events.write().push(violation.clone());
+    }
+}
+
+/// Appends each violation as one line of JSON to a writer, for forwarding
+/// to logging/alerting pipelines that tail a file or stream.
+pub struct JsonLinesReporter<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLinesReporter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer: Mutex::new(writer) }
+    }
+}
+
+impl<W: Write + Send> ViolationReporter for JsonLinesReporter<W> {
+    fn report(&self, violation: &InvariantViolation, _context: &ReportContext) {
+        let Ok(line) = serde_json::to_string(violation) else { return };
+        let mut writer = self.writer.lock().unwrap();
+        let _ = writeln!(writer, "{line}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_violation() -> InvariantViolation {
+        InvariantViolation {
+            invariant_id: "MON_001".to_string(),
+            category: InvariantCategory::Monetary,
+            severity: Severity::Critical,
+            message: "balance conservation violated".to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn sample_context() -> ReportContext {
+        ReportContext {
+            invariant_name: "Balance Conservation".to_string(),
+            confidence: 1.0,
+            evidence: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_buffer_reporter_collects_events_in_order() {
+        let reporter = BufferReporter::new();
+        let first = sample_violation();
+        let mut second = sample_violation();
+        second.invariant_id = "MON_002".to_string();
+
+        reporter.report(&first, &sample_context());
+        reporter.report(&second, &sample_context());
+
+        let events = reporter.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].invariant_id, "MON_001");
+        assert_eq!(events[1].invariant_id, "MON_002");
+    }
+
+    #[test]
+    fn test_json_lines_reporter_writes_one_line_per_violation() {
+        let buffer: Vec<u8> = Vec::new();
+        let reporter = JsonLinesReporter::new(buffer);
+
+        reporter.report(&sample_violation(), &sample_context());
+        reporter.report(&sample_violation(), &sample_context());
+
+        let written = reporter.writer.lock().unwrap().clone();
+        let text = String::from_utf8(written).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.lines().next().unwrap().contains("MON_001"));
+    }
+}
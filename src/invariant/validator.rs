@@ -1,9 +1,15 @@
 //! Invariant Validator - Evaluates findings against security invariants
 
-use super::catalog::{InvariantCatalog, InvariantCategory, SecurityInvariant};
-use crate::state::ApplicationState;
+use super::audit::{transition_root, Digest32, ValidationLedger};
+use super::catalog::{Evidence, InvariantCatalog, InvariantCategory, InvariantOutcome, SecurityInvariant};
+use super::obligations::ObligationTracker;
+use super::reporter::{InvariantViolation, ReportContext, ViolationReporter};
+use crate::state::{ApplicationState, StateField};
+use crate::telemetry::Telemetry;
 use crate::types::*;
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Result of invariant validation
@@ -44,6 +50,41 @@ impl ValidationResult {
     }
 }
 
+/// Result of validating an ordered chain of states as a single atomic
+/// sequence (see [`InvariantValidator::validate_sequence`]). All-or-nothing:
+/// `is_valid` is true only if every adjacent transition held and every
+/// cross-step invariant held over the whole chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceResult {
+    pub is_valid: bool,
+    /// Index `i` such that `states[i] -> states[i + 1]` is the first
+    /// transition that violated something, or `None` if the whole
+    /// sequence held.
+    pub failing_step: Option<usize>,
+    pub checked_invariants: Vec<String>,
+    pub violations: Vec<ViolationDetails>,
+}
+
+impl SequenceResult {
+    pub fn valid(checked_invariants: Vec<String>) -> Self {
+        Self {
+            is_valid: true,
+            failing_step: None,
+            checked_invariants,
+            violations: Vec::new(),
+        }
+    }
+
+    pub fn violation(violations: Vec<ViolationDetails>, checked_invariants: Vec<String>, failing_step: usize) -> Self {
+        Self {
+            is_valid: false,
+            failing_step: Some(failing_step),
+            checked_invariants,
+            violations,
+        }
+    }
+}
+
 /// Details of an invariant violation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ViolationDetails {
@@ -53,16 +94,104 @@ pub struct ViolationDetails {
     pub message: String,
     pub severity: Severity,
     pub confidence: f64,
+    /// Concrete state elements that drove the verdict, for actionable
+    /// counterexamples instead of a bare `false`.
+    pub evidence: Vec<Evidence>,
 }
 
 /// Invariant validator that evaluates state transitions
 pub struct InvariantValidator {
     catalog: Arc<InvariantCatalog>,
+    /// Obligations raised by invariants that could not be decided from a
+    /// single before/after pair, pending discharge by a later transition.
+    obligations: RwLock<ObligationTracker>,
+    telemetry: Telemetry,
+    /// Forwarded every violation the instant it is detected, if configured.
+    reporter: RwLock<Option<Arc<dyn ViolationReporter>>>,
 }
 
 impl InvariantValidator {
     pub fn new(catalog: Arc<InvariantCatalog>) -> Self {
-        Self { catalog }
+        Self {
+            catalog,
+            obligations: RwLock::new(ObligationTracker::new()),
+            telemetry: Telemetry::init(),
+            reporter: RwLock::new(None),
+        }
+    }
+
+    /// Enable or disable this validator's tracing/metrics at runtime. See
+    /// [`Telemetry::set_enabled`].
+    pub fn set_tracing_enabled(&self, enabled: bool) {
+        self.telemetry.set_enabled(enabled);
+    }
+
+    /// Configure the sink every detected violation is forwarded to as it's
+    /// found (see [`ViolationReporter`]). Pass `None` to stop reporting.
+    pub fn set_reporter(&self, reporter: Option<Arc<dyn ViolationReporter>>) {
+        *self.reporter.write() = reporter;
+    }
+
+    /// Forward `violation` to the configured reporter, if any.
+    fn report_violation(&self, violation: &ViolationDetails) {
+        let reporter = self.reporter.read();
+        let Some(reporter) = reporter.as_ref() else { return };
+
+        let event = InvariantViolation {
+            invariant_id: violation.invariant_id.clone(),
+            category: violation.category,
+            severity: violation.severity,
+            message: violation.message.clone(),
+            timestamp: chrono::Utc::now(),
+        };
+        let context = ReportContext {
+            invariant_name: violation.invariant_name.clone(),
+            confidence: violation.confidence,
+            evidence: violation.evidence.clone(),
+        };
+        reporter.report(&event, &context);
+    }
+
+    fn violation_details(
+        &self,
+        invariant: &SecurityInvariant,
+        outcome: &InvariantOutcome,
+    ) -> ViolationDetails {
+        let message = match &outcome.detail {
+            Some(detail) => format!("{}: {}", invariant.violation_message, detail),
+            None => invariant.violation_message.clone(),
+        };
+        ViolationDetails {
+            invariant_id: invariant.id.clone(),
+            invariant_name: invariant.name.clone(),
+            category: invariant.category,
+            message,
+            severity: self.determine_severity(&invariant.category),
+            confidence: 1.0,
+            evidence: outcome.evidence.clone(),
+        }
+    }
+
+    /// Evaluate one invariant, raising any obligations it defers onto the
+    /// tracker, and return its violation details if it was violated.
+    fn check(
+        &self,
+        invariant: &SecurityInvariant,
+        before: &ApplicationState,
+        after: &ApplicationState,
+        obligations: &mut ObligationTracker,
+    ) -> Option<ViolationDetails> {
+        let outcome = invariant.validate(before, after);
+
+        if !outcome.obligations.is_empty() {
+            obligations.raise(&invariant.id, outcome.obligations.clone());
+        }
+
+        if outcome.is_violated() {
+            Some(self.violation_details(invariant, &outcome))
+        } else {
+            None
+        }
     }
 
     /// Validate a state transition against all invariants
@@ -71,29 +200,232 @@ impl InvariantValidator {
         before: &ApplicationState,
         after: &ApplicationState,
     ) -> ValidationResult {
+        let mut span = self.telemetry.start_span("invariant.validate_transition", &[]);
+
+        let mut violations = Vec::new();
+        let mut obligations = self.obligations.write();
+        obligations.discharge(before, after);
+        let touched = before.diff(after).touched_fields();
+
+        // Every invariant in the catalog is reported as checked, even the
+        // ones the region index below lets us skip evaluating entirely -
+        // a skip means "trivially satisfied", not "not checked".
+        let checked: Vec<String> = self.catalog.all().map(|invariant| invariant.id.clone()).collect();
+        for id in &checked {
+            self.telemetry.record_invariant_evaluated(id);
+        }
+
+        for invariant in self.catalog.relevant_for(&touched) {
+            let _invariant_span = self.telemetry.start_span(
+                "invariant_validator.check_invariant",
+                &[("invariant_id", invariant.id.clone())],
+            );
+
+            if let Some(violation) = self.check(invariant, before, after, &mut obligations) {
+                self.telemetry.record_violation_found(&invariant.id);
+                self.report_violation(&violation);
+                span.add_event(
+                    "invariant.violation",
+                    &[
+                        ("invariant_id", violation.invariant_id.clone()),
+                        ("confidence", violation.confidence.to_string()),
+                    ],
+                );
+                violations.push(violation);
+            }
+        }
+
+        span.set_attribute("checked", checked.len().to_string());
+        span.set_attribute("violations", violations.len().to_string());
+        if let Some(highest) = violations.iter().map(|v| v.severity).max() {
+            span.set_attribute("highest_severity", format!("{:?}", highest));
+        }
+
+        if violations.is_empty() {
+            ValidationResult::valid(checked)
+        } else {
+            ValidationResult::violation(violations, checked)
+        }
+    }
+
+    /// Validate a state transition and record every invariant verdict into a
+    /// tamper-evident `ValidationLedger`, chained off its current head.
+    /// Returns the validation result alongside the ledger's new head digest.
+    pub fn validate_transition_audited(
+        &self,
+        before: &ApplicationState,
+        after: &ApplicationState,
+        ledger: &ValidationLedger,
+    ) -> (ValidationResult, Digest32) {
+        let root = transition_root(before, after);
         let mut violations = Vec::new();
         let mut checked = Vec::new();
+        let mut head = ledger.head_digest();
+        let mut obligations = self.obligations.write();
+        obligations.discharge(before, after);
 
         for invariant in self.catalog.all() {
             checked.push(invariant.id.clone());
-            
-            if !invariant.validate(before, after) {
-                violations.push(ViolationDetails {
-                    invariant_id: invariant.id.clone(),
-                    invariant_name: invariant.name.clone(),
-                    category: invariant.category,
-                    message: invariant.violation_message.clone(),
-                    severity: self.determine_severity(&invariant.category),
-                    confidence: 1.0, // Invariant violations have high confidence
-                });
+            let outcome = invariant.validate(before, after);
+
+            if !outcome.obligations.is_empty() {
+                obligations.raise(&invariant.id, outcome.obligations.clone());
+            }
+
+            // The ledger chains a plain bool: a deferred verdict has not yet
+            // been disproven, so it is recorded as holding until an
+            // obligation is later found undischarged.
+            head = ledger.record(&invariant.id, !outcome.is_violated(), root);
+
+            if outcome.is_violated() {
+                let violation = self.violation_details(invariant, &outcome);
+                self.report_violation(&violation);
+                violations.push(violation);
             }
         }
 
-        if violations.is_empty() {
+        let result = if violations.is_empty() {
             ValidationResult::valid(checked)
         } else {
             ValidationResult::violation(violations, checked)
+        };
+
+        (result, head)
+    }
+
+    /// Validate an ordered chain of states as a single atomic transaction,
+    /// analogous to a call stack of nested checkpoints that either all
+    /// commit or all roll back together: each adjacent pair
+    /// `(states[i], states[i + 1])` is validated in order, and the whole
+    /// sequence is rejected at the first violation along with the failing
+    /// step index. A sequence of fewer than two states is trivially
+    /// valid - there is nothing to transition between.
+    ///
+    /// Beyond the per-step checks, two invariants are enforced across the
+    /// entire chain rather than just one adjacent pair at a time: balance
+    /// conservation from the first state to the last, re-derived from the
+    /// whole chain's new `FinancialTransaction`s rather than any single
+    /// step's, and workflow position monotonicity across every
+    /// intermediate state - which catches a session's step index dipping
+    /// backward and recovering between two states, something `WF_001`
+    /// only checks for forward skips and would miss entirely.
+    pub fn validate_sequence(&self, states: &[ApplicationState]) -> SequenceResult {
+        if states.len() < 2 {
+            return SequenceResult::valid(Vec::new());
+        }
+
+        let mut checked: HashSet<String> = HashSet::new();
+
+        for (i, pair) in states.windows(2).enumerate() {
+            let result = self.validate_transition(&pair[0], &pair[1]);
+            checked.extend(result.checked_invariants);
+
+            if !result.is_valid {
+                return SequenceResult::violation(result.violations, Self::sorted(checked), i);
+            }
         }
+
+        checked.insert("SEQ_MON_001".to_string());
+        if let Some(violation) = Self::check_sequence_balance_conservation(states) {
+            self.report_violation(&violation);
+            return SequenceResult::violation(vec![violation], Self::sorted(checked), states.len() - 2);
+        }
+
+        checked.insert("SEQ_WF_001".to_string());
+        if let Some(violation) = Self::check_sequence_workflow_monotonicity(states) {
+            self.report_violation(&violation);
+            return SequenceResult::violation(vec![violation], Self::sorted(checked), states.len() - 2);
+        }
+
+        SequenceResult::valid(Self::sorted(checked))
+    }
+
+    fn sorted(ids: HashSet<String>) -> Vec<String> {
+        let mut ids: Vec<String> = ids.into_iter().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Balance conservation across the whole sequence: the net change in
+    /// each currency's total balance from `states[0]` to `states[last]`
+    /// must equal the external transfers recorded anywhere in between,
+    /// not just the ones visible in any single adjacent pair.
+    fn check_sequence_balance_conservation(states: &[ApplicationState]) -> Option<ViolationDetails> {
+        let first = states.first()?;
+        let last = states.last()?;
+        let diff = first.diff(last);
+
+        let mut currencies: HashSet<Currency> = HashSet::new();
+        currencies.extend(first.balances.values().map(|b| b.currency));
+        currencies.extend(last.balances.values().map(|b| b.currency));
+
+        for currency in currencies {
+            let total_before: MonetaryValue =
+                first.balances.values().filter(|b| b.currency == currency).map(|b| b.amount.clone()).sum();
+            let total_after: MonetaryValue =
+                last.balances.values().filter(|b| b.currency == currency).map(|b| b.amount.clone()).sum();
+            let external_delta: MonetaryValue = diff
+                .new_financial_transactions
+                .iter()
+                .filter(|t| t.is_external && t.currency == currency)
+                .map(|t| t.amount.clone())
+                .sum();
+
+            if total_after != total_before.clone() + external_delta.clone() {
+                return Some(ViolationDetails {
+                    invariant_id: "SEQ_MON_001".to_string(),
+                    invariant_name: "Sequence Balance Conservation".to_string(),
+                    category: InvariantCategory::Monetary,
+                    message: "Balance conservation violation across the sequence - money created or destroyed over the whole chain".to_string(),
+                    severity: Severity::Critical,
+                    confidence: 1.0,
+                    evidence: vec![Evidence::new(
+                        format!("balances[{:?}]", currency),
+                        "sum of account balances for this currency changed across the sequence by more than the recorded external transfers",
+                    )
+                    .with_value(serde_json::json!({
+                        "total_before": total_before.0.to_string(),
+                        "total_after": total_after.0.to_string(),
+                        "external_delta": external_delta.0.to_string(),
+                    }))],
+                });
+            }
+        }
+        None
+    }
+
+    /// Workflow position monotonicity across the whole sequence: each
+    /// session's `step_index` must never decrease between any two states
+    /// in the chain, even non-adjacent ones.
+    fn check_sequence_workflow_monotonicity(states: &[ApplicationState]) -> Option<ViolationDetails> {
+        let mut last_step: HashMap<SessionId, u32> = HashMap::new();
+
+        for state in states {
+            for (session_id, step) in &state.workflow_positions {
+                if let Some(&prev) = last_step.get(session_id) {
+                    if step.step_index < prev {
+                        return Some(ViolationDetails {
+                            invariant_id: "SEQ_WF_001".to_string(),
+                            invariant_name: "Sequence Workflow Monotonicity".to_string(),
+                            category: InvariantCategory::Workflow,
+                            message: "Workflow step regressed somewhere across the sequence".to_string(),
+                            severity: Severity::Medium,
+                            confidence: 1.0,
+                            evidence: vec![Evidence::new(
+                                format!("workflow_positions.{}", session_id.0),
+                                "workflow step index decreased between two states in the sequence",
+                            )
+                            .with_value(serde_json::json!({
+                                "previous_step": prev,
+                                "regressed_step": step.step_index,
+                            }))],
+                        });
+                    }
+                }
+                last_step.insert(session_id.clone(), step.step_index);
+            }
+        }
+        None
     }
 
     /// Validate against specific invariant categories
@@ -103,26 +435,36 @@ impl InvariantValidator {
         after: &ApplicationState,
         categories: &[InvariantCategory],
     ) -> ValidationResult {
+        let mut span = self.telemetry.start_span("invariant.validate_categories", &[]);
+
         let mut violations = Vec::new();
         let mut checked = Vec::new();
+        let mut obligations = self.obligations.write();
+        obligations.discharge(before, after);
 
         for category in categories {
             for invariant in self.catalog.get_by_category(*category) {
                 checked.push(invariant.id.clone());
-                
-                if !invariant.validate(before, after) {
-                    violations.push(ViolationDetails {
-                        invariant_id: invariant.id.clone(),
-                        invariant_name: invariant.name.clone(),
-                        category: invariant.category,
-                        message: invariant.violation_message.clone(),
-                        severity: self.determine_severity(&invariant.category),
-                        confidence: 1.0,
-                    });
+                if let Some(violation) = self.check(invariant, before, after, &mut obligations) {
+                    self.report_violation(&violation);
+                    span.add_event(
+                        "invariant.violation",
+                        &[
+                            ("invariant_id", violation.invariant_id.clone()),
+                            ("confidence", violation.confidence.to_string()),
+                        ],
+                    );
+                    violations.push(violation);
                 }
             }
         }
 
+        span.set_attribute("checked", checked.len().to_string());
+        span.set_attribute("violations", violations.len().to_string());
+        if let Some(highest) = violations.iter().map(|v| v.severity).max() {
+            span.set_attribute("highest_severity", format!("{:?}", highest));
+        }
+
         if violations.is_empty() {
             ValidationResult::valid(checked)
         } else {
@@ -138,24 +480,24 @@ impl InvariantValidator {
         after: &ApplicationState,
     ) -> Option<ValidationResult> {
         let invariant = self.catalog.get(invariant_id)?;
-        
-        if invariant.validate(before, after) {
-            Some(ValidationResult::valid(vec![invariant_id.to_string()]))
-        } else {
-            Some(ValidationResult::violation(
-                vec![ViolationDetails {
-                    invariant_id: invariant.id.clone(),
-                    invariant_name: invariant.name.clone(),
-                    category: invariant.category,
-                    message: invariant.violation_message.clone(),
-                    severity: self.determine_severity(&invariant.category),
-                    confidence: 1.0,
-                }],
-                vec![invariant_id.to_string()],
-            ))
+        let mut obligations = self.obligations.write();
+
+        match self.check(invariant, before, after, &mut obligations) {
+            Some(violation) => {
+                self.report_violation(&violation);
+                Some(ValidationResult::violation(vec![violation], vec![invariant_id.to_string()]))
+            }
+            None => Some(ValidationResult::valid(vec![invariant_id.to_string()])),
         }
     }
 
+    /// Obligations raised by deferred invariants that remain undischarged.
+    /// Call at a terminal state (end of session/workflow) to surface
+    /// two-phase invariants that were never completed.
+    pub fn open_obligations(&self) -> Vec<(String, String)> {
+        self.obligations.read().open_obligations()
+    }
+
     /// Determine severity based on invariant category
     fn determine_severity(&self, category: &InvariantCategory) -> Severity {
         match category {
@@ -190,7 +532,7 @@ impl InvariantValidator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::state::ApplicationState;
+    use crate::state::{ApplicationState, FinancialTransaction};
 
     #[test]
     fn test_validator_creation() {
@@ -203,20 +545,225 @@ mod tests {
     fn test_valid_transition() {
         let catalog = Arc::new(InvariantCatalog::new());
         let validator = InvariantValidator::new(catalog);
-        
+
         let before = ApplicationState::default();
         let after = ApplicationState::default();
-        
+
         let result = validator.validate_transition(&before, &after);
         assert!(result.is_valid);
         assert!(result.violations.is_empty());
     }
 
+    #[test]
+    fn test_audited_transition_chains_into_ledger() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+        let ledger = ValidationLedger::new();
+
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+
+        let (result, head) = validator.validate_transition_audited(&before, &after, &ledger);
+        assert!(result.is_valid);
+        assert_eq!(head, ledger.head_digest());
+        assert!(ledger.verify());
+        assert!(!ledger.is_empty());
+    }
+
+    #[test]
+    fn test_session_fixation_raises_an_obligation_instead_of_failing() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+
+        let mut before = ApplicationState::default();
+        before.current_session = Some(crate::state::SessionState {
+            session_id: SessionId("sess-1".to_string()),
+            user_id: UserId("alice".to_string()),
+            roles: Default::default(),
+            authenticated: false,
+            created_at: chrono::Utc::now(),
+            last_activity: chrono::Utc::now(),
+        });
+
+        let mut after = before.clone();
+        after.current_session = Some(crate::state::SessionState {
+            session_id: SessionId("sess-2".to_string()),
+            user_id: UserId("alice".to_string()),
+            roles: Default::default(),
+            authenticated: true,
+            created_at: chrono::Utc::now(),
+            last_activity: chrono::Utc::now(),
+        });
+
+        let result = validator.validate_transition(&before, &after);
+        assert!(result.is_valid, "a deferred verdict is not yet a violation");
+        assert!(!validator.open_obligations().is_empty());
+    }
+
+    #[test]
+    fn test_validator_telemetry_toggle_does_not_affect_validation_result() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+
+        validator.set_tracing_enabled(false);
+        let result = validator.validate_transition(&before, &after);
+        validator.set_tracing_enabled(true);
+
+        assert!(result.is_valid);
+        assert!(!result.checked_invariants.is_empty());
+    }
+
+    #[test]
+    fn test_untouched_invariants_are_still_reported_as_checked() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+
+        let before = ApplicationState::default();
+        let after = ApplicationState::default();
+
+        let result = validator.validate_transition(&before, &after);
+        assert!(result.is_valid);
+        // A no-op transition touches nothing, so every invariant with a
+        // declared `touched_fields` is skipped - but still counts as checked.
+        assert!(result.checked_invariants.contains(&"MON_001".to_string()));
+    }
+
+    #[test]
+    fn test_balance_only_change_still_catches_a_monetary_violation() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+
+        let before = ApplicationState::default();
+        let mut after = before.clone();
+        after.balances.insert(AccountId("acc1".to_string()), Balance::new(-50, Currency::USD));
+
+        let result = validator.validate_transition(&before, &after);
+        assert!(!result.is_valid, "touched_fields skipping must not mask a real monetary violation");
+    }
+
+    #[test]
+    fn test_validate_sequence_is_trivially_valid_below_two_states() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+
+        assert!(validator.validate_sequence(&[]).is_valid);
+        assert!(validator.validate_sequence(&[ApplicationState::default()]).is_valid);
+    }
+
+    #[test]
+    fn test_validate_sequence_reports_the_failing_step_index() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+
+        let s0 = ApplicationState::default();
+        let s1 = s0.clone();
+        let mut s2 = s1.clone();
+        s2.balances.insert(AccountId("acc1".to_string()), Balance::new(-50, Currency::USD));
+
+        let result = validator.validate_sequence(&[s0, s1, s2]);
+        assert!(!result.is_valid);
+        assert_eq!(result.failing_step, Some(1));
+    }
+
+    #[test]
+    fn test_sequence_balance_conservation_flags_an_aggregate_mismatch() {
+        let mut first = ApplicationState::default();
+        first.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let mut last = first.clone();
+        last.balances.insert(AccountId("acc1".to_string()), Balance::new(150, Currency::USD));
+
+        let violation = InvariantValidator::check_sequence_balance_conservation(&[first, last]);
+        assert_eq!(violation.unwrap().invariant_id, "SEQ_MON_001");
+    }
+
+    #[test]
+    fn test_sequence_balance_conservation_holds_when_reconciled_by_an_external_transaction() {
+        let mut first = ApplicationState::default();
+        first.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let mut last = first.clone();
+        last.balances.insert(AccountId("acc1".to_string()), Balance::new(150, Currency::USD));
+        last.financial_transactions.push(FinancialTransaction {
+            id: "tx_1".to_string(),
+            from_account: None,
+            to_account: Some(AccountId("acc1".to_string())),
+            amount: MonetaryValue::from_integer(50),
+            currency: Currency::USD,
+            converted_amount: None,
+            converted_currency: None,
+            is_external: true,
+            timestamp: chrono::Utc::now(),
+        });
+
+        assert!(InvariantValidator::check_sequence_balance_conservation(&[first, last]).is_none());
+    }
+
+    #[test]
+    fn test_validate_sequence_catches_workflow_regression_across_non_adjacent_states() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+
+        let session_id = SessionId("sess-1".to_string());
+        let mut s0 = ApplicationState::default();
+        s0.workflow_positions.insert(
+            session_id.clone(),
+            WorkflowStep { workflow_id: "checkout".to_string(), step_index: 2, step_name: "payment".to_string() },
+        );
+
+        let s1 = s0.clone();
+
+        let mut s2 = s1.clone();
+        s2.workflow_positions.insert(
+            session_id.clone(),
+            WorkflowStep { workflow_id: "checkout".to_string(), step_index: 1, step_name: "cart".to_string() },
+        );
+
+        let result = validator.validate_sequence(&[s0, s1, s2]);
+        assert!(!result.is_valid);
+        assert!(result.violations.iter().any(|v| v.invariant_id == "SEQ_WF_001"));
+    }
+
+    #[test]
+    fn test_configured_reporter_is_invoked_for_each_detected_violation() {
+        use super::super::reporter::BufferReporter;
+
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+        let reporter = Arc::new(BufferReporter::new());
+        validator.set_reporter(Some(reporter.clone()));
+
+        let before = ApplicationState::default();
+        let mut after = before.clone();
+        after.balances.insert(AccountId("acc1".to_string()), Balance::new(-50, Currency::USD));
+
+        let result = validator.validate_transition(&before, &after);
+        assert!(!result.is_valid);
+        assert_eq!(reporter.events().len(), result.violations.len());
+        assert!(reporter.events().iter().any(|e| e.invariant_id == "MON_001"));
+    }
+
+    #[test]
+    fn test_no_reporter_configured_does_not_affect_validation_result() {
+        let catalog = Arc::new(InvariantCatalog::new());
+        let validator = InvariantValidator::new(catalog);
+
+        let before = ApplicationState::default();
+        let mut after = before.clone();
+        after.balances.insert(AccountId("acc1".to_string()), Balance::new(-50, Currency::USD));
+
+        let result = validator.validate_transition(&before, &after);
+        assert!(!result.is_valid);
+    }
+
     #[test]
     fn test_severity_determination() {
         let catalog = Arc::new(InvariantCatalog::new());
         let validator = InvariantValidator::new(catalog);
-        
+
         assert_eq!(
             validator.determine_severity(&InvariantCategory::Monetary),
             Severity::Critical
@@ -226,4 +773,4 @@ mod tests {
             Severity::High
         );
     }
-}
\ No newline at end of file
+}
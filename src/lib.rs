@@ -6,8 +6,10 @@
 pub mod invariant;
 pub mod state;
 pub mod proof;
+pub mod provenance;
 pub mod types;
 pub mod ffi;
+pub mod telemetry;
 
 use pyo3::prelude::*;
 
@@ -0,0 +1,355 @@
+//! Arrow Export - Columnar export of causal findings for analytics
+//!
+//! Feature-gated behind `arrow_export` (off by default, like `otel` is for
+//! OpenTelemetry): this module and its `arrow`/`parquet` dependencies only
+//! exist when a caller actually wants to load a large capture session
+//! into a dataframe. Every row flattens a `StateChange` together with the
+//! confidence/timestamp of the `CausalLink` that produced it and the id
+//! of the invariant the enclosing `Proof` found violated, rather than a
+//! relational schema — one wide table analytics tools can filter columns
+//! on directly, no joins required. Nested JSON values (`old_value`/
+//! `new_value`) stay UTF-8 strings rather than an Arrow union type,
+//! trading a `serde_json::from_str` on read for a schema that never
+//! churns as new `StateChangeType` variants are added.
+
+#![cfg(feature = "arrow_export")]
+
+use super::causal::{CausalChain, StateChange, StateChangeType};
+use super::Proof;
+use arrow::array::{
+    Array, ArrayRef, DictionaryArray, Float64Array, StringArray, StringDictionaryBuilder,
+    TimestampMillisecondArray,
+};
+use arrow::datatypes::{DataType, Field, Int8Type, Schema, SchemaRef, TimeUnit};
+use arrow::error::ArrowError;
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// One detected change, flattened for columnar export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrowChangeRow {
+    pub change_type: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub confidence: f64,
+    pub timestamp_ms: i64,
+    pub invariant_id: String,
+}
+
+/// Error exporting to, or reading back from, Arrow/Parquet.
+#[derive(Debug)]
+pub enum ArrowExportError {
+    Arrow(ArrowError),
+    Io(std::io::Error),
+    Decode(String),
+}
+
+impl fmt::Display for ArrowExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrowExportError::Arrow(e) => write!(f, "arrow error: {e}"),
+            ArrowExportError::Io(e) => write!(f, "io error: {e}"),
+            ArrowExportError::Decode(message) => write!(f, "decode error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ArrowExportError {}
+
+impl From<ArrowError> for ArrowExportError {
+    fn from(e: ArrowError) -> Self {
+        ArrowExportError::Arrow(e)
+    }
+}
+
+impl From<std::io::Error> for ArrowExportError {
+    fn from(e: std::io::Error) -> Self {
+        ArrowExportError::Io(e)
+    }
+}
+
+fn change_type_label(change_type: &StateChangeType) -> String {
+    match change_type {
+        StateChangeType::OwnershipChange => "OwnershipChange".to_string(),
+        StateChangeType::BalanceChange => "BalanceChange".to_string(),
+        StateChangeType::RoleChange => "RoleChange".to_string(),
+        StateChangeType::WorkflowAdvance => "WorkflowAdvance".to_string(),
+        StateChangeType::DataModification => "DataModification".to_string(),
+        StateChangeType::SessionChange => "SessionChange".to_string(),
+        StateChangeType::Custom(name) => format!("Custom:{name}"),
+    }
+}
+
+fn change_type_from_label(label: &str) -> StateChangeType {
+    match label {
+        "OwnershipChange" => StateChangeType::OwnershipChange,
+        "BalanceChange" => StateChangeType::BalanceChange,
+        "RoleChange" => StateChangeType::RoleChange,
+        "WorkflowAdvance" => StateChangeType::WorkflowAdvance,
+        "DataModification" => StateChangeType::DataModification,
+        "SessionChange" => StateChangeType::SessionChange,
+        other => StateChangeType::Custom(other.strip_prefix("Custom:").unwrap_or(other).to_string()),
+    }
+}
+
+impl ArrowChangeRow {
+    fn from_change(change: &StateChange, confidence: f64, timestamp_ms: i64, invariant_id: &str) -> Self {
+        Self {
+            change_type: change_type_label(&change.change_type),
+            field: change.field.clone(),
+            old_value: change.old_value.as_ref().map(|v| v.to_string()),
+            new_value: change.new_value.as_ref().map(|v| v.to_string()),
+            confidence,
+            timestamp_ms,
+            invariant_id: invariant_id.to_string(),
+        }
+    }
+
+    /// Reconstruct the `StateChange` this row was built from. The
+    /// confidence/timestamp/invariant_id context is dropped, matching
+    /// `StateChange`'s own fields.
+    pub fn to_state_change(&self) -> Result<StateChange, ArrowExportError> {
+        Ok(StateChange {
+            change_type: change_type_from_label(&self.change_type),
+            field: self.field.clone(),
+            old_value: self
+                .old_value
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .map_err(|e| ArrowExportError::Decode(e.to_string()))?,
+            new_value: self
+                .new_value
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .map_err(|e| ArrowExportError::Decode(e.to_string()))?,
+        })
+    }
+}
+
+/// Flatten every link in `chain`, tagged with `invariant_id`, into rows.
+pub fn rows_from_chain(chain: &CausalChain, invariant_id: &str) -> Vec<ArrowChangeRow> {
+    chain
+        .links
+        .iter()
+        .flat_map(|link| {
+            let timestamp_ms = link.timestamp.timestamp_millis();
+            link.state_changes
+                .iter()
+                .map(move |change| ArrowChangeRow::from_change(change, link.confidence, timestamp_ms, invariant_id))
+        })
+        .collect()
+}
+
+/// Flatten `proof.causality_chain`, tagged with the invariant it violated.
+pub fn rows_from_proof(proof: &Proof) -> Vec<ArrowChangeRow> {
+    rows_from_chain(&proof.causality_chain, &proof.invariant_violated)
+}
+
+/// The stable schema every `ArrowChangeRow` batch is written with.
+pub fn schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new(
+            "change_type",
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+            false,
+        ),
+        Field::new("field", DataType::Utf8, false),
+        Field::new("old_value", DataType::Utf8, true),
+        Field::new("new_value", DataType::Utf8, true),
+        Field::new("confidence", DataType::Float64, false),
+        Field::new("timestamp", DataType::Timestamp(TimeUnit::Millisecond, None), false),
+        Field::new("invariant_id", DataType::Utf8, false),
+    ]))
+}
+
+/// Build one `RecordBatch` from `rows`, against `schema()`.
+pub fn rows_to_batch(rows: &[ArrowChangeRow]) -> Result<RecordBatch, ArrowExportError> {
+    let mut change_type_builder = StringDictionaryBuilder::<Int8Type>::new();
+    for row in rows {
+        change_type_builder.append_value(&row.change_type);
+    }
+    let change_type: ArrayRef = Arc::new(change_type_builder.finish());
+    let field: ArrayRef = Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.field.clone())));
+    let old_value: ArrayRef = Arc::new(StringArray::from_iter(rows.iter().map(|r| r.old_value.clone())));
+    let new_value: ArrayRef = Arc::new(StringArray::from_iter(rows.iter().map(|r| r.new_value.clone())));
+    let confidence: ArrayRef = Arc::new(Float64Array::from_iter_values(rows.iter().map(|r| r.confidence)));
+    let timestamp: ArrayRef = Arc::new(TimestampMillisecondArray::from_iter_values(rows.iter().map(|r| r.timestamp_ms)));
+    let invariant_id: ArrayRef = Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.invariant_id.clone())));
+
+    Ok(RecordBatch::try_new(
+        schema(),
+        vec![change_type, field, old_value, new_value, confidence, timestamp, invariant_id],
+    )?)
+}
+
+/// Reconstruct the rows held by a `RecordBatch` built by `rows_to_batch`.
+pub fn batch_to_rows(batch: &RecordBatch) -> Result<Vec<ArrowChangeRow>, ArrowExportError> {
+    let decode_err = |column: &str| ArrowExportError::Decode(format!("{column} column has an unexpected Arrow type"));
+
+    let change_type = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<DictionaryArray<Int8Type>>()
+        .ok_or_else(|| decode_err("change_type"))?;
+    let change_type_values = change_type
+        .values()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or_else(|| decode_err("change_type"))?;
+    let field = batch.column(1).as_any().downcast_ref::<StringArray>().ok_or_else(|| decode_err("field"))?;
+    let old_value = batch.column(2).as_any().downcast_ref::<StringArray>().ok_or_else(|| decode_err("old_value"))?;
+    let new_value = batch.column(3).as_any().downcast_ref::<StringArray>().ok_or_else(|| decode_err("new_value"))?;
+    let confidence = batch.column(4).as_any().downcast_ref::<Float64Array>().ok_or_else(|| decode_err("confidence"))?;
+    let timestamp = batch
+        .column(5)
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .ok_or_else(|| decode_err("timestamp"))?;
+    let invariant_id = batch.column(6).as_any().downcast_ref::<StringArray>().ok_or_else(|| decode_err("invariant_id"))?;
+
+    let mut rows = Vec::with_capacity(batch.num_rows());
+    for i in 0..batch.num_rows() {
+        let key = change_type.keys().value(i) as usize;
+        rows.push(ArrowChangeRow {
+            change_type: change_type_values.value(key).to_string(),
+            field: field.value(i).to_string(),
+            old_value: if old_value.is_null(i) { None } else { Some(old_value.value(i).to_string()) },
+            new_value: if new_value.is_null(i) { None } else { Some(new_value.value(i).to_string()) },
+            confidence: confidence.value(i),
+            timestamp_ms: timestamp.value(i),
+            invariant_id: invariant_id.value(i).to_string(),
+        });
+    }
+    Ok(rows)
+}
+
+/// Writes/reads `ArrowChangeRow` batches as Arrow IPC files, and streams
+/// `Proof` collections out as Parquet for downstream analytics tools.
+pub struct ArrowExporter;
+
+impl ArrowExporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write `batches` to `path` as a single Arrow IPC file.
+    pub fn write_batches(&self, path: &Path, batches: &[RecordBatch]) -> Result<(), ArrowExportError> {
+        let file = File::create(path)?;
+        let mut writer = FileWriter::try_new(file, &schema())?;
+        for batch in batches {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Read back every batch in an Arrow IPC file written by `write_batches`.
+    pub fn read_batches(&self, path: &Path) -> Result<Vec<RecordBatch>, ArrowExportError> {
+        let file = File::open(path)?;
+        let reader = FileReader::try_new(file, None)?;
+        reader.collect::<Result<Vec<_>, _>>().map_err(ArrowExportError::from)
+    }
+
+    /// Flatten `proofs` into rows and stream them to a single Parquet file
+    /// at `path`, for loading straight into a dataframe.
+    pub fn write_proofs_parquet(&self, path: &Path, proofs: &[Proof]) -> Result<(), ArrowExportError> {
+        let rows: Vec<ArrowChangeRow> = proofs.iter().flat_map(rows_from_proof).collect();
+        let batch = rows_to_batch(&rows)?;
+
+        let file = File::create(path)?;
+        let mut writer = parquet::arrow::ArrowWriter::try_new(file, schema(), None)
+            .map_err(|e| ArrowExportError::Decode(e.to_string()))?;
+        writer.write(&batch).map_err(|e| ArrowExportError::Decode(e.to_string()))?;
+        writer.close().map_err(|e| ArrowExportError::Decode(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl Default for ArrowExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::causal::{CausalLink, StateChangeType};
+    use crate::state::Action;
+    use crate::types::ActionTiming;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn test_chain() -> CausalChain {
+        let mut chain = CausalChain::new();
+        chain.add_link(CausalLink {
+            action: Action {
+                id: "a1".to_string(),
+                action_type: crate::state::ActionType::Payment,
+                request: None,
+                parameters: HashMap::new(),
+                authentication: None,
+                timing: ActionTiming { start_time: Utc::now(), end_time: Utc::now(), duration_ms: 5 },
+            },
+            state_changes: vec![StateChange {
+                change_type: StateChangeType::BalanceChange,
+                field: "balances.acc1".to_string(),
+                old_value: Some(serde_json::json!(100)),
+                new_value: Some(serde_json::json!(50)),
+            }],
+            confidence: 0.9,
+            timestamp: Utc::now(),
+        });
+        chain
+    }
+
+    #[test]
+    fn test_rows_from_chain_carries_confidence_and_invariant_id() {
+        let rows = rows_from_chain(&test_chain(), "inv-1");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].change_type, "BalanceChange");
+        assert_eq!(rows[0].invariant_id, "inv-1");
+        assert!((rows[0].confidence - 0.9).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_batch_round_trips_through_arrow_ipc() {
+        let rows = rows_from_chain(&test_chain(), "inv-1");
+        let batch = rows_to_batch(&rows).unwrap();
+
+        let path = std::env::temp_dir().join(format!("arrow-export-test-{}.arrow", uuid::Uuid::new_v4()));
+        let exporter = ArrowExporter::new();
+        exporter.write_batches(&path, &[batch]).unwrap();
+
+        let read_back = exporter.read_batches(&path).unwrap();
+        assert_eq!(read_back.len(), 1);
+
+        let decoded_rows = batch_to_rows(&read_back[0]).unwrap();
+        assert_eq!(decoded_rows, rows);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_row_reconstructs_its_original_state_change() {
+        let change = StateChange {
+            change_type: StateChangeType::Custom("exotic".to_string()),
+            field: "x".to_string(),
+            old_value: None,
+            new_value: Some(serde_json::json!("y")),
+        };
+        let row = ArrowChangeRow::from_change(&change, 0.5, 0, "inv-1");
+        let rebuilt = row.to_state_change().unwrap();
+
+        assert_eq!(rebuilt.field, change.field);
+        assert_eq!(rebuilt.change_type, change.change_type);
+        assert_eq!(rebuilt.new_value, change.new_value);
+    }
+}
@@ -0,0 +1,231 @@
+//! Assertion Engine - Evaluates `StateAssertion`/`Assertion` against a
+//! captured `ApplicationState`, and reconciles `ExpectedOutcome` against an
+//! observed `StateDiff`, so replay can judge pass/fail instead of just
+//! recording what happened.
+
+use super::causal::StateDiff;
+use super::replay::{Assertion, AssertionOperator, ExpectedOutcome, StateAssertion};
+use crate::state::ApplicationState;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// Outcome of evaluating a single `Assertion` against a captured state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionOutcome {
+    pub field: String,
+    pub operator: AssertionOperator,
+    pub actual: Option<Value>,
+    pub passed: bool,
+}
+
+/// Per-assertion pass/fail report for one `StateAssertion`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssertionReport {
+    pub outcomes: Vec<AssertionOutcome>,
+}
+
+impl AssertionReport {
+    /// Whether every assertion in the report passed. Vacuously true for an
+    /// empty report.
+    pub fn all_passed(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.passed)
+    }
+
+    /// The assertions that failed, for callers that want to report why.
+    pub fn failures(&self) -> impl Iterator<Item = &AssertionOutcome> {
+        self.outcomes.iter().filter(|outcome| !outcome.passed)
+    }
+}
+
+/// Evaluate every `Assertion` in `assertion` against `state`, resolving
+/// each `Assertion::field` as a dotted path into `state`'s JSON
+/// serialization (e.g. `balances.acc1.amount`; a numeric path segment
+/// indexes into a JSON array).
+pub fn evaluate(state: &ApplicationState, assertion: &StateAssertion) -> AssertionReport {
+    let state_json = serde_json::to_value(state).unwrap_or(Value::Null);
+    let outcomes = assertion
+        .assertions
+        .iter()
+        .map(|a| evaluate_one(&state_json, a))
+        .collect();
+    AssertionReport { outcomes }
+}
+
+fn evaluate_one(state_json: &Value, assertion: &Assertion) -> AssertionOutcome {
+    let actual = resolve_path(state_json, &assertion.field).cloned();
+    let passed = apply_operator(assertion.operator, &assertion.expected_value, actual.as_ref());
+    AssertionOutcome {
+        field: assertion.field.clone(),
+        operator: assertion.operator,
+        actual,
+        passed,
+    }
+}
+
+/// Walk a dotted path (`"balances.acc1.amount"`) into a JSON value,
+/// descending into objects by key and into arrays by parsing the segment
+/// as an index. Returns `None` as soon as a segment doesn't resolve,
+/// rather than erroring - a missing field is exactly what `Exists`/
+/// `NotExists` need to observe.
+fn resolve_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |current, segment| match current {
+        Value::Object(map) => map.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+fn apply_operator(operator: AssertionOperator, expected: &Value, actual: Option<&Value>) -> bool {
+    match operator {
+        AssertionOperator::Exists => actual.is_some(),
+        AssertionOperator::NotExists => actual.is_none(),
+        AssertionOperator::Equals => values_equal(actual, expected),
+        AssertionOperator::NotEquals => !values_equal(actual, expected),
+        AssertionOperator::GreaterThan => match (actual.and_then(numeric_value), numeric_value(expected)) {
+            (Some(a), Some(e)) => a > e,
+            _ => false,
+        },
+        AssertionOperator::LessThan => match (actual.and_then(numeric_value), numeric_value(expected)) {
+            (Some(a), Some(e)) => a < e,
+            _ => false,
+        },
+        AssertionOperator::Contains => contains(actual, expected),
+        AssertionOperator::NotContains => !contains(actual, expected),
+    }
+}
+
+fn values_equal(actual: Option<&Value>, expected: &Value) -> bool {
+    match actual {
+        Some(actual) => actual == expected || matches!((numeric_value(actual), numeric_value(expected)), (Some(a), Some(e)) if a == e),
+        None => false,
+    }
+}
+
+/// A field's value as an exact `BigRational`, for operators that compare
+/// numerically rather than by JSON equality. Handles both a bare JSON
+/// number and a [`crate::types::MonetaryValue`]'s serialized
+/// `{"numer": "...", "denom": "..."}` form - the shape every monetary
+/// field (e.g. `balances.<account>.amount`) actually serializes to, not
+/// a JSON number. Parses numer/denom as `BigInt` rather than downcasting
+/// to `f64`, so large numerators/denominators compare exactly instead of
+/// risking precision loss or saturating to `inf`/`NaN`.
+fn numeric_value(value: &Value) -> Option<BigRational> {
+    if let Some(n) = value.as_i64() {
+        return Some(BigRational::from_integer(BigInt::from(n)));
+    }
+    if let Some(n) = value.as_f64() {
+        return BigRational::from_float(n);
+    }
+    let numer = BigInt::from_str(value.get("numer")?.as_str()?).ok()?;
+    let denom = BigInt::from_str(value.get("denom")?.as_str()?).ok()?;
+    if denom == BigInt::from(0) {
+        return None;
+    }
+    Some(BigRational::new(numer, denom))
+}
+
+fn contains(actual: Option<&Value>, expected: &Value) -> bool {
+    match actual {
+        Some(Value::Array(items)) => items.contains(expected),
+        Some(Value::String(haystack)) => expected.as_str().map(|needle| haystack.contains(needle)).unwrap_or(false),
+        Some(Value::Object(map)) => expected.as_str().map(|key| map.contains_key(key)).unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Confirm `expected`'s `state_changes` against an observed `diff`: every
+/// `ExpectedStateChange` must match a diffed field of the same name whose
+/// `StateChangeType` debug representation equals `change_type`. Vacuously
+/// true when no state changes were expected.
+pub fn confirms_expected_outcome(diff: &StateDiff, expected: &ExpectedOutcome) -> bool {
+    if expected.state_changes.is_empty() {
+        return true;
+    }
+    let changes = diff.clone().into_changes();
+    expected.state_changes.iter().all(|expected_change| {
+        changes
+            .iter()
+            .any(|change| change.field == expected_change.field && format!("{:?}", change.change_type) == expected_change.change_type)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AccountId, Balance, Currency};
+
+    fn state_with_balance(amount: i64) -> ApplicationState {
+        let mut state = ApplicationState::default();
+        state.balances.insert(AccountId("acc1".to_string()), Balance::new(amount, Currency::USD));
+        state
+    }
+
+    fn assertion(field: &str, operator: AssertionOperator, expected: Value) -> StateAssertion {
+        StateAssertion {
+            assertions: vec![Assertion {
+                field: field.to_string(),
+                operator,
+                expected_value: expected,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_equals_passes_when_the_resolved_field_matches() {
+        let state = state_with_balance(100);
+        let report = evaluate(&state, &assertion("balances.acc1.amount", AssertionOperator::Equals, serde_json::json!(100)));
+
+        assert!(report.all_passed());
+    }
+
+    #[test]
+    fn test_greater_than_fails_when_the_resolved_field_is_lower() {
+        let state = state_with_balance(50);
+        let report = evaluate(&state, &assertion("balances.acc1.amount", AssertionOperator::GreaterThan, serde_json::json!(100)));
+
+        assert!(!report.all_passed());
+        assert_eq!(report.failures().count(), 1);
+    }
+
+    #[test]
+    fn test_greater_than_passes_against_a_monetary_field_serialized_as_numer_denom() {
+        let state = state_with_balance(150);
+        let report = evaluate(&state, &assertion("balances.acc1.amount", AssertionOperator::GreaterThan, serde_json::json!(100)));
+
+        assert!(report.all_passed());
+    }
+
+    #[test]
+    fn test_not_equals_passes_when_a_monetary_field_differs_from_the_expected_number() {
+        let state = state_with_balance(50);
+        let report = evaluate(&state, &assertion("balances.acc1.amount", AssertionOperator::NotEquals, serde_json::json!(100)));
+
+        assert!(report.all_passed());
+    }
+
+    #[test]
+    fn test_not_exists_passes_for_a_field_that_never_resolves() {
+        let state = ApplicationState::default();
+        let report = evaluate(&state, &assertion("balances.missing.amount", AssertionOperator::NotExists, Value::Null));
+
+        assert!(report.all_passed());
+    }
+
+    #[test]
+    fn test_confirms_expected_outcome_is_vacuously_true_with_no_expected_changes() {
+        assert!(confirms_expected_outcome(&StateDiff::default(), &ExpectedOutcome::default()));
+    }
+
+    #[test]
+    fn test_numeric_value_compares_monetary_fields_exactly_beyond_f64_precision() {
+        // 2^63 + 1 and 2^63 + 2 round to the same f64, but must not compare equal.
+        let a = serde_json::json!({"numer": "9223372036854775809", "denom": "1"});
+        let b = serde_json::json!({"numer": "9223372036854775810", "denom": "1"});
+
+        assert_ne!(numeric_value(&a), numeric_value(&b));
+        assert!(numeric_value(&b).unwrap() > numeric_value(&a).unwrap());
+    }
+}
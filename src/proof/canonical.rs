@@ -0,0 +1,209 @@
+//! Canonical Encoding - Deterministic byte encoding for content-addressed
+//! hashing of `Proof`, `CausalChain`, and `StateChange`.
+//!
+//! A SCALE-style codec over each value's `serde_json::Value` projection:
+//! fixed little-endian integers, length-prefixed strings/arrays/objects,
+//! and each variant tagged by a leading index byte. Object entries are
+//! re-sorted by key before encoding rather than trusted to already be in
+//! order, so a `HashMap` field's iteration order (the ownership/balance
+//! maps inside `ApplicationState`, an `Action`'s parameters, etc.) can
+//! never leak into the result. `VERSION` is prefixed so a future change to
+//! this scheme can't silently collide with hashes computed under this one.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Bumped whenever the encoding below changes.
+const VERSION: u8 = 1;
+
+/// Error decoding a value previously produced by `canonical_bytes`.
+#[derive(Debug)]
+pub enum CanonicalError {
+    UnsupportedVersion(u8),
+    Truncated,
+    UnknownTag(u8),
+    InvalidUtf8,
+}
+
+impl fmt::Display for CanonicalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanonicalError::UnsupportedVersion(v) => write!(f, "unsupported canonical encoding version {v}"),
+            CanonicalError::Truncated => write!(f, "canonical bytes truncated"),
+            CanonicalError::UnknownTag(t) => write!(f, "unknown canonical value tag {t}"),
+            CanonicalError::InvalidUtf8 => write!(f, "canonical bytes contained invalid utf-8"),
+        }
+    }
+}
+
+impl std::error::Error for CanonicalError {}
+
+fn encode_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+fn encode_value(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.push(0),
+        Value::Bool(b) => {
+            out.push(1);
+            out.push(u8::from(*b));
+        }
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                out.push(2);
+                out.extend_from_slice(&i.to_le_bytes());
+            } else {
+                out.push(3);
+                out.extend_from_slice(&n.as_f64().unwrap_or(0.0).to_le_bytes());
+            }
+        }
+        Value::String(s) => {
+            out.push(4);
+            encode_bytes(s.as_bytes(), out);
+        }
+        Value::Array(items) => {
+            out.push(5);
+            out.extend_from_slice(&(items.len() as u32).to_le_bytes());
+            for item in items {
+                encode_value(item, out);
+            }
+        }
+        Value::Object(map) => {
+            out.push(6);
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+            for (key, val) in entries {
+                encode_bytes(key.as_bytes(), out);
+                encode_value(val, out);
+            }
+        }
+    }
+}
+
+/// Canonical, versioned byte encoding of `value`, stable regardless of the
+/// iteration order of any `HashMap`/`HashSet` it was built from: two
+/// logically identical values always encode to identical bytes.
+pub fn canonical_bytes<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut out = vec![VERSION];
+    encode_value(&serde_json::to_value(value).unwrap_or(Value::Null), &mut out);
+    out
+}
+
+fn take<'a>(bytes: &'a [u8], pos: &mut usize, n: usize) -> Result<&'a [u8], CanonicalError> {
+    let end = pos.checked_add(n).ok_or(CanonicalError::Truncated)?;
+    let slice = bytes.get(*pos..end).ok_or(CanonicalError::Truncated)?;
+    *pos = end;
+    Ok(slice)
+}
+
+fn take_u32(bytes: &[u8], pos: &mut usize) -> Result<u32, CanonicalError> {
+    Ok(u32::from_le_bytes(take(bytes, pos, 4)?.try_into().unwrap()))
+}
+
+fn take_string(bytes: &[u8], pos: &mut usize) -> Result<String, CanonicalError> {
+    let len = take_u32(bytes, pos)? as usize;
+    String::from_utf8(take(bytes, pos, len)?.to_vec()).map_err(|_| CanonicalError::InvalidUtf8)
+}
+
+fn decode_value(bytes: &[u8], pos: &mut usize) -> Result<Value, CanonicalError> {
+    let tag = *take(bytes, pos, 1)?.first().ok_or(CanonicalError::Truncated)?;
+    match tag {
+        0 => Ok(Value::Null),
+        1 => Ok(Value::Bool(take(bytes, pos, 1)?[0] != 0)),
+        2 => {
+            let i = i64::from_le_bytes(take(bytes, pos, 8)?.try_into().unwrap());
+            Ok(Value::Number(i.into()))
+        }
+        3 => {
+            let f = f64::from_le_bytes(take(bytes, pos, 8)?.try_into().unwrap());
+            Ok(serde_json::Number::from_f64(f).map(Value::Number).unwrap_or(Value::Null))
+        }
+        4 => Ok(Value::String(take_string(bytes, pos)?)),
+        5 => {
+            let len = take_u32(bytes, pos)? as usize;
+            let mut items = Vec::with_capacity(len);
+            for _ in 0..len {
+                items.push(decode_value(bytes, pos)?);
+            }
+            Ok(Value::Array(items))
+        }
+        6 => {
+            let len = take_u32(bytes, pos)? as usize;
+            let mut map = Map::with_capacity(len);
+            for _ in 0..len {
+                let key = take_string(bytes, pos)?;
+                let value = decode_value(bytes, pos)?;
+                map.insert(key, value);
+            }
+            Ok(Value::Object(map))
+        }
+        other => Err(CanonicalError::UnknownTag(other)),
+    }
+}
+
+/// Decode bytes previously produced by `canonical_bytes` back into a
+/// `serde_json::Value`. Since the canonical form is a projection (object
+/// keys are re-sorted, integers and floats are disambiguated by tag
+/// rather than type), this recovers the same `Value` the encoder saw, not
+/// necessarily a byte-identical re-encoding of the original struct — but
+/// re-encoding that `Value` with `canonical_bytes` does round-trip.
+pub fn canonical_decode(bytes: &[u8]) -> Result<Value, CanonicalError> {
+    let mut pos = 0;
+    let version = *bytes.first().ok_or(CanonicalError::Truncated)?;
+    if version != VERSION {
+        return Err(CanonicalError::UnsupportedVersion(version));
+    }
+    pos += 1;
+    decode_value(bytes, &mut pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_canonical_bytes_is_stable_across_map_insertion_order() {
+        let mut a = HashMap::new();
+        a.insert("z", 1);
+        a.insert("a", 2);
+        a.insert("m", 3);
+
+        let mut b = HashMap::new();
+        b.insert("m", 3);
+        b.insert("a", 2);
+        b.insert("z", 1);
+
+        assert_eq!(canonical_bytes(&a), canonical_bytes(&b));
+    }
+
+    #[test]
+    fn test_canonical_bytes_differ_for_different_values() {
+        let a: HashMap<&str, i32> = HashMap::from([("x", 1)]);
+        let b: HashMap<&str, i32> = HashMap::from([("x", 2)]);
+        assert_ne!(canonical_bytes(&a), canonical_bytes(&b));
+    }
+
+    #[test]
+    fn test_canonical_decode_round_trips_through_re_encoding() {
+        let original: HashMap<&str, Vec<i32>> = HashMap::from([
+            ("alpha", vec![1, 2, 3]),
+            ("beta", vec![]),
+        ]);
+
+        let encoded = canonical_bytes(&original);
+        let decoded = canonical_decode(&encoded).unwrap();
+
+        assert_eq!(canonical_bytes(&decoded), encoded);
+    }
+
+    #[test]
+    fn test_canonical_decode_rejects_an_unknown_version() {
+        let err = canonical_decode(&[99, 0]).unwrap_err();
+        assert!(matches!(err, CanonicalError::UnsupportedVersion(99)));
+    }
+}
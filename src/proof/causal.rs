@@ -1,10 +1,13 @@
 //! Causal Engine - Establishes cause-effect relationships
 
-use crate::state::{Action, ApplicationState, StateTransition};
+use super::prov_export::{ProvActivity, ProvAgent, ProvAssociation, ProvDocument, ProvEntity, ProvUsage};
+use crate::state::{Action, ApplicationState, SessionState, StateTransition};
+use crate::telemetry::Telemetry;
 use crate::types::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// A link in the causal chain
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,7 +19,7 @@ pub struct CausalLink {
 }
 
 /// Description of a state change
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StateChange {
     pub change_type: StateChangeType,
     pub field: String,
@@ -24,8 +27,24 @@ pub struct StateChange {
     pub new_value: Option<serde_json::Value>,
 }
 
+impl StateChange {
+    /// Deterministic, versioned byte encoding of this change — see
+    /// `canonical::canonical_bytes`.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        super::canonical::canonical_bytes(self)
+    }
+
+    /// SHA-256 over `canonical_bytes`, hex-encoded.
+    pub fn content_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(self.canonical_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
 /// Types of state changes
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum StateChangeType {
     OwnershipChange,
     BalanceChange,
@@ -36,6 +55,95 @@ pub enum StateChangeType {
     Custom(String),
 }
 
+/// One key's change, modeled on Ethereum's pod-state diffing: the key
+/// either appeared, disappeared, or had its value replaced, each carrying
+/// the JSON-encoded value(s) so the entry is self-describing without the
+/// original states.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DiffEntry {
+    Added { new: serde_json::Value },
+    Removed { old: serde_json::Value },
+    Changed { old: serde_json::Value, new: serde_json::Value },
+}
+
+impl DiffEntry {
+    fn old_value(&self) -> Option<serde_json::Value> {
+        match self {
+            DiffEntry::Added { .. } => None,
+            DiffEntry::Removed { old } | DiffEntry::Changed { old, .. } => Some(old.clone()),
+        }
+    }
+
+    fn new_value(&self) -> Option<serde_json::Value> {
+        match self {
+            DiffEntry::Removed { .. } => None,
+            DiffEntry::Added { new } | DiffEntry::Changed { new, .. } => Some(new.clone()),
+        }
+    }
+}
+
+/// Canonical, auditable diff between two `ApplicationState`s, computed by
+/// [`CausalEngine::diff_states`] across every tracked sub-map - balances,
+/// ownership, workflow positions, data objects, session identity and
+/// roles - rather than the handful of ad-hoc field checks
+/// `detect_state_changes` used to perform. Each diffed key is grouped
+/// under the `StateChangeType` it belongs to, so `into_changes` yields a
+/// deterministic, complete set of `StateChange`s for a `CausalLink`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StateDiff {
+    entries: HashMap<StateChangeType, Vec<(String, DiffEntry)>>,
+}
+
+impl StateDiff {
+    fn push(&mut self, change_type: StateChangeType, field: String, entry: DiffEntry) {
+        self.entries.entry(change_type).or_insert_with(Vec::new).push((field, entry));
+    }
+
+    /// Whether no tracked sub-map differed between the two states.
+    pub fn is_empty(&self) -> bool {
+        self.entries.values().all(|entries| entries.is_empty())
+    }
+
+    /// Total number of diffed keys across every `StateChangeType`.
+    pub fn len(&self) -> usize {
+        self.entries.values().map(|entries| entries.len()).sum()
+    }
+
+    /// Flatten into the `StateChange`s a `CausalLink` records. Ordered by
+    /// `StateChangeType` (debug representation) and then by field name, so
+    /// the result is deterministic regardless of the `HashMap`/`HashSet`
+    /// iteration order the diff was built from.
+    pub fn into_changes(self) -> Vec<StateChange> {
+        let mut grouped: Vec<(StateChangeType, Vec<(String, DiffEntry)>)> = self.entries.into_iter().collect();
+        grouped.sort_by_key(|(change_type, _)| format!("{change_type:?}"));
+
+        let mut changes = Vec::new();
+        for (change_type, mut fields) in grouped {
+            fields.sort_by(|a, b| a.0.cmp(&b.0));
+            for (field, entry) in fields {
+                changes.push(StateChange {
+                    old_value: entry.old_value(),
+                    new_value: entry.new_value(),
+                    change_type: change_type.clone(),
+                    field,
+                });
+            }
+        }
+        changes
+    }
+}
+
+/// One link's action referencing a field that an earlier link's effect
+/// modified, as found by `CausalEngine::build_chain_from_sequence`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    /// Index into `CausalChain::links`: the link whose effect enabled `to`.
+    pub from: usize,
+    /// Index into `CausalChain::links`: the link whose action depended on it.
+    pub to: usize,
+    pub field: String,
+}
+
 /// Complete causal chain from action to effect
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CausalChain {
@@ -44,6 +152,11 @@ pub struct CausalChain {
     pub final_effect: Option<StateChange>,
     pub is_complete: bool,
     pub confidence: f64,
+    /// Edges discovered by `build_chain_from_sequence` linking a link's
+    /// action to an earlier link's effect it depended on. Empty for
+    /// chains built by `build_chain` from a single transition.
+    #[serde(default)]
+    pub dependencies: Vec<DependencyEdge>,
 }
 
 impl CausalChain {
@@ -54,6 +167,7 @@ impl CausalChain {
             final_effect: None,
             is_complete: false,
             confidence: 0.0,
+            dependencies: Vec::new(),
         }
     }
 
@@ -82,11 +196,21 @@ impl CausalChain {
             self.confidence = 0.0;
             return;
         }
-        
-        // Chain confidence is product of individual link confidences
-        self.confidence = self.links.iter()
-            .map(|l| l.confidence)
-            .product();
+
+        // Length-normalized geometric mean of the links' confidences,
+        // rather than their raw product: a straight product decays toward
+        // zero as the chain grows even when every link is individually
+        // strong, which makes long chains look weaker than they are.
+        let mean_log: f64 = self.links.iter()
+            .map(|l| l.confidence.max(f64::MIN_POSITIVE).ln())
+            .sum::<f64>() / self.links.len() as f64;
+        self.confidence = mean_log.exp();
+    }
+
+    /// The link with the lowest confidence, for triage: the chain-level
+    /// confidence alone doesn't say which single link is the weak point.
+    pub fn weakest_link(&self) -> Option<&CausalLink> {
+        self.links.iter().min_by(|a, b| a.confidence.partial_cmp(&b.confidence).unwrap())
     }
 
     /// Mark chain as complete
@@ -105,6 +229,113 @@ impl CausalChain {
     pub fn is_empty(&self) -> bool {
         self.links.is_empty()
     }
+
+    /// Deterministic, versioned byte encoding of this chain — see
+    /// `canonical::canonical_bytes`.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        super::canonical::canonical_bytes(self)
+    }
+
+    /// SHA-256 over `canonical_bytes`, hex-encoded.
+    pub fn content_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(self.canonical_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Export this chain as a W3C PROV document: each link's action
+    /// becomes an Activity, each of its state changes an Entity
+    /// `wasGeneratedBy` that activity (qualified with the link's
+    /// confidence and timestamp), and the action's authenticated user (if
+    /// any) an Agent `wasAssociatedWith` it. An activity also `used` the
+    /// entity produced by the previous link's final effect, capturing the
+    /// dependency one action's effect has on the action before it.
+    pub fn to_prov(&self) -> ProvDocument {
+        let mut document = ProvDocument::default();
+        let mut seen_agents = HashSet::new();
+
+        for (i, link) in self.links.iter().enumerate() {
+            let activity_id = format!("activity-{}", link.action.id);
+            document.activities.push(ProvActivity {
+                id: activity_id.clone(),
+                started_at: link.action.timing.start_time,
+                ended_at: link.action.timing.end_time,
+                action: link.action.clone(),
+            });
+
+            if let Some(user_id) = link.action.authentication.as_ref().and_then(|auth| auth.user_id.as_ref()) {
+                let agent_id = format!("agent-{}", user_id.0);
+                if seen_agents.insert(agent_id.clone()) {
+                    document.agents.push(ProvAgent { id: agent_id.clone(), user_id: user_id.clone() });
+                }
+                document.associated_with.push(ProvAssociation { activity_id: activity_id.clone(), agent_id });
+            }
+
+            for (j, change) in link.state_changes.iter().enumerate() {
+                document.entities.push(ProvEntity {
+                    id: format!("entity-{i}-{j}"),
+                    generated_by: activity_id.clone(),
+                    confidence: link.confidence,
+                    timestamp: link.timestamp,
+                    change: change.clone(),
+                });
+            }
+
+            if let Some(previous) = i.checked_sub(1).and_then(|p| self.links.get(p)) {
+                if let Some(last_index) = previous.state_changes.len().checked_sub(1) {
+                    document.used.push(ProvUsage {
+                        activity_id: activity_id.clone(),
+                        entity_id: format!("entity-{}-{}", i - 1, last_index),
+                    });
+                }
+            }
+        }
+
+        document
+    }
+
+    /// Reconstruct a `CausalChain` from a `ProvDocument`, the inverse of
+    /// `to_prov`, so externally-produced provenance can be ingested and
+    /// re-validated against this engine's `AttributionRule`s. Links are
+    /// rebuilt in activity-start order; an activity with no generated
+    /// entities in the document contributes no link.
+    pub fn from_prov(document: &ProvDocument) -> Self {
+        let mut activities = document.activities.clone();
+        activities.sort_by_key(|a| a.started_at);
+
+        let mut chain = CausalChain::new();
+        for activity in &activities {
+            let state_changes: Vec<StateChange> = document.entities.iter()
+                .filter(|e| e.generated_by == activity.id)
+                .map(|e| e.change.clone())
+                .collect();
+
+            if state_changes.is_empty() {
+                continue;
+            }
+
+            let confidence = document.entities.iter()
+                .filter(|e| e.generated_by == activity.id)
+                .map(|e| e.confidence)
+                .sum::<f64>() / state_changes.len() as f64;
+
+            let timestamp = document.entities.iter()
+                .find(|e| e.generated_by == activity.id)
+                .map(|e| e.timestamp)
+                .unwrap_or(activity.started_at);
+
+            chain.add_link(CausalLink {
+                action: activity.action.clone(),
+                state_changes,
+                confidence,
+                timestamp,
+            });
+        }
+
+        chain.complete();
+        chain
+    }
 }
 
 impl Default for CausalChain {
@@ -116,6 +347,7 @@ impl Default for CausalChain {
 /// Engine for establishing causal relationships
 pub struct CausalEngine {
     attribution_rules: Vec<AttributionRule>,
+    telemetry: Telemetry,
 }
 
 /// Rule for attributing state changes to actions
@@ -129,11 +361,18 @@ impl CausalEngine {
     pub fn new() -> Self {
         let mut engine = Self {
             attribution_rules: Vec::new(),
+            telemetry: Telemetry::init(),
         };
         engine.register_default_rules();
         engine
     }
 
+    /// Enable or disable this engine's tracing/metrics at runtime. See
+    /// [`Telemetry::set_enabled`].
+    pub fn set_tracing_enabled(&self, enabled: bool) {
+        self.telemetry.set_enabled(enabled);
+    }
+
     /// Register default attribution rules
     fn register_default_rules(&mut self) {
         // HTTP request -> state change attribution
@@ -169,23 +408,40 @@ impl CausalEngine {
 
     /// Build causal chain from state transition
     pub fn build_chain(&self, transition: &StateTransition) -> CausalChain {
+        let started = std::time::Instant::now();
+        let _span = self.telemetry.start_span(
+            "causal_engine.build_chain",
+            &[("transition_id", transition.id.clone())],
+        );
+
         let mut chain = CausalChain::new();
-        
+
         let state_changes = self.detect_state_changes(&transition.from_state, &transition.to_state);
-        
+
         if state_changes.is_empty() {
             return chain;
         }
 
-        // Find best matching rule for each change
+        // Combine every matching rule's confidence for each change, rather
+        // than just taking the strongest one: independent rules pointing
+        // at the same change should reinforce each other.
         let mut attributed_changes = Vec::new();
         for change in state_changes {
-            let best_rule = self.attribution_rules.iter()
+            let matching: Vec<f64> = self.attribution_rules.iter()
                 .filter(|rule| (rule.matcher)(&transition.triggering_action, &change))
-                .max_by(|a, b| a.confidence.partial_cmp(&b.confidence).unwrap());
-            
-            if let Some(rule) = best_rule {
-                attributed_changes.push((change, rule.confidence));
+                .map(|rule| rule.confidence)
+                .collect();
+
+            if !matching.is_empty() {
+                let confidence = noisy_or(&matching);
+                let _change_span = self.telemetry.start_span(
+                    "causal_engine.attribute_change",
+                    &[
+                        ("change_type", format!("{:?}", change.change_type)),
+                        ("confidence", confidence.to_string()),
+                    ],
+                );
+                attributed_changes.push((change, confidence));
             }
         }
 
@@ -193,85 +449,343 @@ impl CausalEngine {
             let avg_confidence = attributed_changes.iter()
                 .map(|(_, c)| c)
                 .sum::<f64>() / attributed_changes.len() as f64;
-            
+
             chain.add_link(CausalLink {
                 action: transition.triggering_action.clone(),
                 state_changes: attributed_changes.into_iter().map(|(c, _)| c).collect(),
                 confidence: avg_confidence,
                 timestamp: transition.timestamp,
             });
-            
+
             chain.complete();
+            self.telemetry.record_chain_completed(chain.len(), started.elapsed().as_millis() as u64);
         }
 
         chain
     }
 
-    /// Detect state changes between two states
-    fn detect_state_changes(&self, before: &ApplicationState, after: &ApplicationState) -> Vec<StateChange> {
-        let mut changes = Vec::new();
+    /// Build a chain from a sequence of transitions, linking them
+    /// transitively: each transition is attributed independently (as in
+    /// `build_chain`), then a link's action is connected back to the
+    /// nearest earlier link whose effect it references — matched by the
+    /// modified field's id showing up in the action's request URL/body or
+    /// parameters. `root_action` is the first link with no incoming
+    /// dependency, `final_effect` the terminal change (the last attributed
+    /// link's last change), and `is_complete` only holds when there is an
+    /// unbroken dependency path from root to that final link.
+    pub fn build_chain_from_sequence(&self, transitions: &[StateTransition]) -> CausalChain {
+        let started = std::time::Instant::now();
+        let _span = self.telemetry.start_span(
+            "causal_engine.build_chain_from_sequence",
+            &[("transition_count", transitions.len().to_string())],
+        );
 
-        // Check ownership changes
-        for (obj_id, new_owner) in &after.ownership {
-            let old_owner = before.ownership.get(obj_id);
-            if old_owner != Some(new_owner) {
-                changes.push(StateChange {
-                    change_type: StateChangeType::OwnershipChange,
-                    field: format!("ownership.{}", obj_id.0),
-                    old_value: old_owner.map(|o| serde_json::json!(o.0)),
-                    new_value: Some(serde_json::json!(new_owner.0)),
-                });
+        let mut ordered: Vec<&StateTransition> = transitions.iter().collect();
+        ordered.sort_by_key(|t| t.timestamp);
+
+        let mut chain = CausalChain::new();
+        let mut modified_fields: Vec<(usize, String)> = Vec::new();
+
+        for transition in ordered {
+            let state_changes = self.detect_state_changes(&transition.from_state, &transition.to_state);
+            if state_changes.is_empty() {
+                continue;
+            }
+
+            let mut attributed_changes = Vec::new();
+            for change in state_changes {
+                let matching: Vec<f64> = self.attribution_rules.iter()
+                    .filter(|rule| (rule.matcher)(&transition.triggering_action, &change))
+                    .map(|rule| rule.confidence)
+                    .collect();
+
+                if !matching.is_empty() {
+                    let confidence = noisy_or(&matching);
+                    let _change_span = self.telemetry.start_span(
+                        "causal_engine.attribute_change",
+                        &[
+                            ("change_type", format!("{:?}", change.change_type)),
+                            ("confidence", confidence.to_string()),
+                        ],
+                    );
+                    attributed_changes.push((change, confidence));
+                }
+            }
+
+            if attributed_changes.is_empty() {
+                continue;
+            }
+
+            let avg_confidence = attributed_changes.iter()
+                .map(|(_, c)| c)
+                .sum::<f64>() / attributed_changes.len() as f64;
+
+            let link_index = chain.links.len();
+            let nearest = modified_fields
+                .iter()
+                .rev()
+                .find(|(_, field)| Self::action_references_field(&transition.triggering_action, field))
+                .map(|(index, _)| *index);
+            for (earlier_index, field) in &modified_fields {
+                if Some(*earlier_index) == nearest
+                    && Self::action_references_field(&transition.triggering_action, field)
+                {
+                    chain.dependencies.push(DependencyEdge {
+                        from: *earlier_index,
+                        to: link_index,
+                        field: field.clone(),
+                    });
+                }
             }
+
+            for (change, _) in &attributed_changes {
+                modified_fields.push((link_index, change.field.clone()));
+            }
+
+            chain.add_link(CausalLink {
+                action: transition.triggering_action.clone(),
+                state_changes: attributed_changes.into_iter().map(|(c, _)| c).collect(),
+                confidence: avg_confidence,
+                timestamp: transition.timestamp,
+            });
         }
 
-        // Check balance changes
-        for (acc_id, new_balance) in &after.balances {
-            let old_balance = before.balances.get(acc_id);
-            if old_balance.map(|b| b.amount) != Some(new_balance.amount) {
-                changes.push(StateChange {
-                    change_type: StateChangeType::BalanceChange,
-                    field: format!("balances.{}", acc_id.0),
-                    old_value: old_balance.map(|b| serde_json::json!(b.amount)),
-                    new_value: Some(serde_json::json!(new_balance.amount)),
-                });
+        if let Some(root_index) = Self::root_link_index(&chain) {
+            chain.root_action = Some(chain.links[root_index].action.clone());
+        }
+        chain.is_complete = Self::has_unbroken_path(&chain);
+
+        if !chain.links.is_empty() {
+            self.telemetry.record_chain_completed(chain.len(), started.elapsed().as_millis() as u64);
+        }
+
+        chain
+    }
+
+    /// The index of the first link with no incoming `DependencyEdge`, if
+    /// the chain has any links.
+    fn root_link_index(chain: &CausalChain) -> Option<usize> {
+        if chain.links.is_empty() {
+            return None;
+        }
+        let has_incoming: HashSet<usize> = chain.dependencies.iter().map(|e| e.to).collect();
+        (0..chain.links.len()).find(|i| !has_incoming.contains(i))
+    }
+
+    /// Whether the dependency graph connects the chain's root link to its
+    /// terminal link (the one `final_effect` came from).
+    fn has_unbroken_path(chain: &CausalChain) -> bool {
+        let Some(root_index) = Self::root_link_index(chain) else { return false };
+        let last_index = chain.links.len() - 1;
+        if root_index == last_index {
+            return true;
+        }
+
+        let mut visited = HashSet::new();
+        let mut frontier = vec![root_index];
+        visited.insert(root_index);
+        while let Some(current) = frontier.pop() {
+            if current == last_index {
+                return true;
+            }
+            for edge in &chain.dependencies {
+                if edge.from == current && visited.insert(edge.to) {
+                    frontier.push(edge.to);
+                }
             }
         }
+        false
+    }
 
-        // Check session changes
-        match (&before.current_session, &after.current_session) {
-            (None, Some(session)) => {
-                changes.push(StateChange {
-                    change_type: StateChangeType::SessionChange,
-                    field: "current_session".to_string(),
-                    old_value: None,
-                    new_value: Some(serde_json::json!(session.session_id.0)),
-                });
+    /// Whether `action` appears to reference `field` (e.g. `"ownership.obj1"`):
+    /// its id (the part after the first `.`) shows up in the request's URL
+    /// or body, or in any parameter value.
+    fn action_references_field(action: &Action, field: &str) -> bool {
+        let Some((_, id)) = field.split_once('.') else { return false };
+
+        if let Some(request) = &action.request {
+            if request.url.contains(id) {
+                return true;
             }
-            (Some(old), Some(new)) if old.session_id != new.session_id => {
-                changes.push(StateChange {
-                    change_type: StateChangeType::SessionChange,
-                    field: "current_session".to_string(),
-                    old_value: Some(serde_json::json!(old.session_id.0)),
-                    new_value: Some(serde_json::json!(new.session_id.0)),
-                });
+            if let Some(body) = &request.body {
+                if let Ok(text) = std::str::from_utf8(body) {
+                    if text.contains(id) {
+                        return true;
+                    }
+                }
             }
-            _ => {}
         }
 
-        // Check workflow changes
-        for (session_id, new_step) in &after.workflow_positions {
-            let old_step = before.workflow_positions.get(session_id);
-            if old_step.map(|s| s.step_index) != Some(new_step.step_index) {
-                changes.push(StateChange {
-                    change_type: StateChangeType::WorkflowAdvance,
-                    field: format!("workflow.{}", session_id.0),
-                    old_value: old_step.map(|s| serde_json::json!(s.step_index)),
-                    new_value: Some(serde_json::json!(new_step.step_index)),
-                });
+        action.parameters.values().any(|value| value.to_string().contains(id))
+    }
+
+    /// Canonical diff between two `ApplicationState`s, across every
+    /// tracked sub-map rather than the handful of fields
+    /// `detect_state_changes` used to special-case. See [`StateDiff`].
+    pub fn diff_states(&self, before: &ApplicationState, after: &ApplicationState) -> StateDiff {
+        let mut diff = StateDiff::default();
+
+        Self::diff_map(
+            &mut diff,
+            StateChangeType::OwnershipChange,
+            "ownership",
+            &before.ownership,
+            &after.ownership,
+            |id| id.0.clone(),
+            |owner| serde_json::json!(owner.0),
+        );
+
+        Self::diff_map(
+            &mut diff,
+            StateChangeType::BalanceChange,
+            "balances",
+            &before.balances,
+            &after.balances,
+            |id| id.0.clone(),
+            |balance| serde_json::json!(balance),
+        );
+
+        Self::diff_map(
+            &mut diff,
+            StateChangeType::WorkflowAdvance,
+            "workflow",
+            &before.workflow_positions,
+            &after.workflow_positions,
+            |id| id.0.clone(),
+            |step| serde_json::json!(step),
+        );
+
+        Self::diff_map(
+            &mut diff,
+            StateChangeType::DataModification,
+            "data_objects",
+            &before.data_objects,
+            &after.data_objects,
+            |id| id.0.clone(),
+            |data| serde_json::json!(data),
+        );
+
+        Self::diff_session(&mut diff, &before.current_session, &after.current_session);
+
+        Self::diff_set(
+            &mut diff,
+            StateChangeType::Custom("overdraft_permission".to_string()),
+            "overdraft_permissions",
+            &before.overdraft_permissions,
+            &after.overdraft_permissions,
+            |id| id.0.clone(),
+        );
+
+        diff
+    }
+
+    /// Diff a single `HashMap` sub-state: every key in the union of both
+    /// maps whose value differs (added, removed, or changed) becomes one
+    /// `DiffEntry` under `change_type`, keyed `{prefix}.{key}`.
+    fn diff_map<K, V>(
+        diff: &mut StateDiff,
+        change_type: StateChangeType,
+        prefix: &str,
+        before: &HashMap<K, V>,
+        after: &HashMap<K, V>,
+        key_name: impl Fn(&K) -> String,
+        to_json: impl Fn(&V) -> serde_json::Value,
+    ) where
+        K: std::hash::Hash + Eq,
+        V: PartialEq,
+    {
+        let keys: HashSet<&K> = before.keys().chain(after.keys()).collect();
+        for key in keys {
+            let field = format!("{prefix}.{}", key_name(key));
+            match (before.get(key), after.get(key)) {
+                (None, Some(new)) => diff.push(change_type.clone(), field, DiffEntry::Added { new: to_json(new) }),
+                (Some(old), None) => diff.push(change_type.clone(), field, DiffEntry::Removed { old: to_json(old) }),
+                (Some(old), Some(new)) if old != new => {
+                    diff.push(change_type.clone(), field, DiffEntry::Changed { old: to_json(old), new: to_json(new) })
+                }
+                _ => {}
             }
         }
+    }
 
-        changes
+    /// Diff a `HashSet` sub-state: a member present in only one side
+    /// becomes an `Added`/`Removed` entry keyed `{prefix}.{member}`. Sets
+    /// have no notion of "changed" - a member is either present or not.
+    fn diff_set<T>(
+        diff: &mut StateDiff,
+        change_type: StateChangeType,
+        prefix: &str,
+        before: &HashSet<T>,
+        after: &HashSet<T>,
+        key_name: impl Fn(&T) -> String,
+    ) where
+        T: std::hash::Hash + Eq + Serialize,
+    {
+        for member in after.difference(before) {
+            diff.push(
+                change_type.clone(),
+                format!("{prefix}.{}", key_name(member)),
+                DiffEntry::Added { new: serde_json::json!(member) },
+            );
+        }
+        for member in before.difference(after) {
+            diff.push(
+                change_type.clone(),
+                format!("{prefix}.{}", key_name(member)),
+                DiffEntry::Removed { old: serde_json::json!(member) },
+            );
+        }
+    }
+
+    /// Diff `current_session`'s identity and role set. A session swap
+    /// (including appearing or disappearing) is one `SessionChange` entry
+    /// keyed on the session id; a role grant/revoke within the *same*
+    /// session additionally produces `RoleChange` entries, since role
+    /// membership can change independently of the session identity.
+    fn diff_session(diff: &mut StateDiff, before: &Option<SessionState>, after: &Option<SessionState>) {
+        match (before, after) {
+            (None, Some(session)) => {
+                diff.push(
+                    StateChangeType::SessionChange,
+                    "current_session".to_string(),
+                    DiffEntry::Added { new: serde_json::json!(session.session_id.0) },
+                );
+            }
+            (Some(session), None) => {
+                diff.push(
+                    StateChangeType::SessionChange,
+                    "current_session".to_string(),
+                    DiffEntry::Removed { old: serde_json::json!(session.session_id.0) },
+                );
+            }
+            (Some(old), Some(new)) => {
+                if old.session_id != new.session_id {
+                    diff.push(
+                        StateChangeType::SessionChange,
+                        "current_session".to_string(),
+                        DiffEntry::Changed {
+                            old: serde_json::json!(old.session_id.0),
+                            new: serde_json::json!(new.session_id.0),
+                        },
+                    );
+                } else {
+                    Self::diff_set(
+                        diff,
+                        StateChangeType::RoleChange,
+                        &format!("session.{}.roles", new.session_id.0),
+                        &old.roles,
+                        &new.roles,
+                        |role| role.0.clone(),
+                    );
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Detect state changes between two states, via `diff_states`.
+    fn detect_state_changes(&self, before: &ApplicationState, after: &ApplicationState) -> Vec<StateChange> {
+        self.diff_states(before, after).into_changes()
     }
 
     /// Validate that an action caused a specific effect
@@ -282,14 +796,28 @@ impl CausalEngine {
 
     /// Get confidence that action caused effect
     pub fn get_causality_confidence(&self, action: &Action, effect: &StateChange) -> f64 {
-        self.attribution_rules.iter()
+        let matching: Vec<f64> = self.attribution_rules.iter()
             .filter(|rule| (rule.matcher)(action, effect))
             .map(|rule| rule.confidence)
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap_or(0.0)
+            .collect();
+
+        if matching.is_empty() {
+            0.0
+        } else {
+            noisy_or(&matching)
+        }
     }
 }
 
+/// Combine independent confidences as if they were independent indicators
+/// of the same event, `P = 1 - ∏(1 - p_i)`: each additional matching rule
+/// raises the combined confidence, it never caps out at a single rule's
+/// own ceiling. Degenerates to `p` itself when only one confidence is
+/// given.
+fn noisy_or(confidences: &[f64]) -> f64 {
+    1.0 - confidences.iter().map(|p| 1.0 - p).product::<f64>()
+}
+
 impl Default for CausalEngine {
     fn default() -> Self {
         Self::new()
@@ -330,4 +858,291 @@ mod tests {
         assert!(!changes.is_empty());
         assert!(changes.iter().any(|c| matches!(c.change_type, StateChangeType::BalanceChange)));
     }
+
+    fn http_transition(
+        before: ApplicationState,
+        after: ApplicationState,
+        url: &str,
+        start_time: DateTime<Utc>,
+    ) -> StateTransition {
+        StateTransition {
+            id: uuid::Uuid::new_v4().to_string(),
+            from_state: before,
+            to_state: after,
+            triggering_action: Action {
+                id: uuid::Uuid::new_v4().to_string(),
+                action_type: crate::state::ActionType::HttpRequest,
+                request: Some(HttpRequest {
+                    method: HttpMethod::POST,
+                    url: url.to_string(),
+                    headers: HashMap::new(),
+                    body: None,
+                    timestamp: start_time,
+                }),
+                parameters: HashMap::new(),
+                authentication: None,
+                timing: ActionTiming { start_time, end_time: start_time, duration_ms: 5 },
+            },
+            timestamp: start_time,
+        }
+    }
+
+    #[test]
+    fn test_build_chain_from_sequence_links_dependent_actions() {
+        let engine = CausalEngine::new();
+
+        let t0 = Utc::now();
+        let t1 = t0 + chrono::Duration::seconds(1);
+
+        let empty = ApplicationState::default();
+        let mut owned = ApplicationState::default();
+        owned.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+
+        // First transition creates obj1's ownership.
+        let first = http_transition(empty, owned.clone(), "/objects", t0);
+
+        // Second transition's request references obj1, and changes its
+        // ownership again - it should link back to the first.
+        let mut reowned = owned.clone();
+        reowned.ownership.insert(ObjectId("obj1".to_string()), UserId("bob".to_string()));
+        let second = http_transition(owned, reowned, "/objects/obj1/transfer", t1);
+
+        let chain = engine.build_chain_from_sequence(&[second, first]);
+
+        assert_eq!(chain.links.len(), 2);
+        assert_eq!(chain.dependencies.len(), 1);
+        assert_eq!(chain.dependencies[0], DependencyEdge { from: 0, to: 1, field: "ownership.obj1".to_string() });
+        assert!(chain.is_complete);
+    }
+
+    #[test]
+    fn test_build_chain_from_sequence_unrelated_actions_are_incomplete() {
+        let engine = CausalEngine::new();
+
+        let t0 = Utc::now();
+        let t1 = t0 + chrono::Duration::seconds(1);
+
+        let mut first_after = ApplicationState::default();
+        first_after.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        let first = http_transition(ApplicationState::default(), first_after, "/objects", t0);
+
+        let mut second_after = ApplicationState::default();
+        second_after.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+        let second = http_transition(ApplicationState::default(), second_after, "/unrelated", t1);
+
+        let chain = engine.build_chain_from_sequence(&[first, second]);
+
+        assert!(chain.dependencies.is_empty());
+        assert!(!chain.is_complete);
+    }
+
+    #[test]
+    fn test_noisy_or_combines_independent_confidences_above_any_single_one() {
+        let combined = noisy_or(&[0.5, 0.5]);
+        assert!(combined > 0.5);
+        assert!((combined - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_noisy_or_is_identity_for_a_single_confidence() {
+        assert!((noisy_or(&[0.73]) - 0.73).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_chain_confidence_does_not_collapse_on_a_long_high_confidence_chain() {
+        let mut chain = CausalChain::new();
+        for _ in 0..5 {
+            chain.add_link(CausalLink {
+                action: http_transition(
+                    ApplicationState::default(),
+                    ApplicationState::default(),
+                    "/x",
+                    Utc::now(),
+                ).triggering_action,
+                state_changes: vec![StateChange {
+                    change_type: StateChangeType::Custom("x".to_string()),
+                    field: "x".to_string(),
+                    old_value: None,
+                    new_value: None,
+                }],
+                confidence: 0.95,
+                timestamp: Utc::now(),
+            });
+        }
+
+        // A five-link chain of 0.95-confidence links should stay close to
+        // 0.95, not collapse toward 0.95^5 ≈ 0.77.
+        assert!(chain.confidence > 0.9);
+    }
+
+    #[test]
+    fn test_weakest_link_returns_the_minimum_confidence_link() {
+        let mut chain = CausalChain::new();
+        for confidence in [0.9, 0.4, 0.8] {
+            chain.add_link(CausalLink {
+                action: http_transition(
+                    ApplicationState::default(),
+                    ApplicationState::default(),
+                    "/x",
+                    Utc::now(),
+                ).triggering_action,
+                state_changes: vec![StateChange {
+                    change_type: StateChangeType::Custom("x".to_string()),
+                    field: "x".to_string(),
+                    old_value: None,
+                    new_value: None,
+                }],
+                confidence,
+                timestamp: Utc::now(),
+            });
+        }
+
+        assert!((chain.weakest_link().unwrap().confidence - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_state_change_content_hash_is_stable_and_sensitive() {
+        let a = StateChange {
+            change_type: StateChangeType::BalanceChange,
+            field: "balances.acc1".to_string(),
+            old_value: Some(serde_json::json!(100)),
+            new_value: Some(serde_json::json!(50)),
+        };
+        let b = StateChange { field: "balances.acc1".to_string(), ..a.clone() };
+        let c = StateChange { new_value: Some(serde_json::json!(49)), ..a.clone() };
+
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_ne!(a.content_hash(), c.content_hash());
+    }
+
+    #[test]
+    fn test_causal_chain_content_hash_is_stable_for_an_identical_chain() {
+        let mut a = CausalChain::new();
+        a.add_link(CausalLink {
+            action: http_transition(ApplicationState::default(), ApplicationState::default(), "/x", Utc::now()).triggering_action,
+            state_changes: vec![StateChange {
+                change_type: StateChangeType::Custom("x".to_string()),
+                field: "x".to_string(),
+                old_value: None,
+                new_value: None,
+            }],
+            confidence: 0.9,
+            timestamp: Utc::now(),
+        });
+        let b = a.clone();
+
+        assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+        assert_eq!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn test_diff_states_covers_every_tracked_sub_map() {
+        let engine = CausalEngine::new();
+
+        let mut before = ApplicationState::default();
+        before.current_session = Some(SessionState {
+            session_id: SessionId("sess-1".to_string()),
+            user_id: UserId("alice".to_string()),
+            roles: HashSet::from([Role("user".to_string())]),
+            authenticated: true,
+            created_at: Utc::now(),
+            last_activity: Utc::now(),
+        });
+
+        let mut after = before.clone();
+        after.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        after.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+        after.workflow_positions.insert(
+            SessionId("sess-1".to_string()),
+            WorkflowStep { workflow_id: "checkout".to_string(), step_index: 1, step_name: "cart".to_string() },
+        );
+        after.data_objects.insert(
+            ObjectId("obj1".to_string()),
+            crate::state::DataObject {
+                id: ObjectId("obj1".to_string()),
+                data_type: "note".to_string(),
+                content_hash: "abc".to_string(),
+                last_modified: Utc::now(),
+                version: 1,
+            },
+        );
+        after.overdraft_permissions.insert(AccountId("acc1".to_string()));
+        if let Some(session) = &mut after.current_session {
+            session.roles.insert(Role("admin".to_string()));
+        }
+
+        let diff = engine.diff_states(&before, &after);
+        assert!(!diff.is_empty());
+
+        let changes = diff.into_changes();
+        let types: HashSet<_> = changes.iter().map(|c| c.change_type.clone()).collect();
+        assert!(types.contains(&StateChangeType::OwnershipChange));
+        assert!(types.contains(&StateChangeType::BalanceChange));
+        assert!(types.contains(&StateChangeType::WorkflowAdvance));
+        assert!(types.contains(&StateChangeType::DataModification));
+        assert!(types.contains(&StateChangeType::RoleChange));
+        assert!(types.contains(&StateChangeType::Custom("overdraft_permission".to_string())));
+
+        assert!(changes.iter().any(|c| c.field == "session.sess-1.roles.admin"
+            && matches!(&c.old_value, None)
+            && c.new_value == Some(serde_json::json!("admin"))));
+    }
+
+    #[test]
+    fn test_diff_states_is_empty_for_identical_states() {
+        let engine = CausalEngine::new();
+        let state = ApplicationState::default();
+
+        let diff = engine.diff_states(&state, &state);
+        assert!(diff.is_empty());
+        assert!(diff.into_changes().is_empty());
+    }
+
+    #[test]
+    fn test_diff_states_reports_session_swap_as_changed_not_added_and_removed() {
+        let engine = CausalEngine::new();
+
+        let mut before = ApplicationState::default();
+        before.current_session = Some(SessionState {
+            session_id: SessionId("sess-1".to_string()),
+            user_id: UserId("alice".to_string()),
+            roles: Default::default(),
+            authenticated: true,
+            created_at: Utc::now(),
+            last_activity: Utc::now(),
+        });
+
+        let mut after = before.clone();
+        after.current_session.as_mut().unwrap().session_id = SessionId("sess-2".to_string());
+
+        let changes = engine.diff_states(&before, &after).into_changes();
+        let session_changes: Vec<_> =
+            changes.iter().filter(|c| c.change_type == StateChangeType::SessionChange).collect();
+
+        assert_eq!(session_changes.len(), 1);
+        assert_eq!(session_changes[0].old_value, Some(serde_json::json!("sess-1")));
+        assert_eq!(session_changes[0].new_value, Some(serde_json::json!("sess-2")));
+    }
+
+    #[test]
+    fn test_engine_telemetry_toggle_does_not_affect_build_chain_result() {
+        let engine = CausalEngine::new();
+        let transition = http_transition(
+            ApplicationState::default(),
+            {
+                let mut after = ApplicationState::default();
+                after.balances.insert(AccountId("acc1".to_string()), Balance::new(50, Currency::USD));
+                after
+            },
+            "/pay",
+            Utc::now(),
+        );
+
+        engine.set_tracing_enabled(false);
+        let chain = engine.build_chain(&transition);
+        engine.set_tracing_enabled(true);
+
+        assert!(chain.is_complete());
+        assert_eq!(chain.len(), 1);
+    }
 }
\ No newline at end of file
@@ -1,10 +1,15 @@
 //! Evidence Collector - Captures immutable proof artifacts
 
+use super::provenance::{Activity, Agent, ProvenanceGraph};
+use super::sink::EvidenceSink;
+use crate::telemetry::Telemetry;
 use crate::types::*;
 use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Types of evidence
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -29,6 +34,9 @@ pub struct EvidenceArtifact {
     pub content_hash: String,
     pub metadata: HashMap<String, String>,
     pub captured_at: DateTime<Utc>,
+    /// The previous artifact's `content_hash` in its collection, fixing
+    /// insertion order cryptographically. Empty for the first artifact.
+    pub prev_hash: String,
 }
 
 impl EvidenceArtifact {
@@ -41,6 +49,7 @@ impl EvidenceArtifact {
             content_hash,
             metadata: HashMap::new(),
             captured_at: Utc::now(),
+            prev_hash: String::new(),
         }
     }
 
@@ -73,6 +82,13 @@ pub struct Evidence {
     pub collection_id: String,
     pub collected_at: DateTime<Utc>,
     pub is_complete: bool,
+    /// Hex-encoded Merkle root over all artifacts, set by `seal()`. `None`
+    /// until the collection has been sealed.
+    pub merkle_root: Option<String>,
+    /// Detached ed25519 signature over `signing_digest()`, set by `sign()`.
+    pub signature: Option<Vec<u8>>,
+    /// The ed25519 public key the signature can be verified against.
+    pub public_key: Option<Vec<u8>>,
 }
 
 impl Evidence {
@@ -82,14 +98,159 @@ impl Evidence {
             collection_id: uuid::Uuid::new_v4().to_string(),
             collected_at: Utc::now(),
             is_complete: false,
+            merkle_root: None,
+            signature: None,
+            public_key: None,
         }
     }
 
-    /// Add an artifact to the collection
-    pub fn add_artifact(&mut self, artifact: EvidenceArtifact) {
+    /// Add an artifact to the collection, chaining it to the previous
+    /// artifact's `content_hash` so insertion order is cryptographically
+    /// fixed.
+    pub fn add_artifact(&mut self, mut artifact: EvidenceArtifact) {
+        artifact.prev_hash = self.artifacts.last().map(|a| a.content_hash.clone()).unwrap_or_default();
         self.artifacts.push(artifact);
     }
 
+    /// Whether every artifact's `prev_hash` correctly chains to the
+    /// artifact before it.
+    pub fn verify_chain(&self) -> bool {
+        self.artifacts.windows(2).all(|pair| pair[1].prev_hash == pair[0].content_hash)
+            && self.artifacts.first().map(|a| a.prev_hash.is_empty()).unwrap_or(true)
+    }
+
+    fn leaf_hash(artifact: &EvidenceArtifact) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(artifact.content_hash.as_bytes());
+        hasher.update(artifact.id.as_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    fn parent_hash(left: &[u8], right: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().to_vec()
+    }
+
+    /// Every level of the Merkle tree, from leaves (index 0) up to the
+    /// single root. A level with an odd count duplicates its last node
+    /// before pairing.
+    fn merkle_levels(&self) -> Vec<Vec<Vec<u8>>> {
+        if self.artifacts.is_empty() {
+            return Vec::new();
+        }
+
+        let mut levels = vec![self.artifacts.iter().map(Self::leaf_hash).collect::<Vec<_>>()];
+
+        while levels.last().unwrap().len() > 1 {
+            let current = levels.last().unwrap();
+            let mut next = Vec::with_capacity((current.len() + 1) / 2);
+            let mut i = 0;
+            while i < current.len() {
+                let left = &current[i];
+                let right = if i + 1 < current.len() { &current[i + 1] } else { left };
+                next.push(Self::parent_hash(left, right));
+                i += 2;
+            }
+            levels.push(next);
+        }
+
+        levels
+    }
+
+    /// Compute and store the Merkle root over all artifacts, sealing the
+    /// collection as tamper-evident, ordered proof.
+    pub fn seal(&mut self) {
+        let levels = self.merkle_levels();
+        self.merkle_root = levels.last().and_then(|root_level| root_level.first()).map(hex::encode);
+    }
+
+    /// Audit path proving `artifact_id` belongs to this collection: one
+    /// `(sibling_hash_hex, sibling_is_on_the_right)` pair per tree level,
+    /// from leaf to root.
+    pub fn inclusion_proof(&self, artifact_id: &str) -> Option<Vec<(String, bool)>> {
+        let mut index = self.artifacts.iter().position(|a| a.id == artifact_id)?;
+        let levels = self.merkle_levels();
+        let mut proof = Vec::new();
+
+        for level in &levels[..levels.len().saturating_sub(1)] {
+            let sibling_is_right = index % 2 == 0;
+            let sibling_index = if sibling_is_right { index + 1 } else { index - 1 };
+            let sibling_index = sibling_index.min(level.len() - 1);
+            proof.push((hex::encode(&level[sibling_index]), sibling_is_right));
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+
+    /// The leaf hash for `artifact`, hex-encoded, as used by the Merkle
+    /// tree and expected as the `leaf` argument to `verify_inclusion`.
+    pub fn leaf_hash_hex(artifact: &EvidenceArtifact) -> String {
+        hex::encode(Self::leaf_hash(artifact))
+    }
+
+    /// The digest an ed25519 signature over this collection is taken over:
+    /// the Merkle root if sealed (else a canonical hash of every
+    /// `content_hash` in order), plus `collection_id` and `collected_at`.
+    fn signing_digest(&self) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+
+        match &self.merkle_root {
+            Some(root) => hasher.update(root.as_bytes()),
+            None => {
+                for artifact in &self.artifacts {
+                    hasher.update(artifact.content_hash.as_bytes());
+                }
+            }
+        }
+
+        hasher.update(self.collection_id.as_bytes());
+        hasher.update(self.collected_at.to_rfc3339().as_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    /// Sign this collection for non-repudiation, attributing it to
+    /// `signing_key` and letting a third party confirm it hasn't been
+    /// altered since sealing via `verify_signature`.
+    pub fn sign(&mut self, signing_key: &SigningKey) {
+        let digest = self.signing_digest();
+        let signature = signing_key.sign(&digest);
+        self.signature = Some(signature.to_bytes().to_vec());
+        self.public_key = Some(signing_key.verifying_key().to_bytes().to_vec());
+    }
+
+    /// Verify the stored signature against this collection's signing
+    /// digest and an independently supplied `expected_public_key` -
+    /// e.g. one pinned out-of-band to the signer, the way
+    /// [`super::signing::verify`] takes its expected key. Returns `false`
+    /// if the collection was never signed, or if the embedded `public_key`
+    /// doesn't match `expected_public_key`: checking the signature against
+    /// a key taken from the same (possibly tampered) collection would only
+    /// prove internal self-consistency, not who really signed it.
+    pub fn verify_signature(&self, expected_public_key: &VerifyingKey) -> bool {
+        let signature_bytes = match &self.signature {
+            Some(bytes) => bytes,
+            None => return false,
+        };
+        let public_key_bytes = match &self.public_key {
+            Some(bytes) => bytes,
+            None => return false,
+        };
+
+        if public_key_bytes.as_slice() != expected_public_key.as_bytes() {
+            return false;
+        }
+
+        let signature = match Signature::from_slice(signature_bytes) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+
+        expected_public_key.verify(&self.signing_digest(), &signature).is_ok()
+    }
+
     /// Get artifacts by type
     pub fn get_by_type(&self, evidence_type: EvidenceType) -> Vec<&EvidenceArtifact> {
         self.artifacts.iter()
@@ -126,10 +287,52 @@ impl Default for Evidence {
     }
 }
 
+/// Verify that a leaf (hex-encoded, as returned by `Evidence::leaf_hash_hex`)
+/// belongs to a Merkle tree whose root is `root` (hex-encoded), given its
+/// `inclusion_proof`. Lets a single artifact be proven to belong to a
+/// sealed collection without shipping the whole bundle.
+pub fn verify_inclusion(root: &str, leaf: &str, proof: &[(String, bool)]) -> bool {
+    let mut current = match hex::decode(leaf) {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+
+    for (sibling_hex, sibling_is_right) in proof {
+        let sibling = match hex::decode(sibling_hex) {
+            Ok(bytes) => bytes,
+            Err(_) => return false,
+        };
+        current = if *sibling_is_right {
+            Evidence::parent_hash(&current, &sibling)
+        } else {
+            Evidence::parent_hash(&sibling, &current)
+        };
+    }
+
+    hex::encode(current) == root
+}
+
 /// Collector for gathering evidence
 pub struct EvidenceCollector {
     current_collection: Evidence,
     required_types: Vec<EvidenceType>,
+    /// When set, every finalized collection is signed with this key.
+    signing_key: Option<SigningKey>,
+    /// The scan step currently generating artifacts, for provenance.
+    activity: Option<Activity>,
+    /// The tool or operator driving `activity`, for provenance.
+    agent: Option<Agent>,
+    /// PROV graph linking generated artifacts to the activity/agent that
+    /// produced them.
+    provenance: ProvenanceGraph,
+    /// Destinations each artifact is streamed to as it is captured.
+    sinks: Vec<Arc<dyn EvidenceSink>>,
+    /// A `content_hash` to resume emitting after, e.g. a sink's cursor
+    /// from a crashed run. Artifacts are still buffered as usual but not
+    /// re-emitted to sinks until this one has been seen.
+    resume_after: Option<String>,
+    resumed: bool,
+    telemetry: Telemetry,
 }
 
 impl EvidenceCollector {
@@ -140,6 +343,14 @@ impl EvidenceCollector {
                 EvidenceType::HttpRequest,
                 EvidenceType::HttpResponse,
             ],
+            signing_key: None,
+            activity: None,
+            agent: None,
+            provenance: ProvenanceGraph::new(),
+            sinks: Vec::new(),
+            resume_after: None,
+            resumed: false,
+            telemetry: Telemetry::init(),
         }
     }
 
@@ -147,82 +358,211 @@ impl EvidenceCollector {
         Self {
             current_collection: Evidence::new(),
             required_types: required,
+            signing_key: None,
+            activity: None,
+            agent: None,
+            provenance: ProvenanceGraph::new(),
+            sinks: Vec::new(),
+            resume_after: None,
+            resumed: false,
+            telemetry: Telemetry::init(),
         }
     }
 
+    /// Attribute every collection this collector finalizes to `signing_key`,
+    /// for non-repudiation.
+    pub fn with_signing_key(mut self, signing_key: SigningKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
+    /// Attribute every artifact this collector captures to `agent` (the
+    /// tool or operator driving the current activity) until changed.
+    pub fn with_agent(mut self, agent: Agent) -> Self {
+        self.agent = Some(agent);
+        self
+    }
+
+    /// Set the scan step generating artifacts from the start, for
+    /// provenance. Equivalent to calling `set_activity` immediately.
+    pub fn with_activity(mut self, activity: Activity) -> Self {
+        self.activity = Some(activity);
+        self
+    }
+
+    /// Stream every captured artifact out to `sink` as it arrives, in
+    /// addition to buffering it for `finalize()`.
+    pub fn with_sink(mut self, sink: Arc<dyn EvidenceSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Resume a crashed run: skip re-emitting artifacts to sinks until
+    /// one whose `content_hash` matches `cursor` (e.g. a sink's own
+    /// `cursor()`) has been seen again, then resume from the next one.
+    /// Artifacts are still added to the collection as usual.
+    pub fn resume_after(mut self, cursor: impl Into<String>) -> Self {
+        self.resume_after = Some(cursor.into());
+        self
+    }
+
     /// Start a new collection
     pub fn start_collection(&mut self) {
         self.current_collection = Evidence::new();
     }
 
+    /// Set the scan step now generating artifacts, for provenance. Every
+    /// artifact added after this call is recorded as `wasGeneratedBy`
+    /// `activity`, itself `wasAssociatedWith` the current agent.
+    pub fn set_activity(&mut self, activity: Activity) {
+        self.activity = Some(activity);
+    }
+
+    /// Set the tool or operator driving the current activity, for
+    /// provenance.
+    pub fn set_agent(&mut self, agent: Agent) {
+        self.agent = Some(agent);
+    }
+
+    /// Record that the current activity used `artifact_id` as an input
+    /// (e.g. replaying a previously captured request), in addition to
+    /// whatever it goes on to generate.
+    pub fn mark_used(&mut self, artifact_id: &str) {
+        if let Some(activity) = &self.activity {
+            self.provenance.record_use(&activity.id, artifact_id);
+        }
+    }
+
+    /// Record that `derived_artifact_id`'s entity was derived from
+    /// `source_artifact_id`'s (e.g. a DOM snapshot derived from the HTTP
+    /// response that produced it).
+    pub fn mark_derived(&mut self, derived_artifact_id: &str, source_artifact_id: &str) {
+        self.provenance.record_derivation(derived_artifact_id, source_artifact_id);
+    }
+
+    /// This collector's provenance graph as a PROV-JSON document.
+    pub fn to_prov_json(&self) -> String {
+        self.provenance.to_prov_json()
+    }
+
+    /// Add `artifact` to the current collection, recording its generation
+    /// under the collector's current activity/agent context and streaming
+    /// it out to every configured sink (unless still skipping ahead to a
+    /// resume cursor).
+    fn add_artifact(&mut self, artifact: EvidenceArtifact) -> String {
+        let artifact_id = artifact.id.clone();
+        self.provenance.record_generation(&artifact_id, self.activity.as_ref(), self.agent.as_ref());
+
+        let should_emit = if self.resumed {
+            true
+        } else {
+            match &self.resume_after {
+                // This is the already-acknowledged cursor artifact itself:
+                // mark resumed but don't re-emit it.
+                Some(cursor) if *cursor == artifact.content_hash => {
+                    self.resumed = true;
+                    false
+                }
+                // Still skipping ahead to the cursor.
+                Some(_) => false,
+                // No cursor to wait for: emit from the start.
+                None => {
+                    self.resumed = true;
+                    true
+                }
+            }
+        };
+
+        if should_emit {
+            for sink in &self.sinks {
+                let _ = sink.emit(&artifact);
+            }
+        }
+
+        self.telemetry.record_artifact_captured(&format!("{:?}", artifact.evidence_type));
+        self.current_collection.add_artifact(artifact);
+        artifact_id
+    }
+
     /// Add HTTP request evidence
-    pub fn add_http_request(&mut self, request: &HttpRequest) {
+    pub fn add_http_request(&mut self, request: &HttpRequest) -> String {
         let content = serde_json::to_vec(request).unwrap_or_default();
         let mut artifact = EvidenceArtifact::new(EvidenceType::HttpRequest, content);
         artifact.metadata.insert("method".to_string(), format!("{:?}", request.method));
         artifact.metadata.insert("url".to_string(), request.url.clone());
-        self.current_collection.add_artifact(artifact);
+        self.add_artifact(artifact)
     }
 
     /// Add HTTP response evidence
-    pub fn add_http_response(&mut self, response: &HttpResponse) {
+    pub fn add_http_response(&mut self, response: &HttpResponse) -> String {
         let content = serde_json::to_vec(response).unwrap_or_default();
         let mut artifact = EvidenceArtifact::new(EvidenceType::HttpResponse, content);
         artifact.metadata.insert("status_code".to_string(), response.status_code.to_string());
         artifact.metadata.insert("duration_ms".to_string(), response.duration_ms.to_string());
-        self.current_collection.add_artifact(artifact);
+        self.telemetry.record_http_duration_ms(response.duration_ms);
+        self.add_artifact(artifact)
     }
 
     /// Add screenshot evidence
-    pub fn add_screenshot(&mut self, image_data: Vec<u8>, description: &str) {
+    pub fn add_screenshot(&mut self, image_data: Vec<u8>, description: &str) -> String {
         let artifact = EvidenceArtifact::new(EvidenceType::Screenshot, image_data)
             .with_metadata("description", description);
-        self.current_collection.add_artifact(artifact);
+        self.add_artifact(artifact)
     }
 
     /// Add DOM snapshot evidence
-    pub fn add_dom_snapshot(&mut self, html: &str) {
+    pub fn add_dom_snapshot(&mut self, html: &str) -> String {
         let artifact = EvidenceArtifact::new(
-            EvidenceType::DomSnapshot, 
+            EvidenceType::DomSnapshot,
             html.as_bytes().to_vec()
         );
-        self.current_collection.add_artifact(artifact);
+        self.add_artifact(artifact)
     }
 
     /// Add state snapshot evidence
-    pub fn add_state_snapshot(&mut self, state: &crate::state::ApplicationState) {
+    pub fn add_state_snapshot(&mut self, state: &crate::state::ApplicationState) -> String {
         let content = serde_json::to_vec(state).unwrap_or_default();
-        self.current_collection.add_artifact(
-            EvidenceArtifact::new(EvidenceType::StateSnapshot, content)
-        );
+        self.add_artifact(EvidenceArtifact::new(EvidenceType::StateSnapshot, content))
     }
 
     /// Add exploit output evidence
-    pub fn add_exploit_output(&mut self, output: &str, exploit_name: &str) {
+    pub fn add_exploit_output(&mut self, output: &str, exploit_name: &str) -> String {
         let artifact = EvidenceArtifact::new(
             EvidenceType::ExploitOutput,
             output.as_bytes().to_vec()
         ).with_metadata("exploit_name", exploit_name);
-        self.current_collection.add_artifact(artifact);
+        self.add_artifact(artifact)
     }
 
     /// Add custom evidence
-    pub fn add_custom(&mut self, name: &str, content: Vec<u8>) {
+    pub fn add_custom(&mut self, name: &str, content: Vec<u8>) -> String {
         let artifact = EvidenceArtifact::new(
             EvidenceType::Custom(name.to_string()),
             content
         );
-        self.current_collection.add_artifact(artifact);
+        self.add_artifact(artifact)
     }
 
-    /// Finalize and return the collection
+    /// Finalize and return the collection, sealing it with a Merkle root
+    /// over its artifacts so a dropped or reordered artifact can be
+    /// detected.
     pub fn finalize(&mut self) -> Evidence {
+        let artifact_count = self.current_collection.artifacts.len().to_string();
+        let _span = self.telemetry.start_span(
+            "evidence_collector.finalize",
+            &[("artifact_count", artifact_count)],
+        );
+
         let mut collection = std::mem::replace(&mut self.current_collection, Evidence::new());
-        
+
         if collection.has_required_types(&self.required_types) {
             collection.complete();
         }
-        
+        collection.seal();
+        if let Some(signing_key) = &self.signing_key {
+            collection.sign(signing_key);
+        }
+
         collection
     }
 
@@ -310,7 +650,222 @@ mod tests {
             EvidenceType::HttpRequest,
             b"test".to_vec()
         );
-        
+
         assert!(artifact.verify_integrity());
     }
+
+    #[test]
+    fn test_seal_produces_a_merkle_root() {
+        let mut evidence = Evidence::new();
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpRequest, b"request".to_vec()));
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpResponse, b"response".to_vec()));
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::Screenshot, b"png".to_vec()));
+
+        assert!(evidence.merkle_root.is_none());
+        evidence.seal();
+        assert!(evidence.merkle_root.is_some());
+    }
+
+    #[test]
+    fn test_inclusion_proof_verifies_against_the_sealed_root() {
+        let mut evidence = Evidence::new();
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpRequest, b"request".to_vec()));
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpResponse, b"response".to_vec()));
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::Screenshot, b"png".to_vec()));
+        evidence.seal();
+
+        let target = &evidence.artifacts[1];
+        let leaf = Evidence::leaf_hash_hex(target);
+        let proof = evidence.inclusion_proof(&target.id).unwrap();
+
+        assert!(verify_inclusion(evidence.merkle_root.as_ref().unwrap(), &leaf, &proof));
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_a_tampered_leaf() {
+        let mut evidence = Evidence::new();
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpRequest, b"request".to_vec()));
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpResponse, b"response".to_vec()));
+        evidence.seal();
+
+        let target = &evidence.artifacts[0];
+        let proof = evidence.inclusion_proof(&target.id).unwrap();
+        let forged_leaf = Evidence::leaf_hash_hex(&EvidenceArtifact::new(EvidenceType::HttpRequest, b"forged".to_vec()));
+
+        assert!(!verify_inclusion(evidence.merkle_root.as_ref().unwrap(), &forged_leaf, &proof));
+    }
+
+    #[test]
+    fn test_signed_collection_verifies() {
+        use rand::rngs::OsRng;
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let verifying_key = signing_key.verifying_key();
+        let mut collector = EvidenceCollector::new().with_signing_key(signing_key);
+
+        let request = HttpRequest {
+            method: HttpMethod::GET,
+            url: "https://example.com".to_string(),
+            headers: HashMap::new(),
+            body: None,
+            timestamp: Utc::now(),
+        };
+        let response = HttpResponse {
+            status_code: 200,
+            headers: HashMap::new(),
+            body: b"OK".to_vec(),
+            timestamp: Utc::now(),
+            duration_ms: 100,
+        };
+        collector.add_http_request(&request);
+        collector.add_http_response(&response);
+
+        let evidence = collector.finalize();
+        assert!(evidence.signature.is_some());
+        assert!(evidence.public_key.is_some());
+        assert!(evidence.verify_signature(&verifying_key));
+    }
+
+    #[test]
+    fn test_unsigned_collection_fails_verification() {
+        use rand::rngs::OsRng;
+
+        let verifying_key = SigningKey::generate(&mut OsRng).verifying_key();
+        let evidence = Evidence::new();
+        assert!(!evidence.verify_signature(&verifying_key));
+    }
+
+    #[test]
+    fn test_tampering_with_the_merkle_root_invalidates_the_signature() {
+        use rand::rngs::OsRng;
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let verifying_key = signing_key.verifying_key();
+        let mut evidence = Evidence::new();
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpRequest, b"request".to_vec()));
+        evidence.seal();
+        evidence.sign(&signing_key);
+        assert!(evidence.verify_signature(&verifying_key));
+
+        evidence.merkle_root = Some("0".repeat(64));
+        assert!(!evidence.verify_signature(&verifying_key));
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_a_mismatched_expected_key() {
+        use rand::rngs::OsRng;
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let attacker_key = SigningKey::generate(&mut OsRng);
+        let mut evidence = Evidence::new();
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpRequest, b"request".to_vec()));
+        evidence.seal();
+        evidence.sign(&signing_key);
+
+        assert!(!evidence.verify_signature(&attacker_key.verifying_key()));
+    }
+
+    #[test]
+    fn test_chain_binds_artifacts_in_insertion_order() {
+        let mut evidence = Evidence::new();
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpRequest, b"first".to_vec()));
+        evidence.add_artifact(EvidenceArtifact::new(EvidenceType::HttpResponse, b"second".to_vec()));
+
+        assert!(evidence.artifacts[0].prev_hash.is_empty());
+        assert_eq!(evidence.artifacts[1].prev_hash, evidence.artifacts[0].content_hash);
+        assert!(evidence.verify_chain());
+    }
+
+    #[test]
+    fn test_collector_links_artifacts_to_activity_and_agent() {
+        let mut collector = EvidenceCollector::new()
+            .with_agent(Agent { id: "agent1".to_string(), name: "idor-probe".to_string() });
+        collector.set_activity(Activity { id: "act1".to_string(), name: "replay-request".to_string() });
+
+        let request = HttpRequest {
+            method: HttpMethod::GET,
+            url: "https://example.com".to_string(),
+            headers: HashMap::new(),
+            body: None,
+            timestamp: Utc::now(),
+        };
+        let response = HttpResponse {
+            status_code: 200,
+            headers: HashMap::new(),
+            body: b"OK".to_vec(),
+            timestamp: Utc::now(),
+            duration_ms: 100,
+        };
+
+        let request_id = collector.add_http_request(&request);
+        let response_id = collector.add_http_response(&response);
+        let dom_id = collector.add_dom_snapshot("<html></html>");
+        collector.mark_derived(&dom_id, &response_id);
+
+        let prov: serde_json::Value = serde_json::from_str(&collector.to_prov_json()).unwrap();
+        assert!(prov["entity"][&request_id].is_object());
+        assert!(prov["entity"][&response_id].is_object());
+        assert!(prov["activity"]["act1"].is_object());
+        assert!(prov["agent"]["agent1"].is_object());
+        assert_eq!(prov["wasGeneratedBy"].as_object().unwrap().len(), 3);
+        assert_eq!(prov["wasDerivedFrom"].as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_collector_streams_each_artifact_to_every_sink() {
+        use super::super::sink::MemorySink;
+
+        let sink = Arc::new(MemorySink::new());
+        let mut collector = EvidenceCollector::new().with_sink(sink.clone());
+
+        collector.add_custom("first", b"alpha".to_vec());
+        collector.add_custom("second", b"beta".to_vec());
+
+        assert_eq!(sink.artifacts().len(), 2);
+    }
+
+    #[test]
+    fn test_collector_resume_after_skips_up_to_and_including_the_cursor() {
+        use super::super::sink::MemorySink;
+
+        let sink = Arc::new(MemorySink::new());
+        let mut collector = EvidenceCollector::new().with_sink(sink.clone());
+        collector.add_custom("first", b"alpha".to_vec());
+        collector.add_custom("second", b"beta".to_vec());
+        let cursor = sink.cursor().unwrap();
+
+        // Simulate a crash and restart: a fresh collector replays the same
+        // artifacts against a fresh sink, resuming after what was already
+        // acknowledged.
+        let resumed_sink = Arc::new(MemorySink::new());
+        let mut resumed_collector = EvidenceCollector::new()
+            .with_sink(resumed_sink.clone())
+            .resume_after(cursor);
+        resumed_collector.add_custom("first", b"alpha".to_vec());
+        resumed_collector.add_custom("second", b"beta".to_vec());
+        resumed_collector.add_custom("third", b"gamma".to_vec());
+
+        let recorded = resumed_sink.artifacts();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].content_hash, EvidenceArtifact::new(EvidenceType::Custom("third".to_string()), b"gamma".to_vec()).content_hash);
+    }
+
+    #[test]
+    fn test_collector_telemetry_does_not_affect_capture_or_finalize() {
+        let mut collector = EvidenceCollector::new();
+
+        let response = HttpResponse {
+            status_code: 200,
+            headers: HashMap::new(),
+            body: b"OK".to_vec(),
+            timestamp: Utc::now(),
+            duration_ms: 42,
+        };
+
+        collector.add_http_response(&response);
+        collector.add_custom("note", b"payload".to_vec());
+
+        let evidence = collector.finalize();
+        assert_eq!(evidence.artifacts.len(), 2);
+    }
 }
\ No newline at end of file
@@ -2,19 +2,38 @@
 //! 
 //! This module implements causal attribution, replay, and contradiction proving.
 
+mod assertion;
 mod causal;
 mod replay;
 mod evidence;
+mod provenance;
+mod prov_export;
+mod sink;
+mod replay_sink;
+mod signing;
+mod canonical;
+#[cfg(feature = "arrow_export")]
+mod arrow_export;
 
-pub use causal::{CausalEngine, CausalChain, CausalLink, StateChange, StateChangeType};
-pub use replay::{ReplayEngine, ReplayResult, ReplayInstructions, StateRequirements};
-pub use evidence::{EvidenceCollector, Evidence, EvidenceType, EvidenceArtifact};
+pub use assertion::{AssertionOutcome, AssertionReport};
+pub use causal::{CausalEngine, CausalChain, CausalLink, DiffEntry, StateChange, StateChangeType, StateDiff};
+#[cfg(feature = "arrow_export")]
+pub use arrow_export::{ArrowChangeRow, ArrowExportError, ArrowExporter, batch_to_rows, rows_from_chain, rows_from_proof, rows_to_batch};
+pub use replay::{ActionDispatcher, ReplayEngine, ReplayReceipt, ReplayResult, ReplayInstructions, ReplayStatus, ReplayTrace, StateRequirements};
+pub use evidence::{verify_inclusion, EvidenceCollector, Evidence, EvidenceType, EvidenceArtifact};
+pub use provenance::{Agent, Activity, Entity, ProvenanceGraph};
+pub use prov_export::{ProvActivity, ProvAgent, ProvAssociation, ProvDocument, ProvEntity, ProvError, ProvExporter, ProvUsage};
+pub use sink::{EvidenceSink, FilesystemSink, MemorySink, NdjsonStreamSink, SinkError, SinkResult};
+pub use replay_sink::{MessageQueueSink, NdjsonFileSink, PayloadTransport, ReplaySink, SinkFilter, SinkPipeline, StdoutSink, WebhookSink};
+pub use signing::{address, generate_keypair, keypair_from_passphrase, recover_public_key, sign, verify, ReplayBundle, Signature};
 
 use crate::invariant::{ViolationDetails, InvariantCategory};
 use crate::state::{ApplicationState, StateTransition, Action};
 use crate::types::*;
+use canonical::canonical_bytes;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// Complete proof of an invariant violation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +69,24 @@ impl Proof {
     pub fn confidence(&self) -> f64 {
         self.violation_details.confidence
     }
+
+    /// Deterministic, versioned byte encoding of this proof. Stable
+    /// regardless of `HashMap` iteration order anywhere inside it (the
+    /// ownership/balance maps in `before_state`/`after_state`, an
+    /// `Action`'s parameters, etc.) — see `canonical::canonical_bytes`.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        canonical_bytes(self)
+    }
+
+    /// SHA-256 over `canonical_bytes`, hex-encoded: a content address two
+    /// logically identical proofs always share, and that changes the
+    /// instant any field is mutated. Pair with `StateLedger::attest_proof`
+    /// to let a ledger notice a stored proof was tampered with later.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.canonical_bytes());
+        hex::encode(hasher.finalize())
+    }
 }
 
 /// Error types for proof generation
@@ -60,4 +97,108 @@ pub enum ProofError {
     InsufficientEvidence,
     NonDeterministic,
     StateInconsistency,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn state_with_entries(ownership: &[(&str, &str)], balances: &[(&str, i64)]) -> ApplicationState {
+        let mut state = ApplicationState::default();
+        for (object_id, user_id) in ownership {
+            state.ownership.insert(ObjectId(object_id.to_string()), UserId(user_id.to_string()));
+        }
+        for (account_id, amount) in balances {
+            state.balances.insert(AccountId(account_id.to_string()), Balance::new(*amount, Currency::USD));
+        }
+        state
+    }
+
+    fn test_proof(ownership: &[(&str, &str)], balances: &[(&str, i64)]) -> Proof {
+        Proof {
+            id: "proof-1".to_string(),
+            before_state: ApplicationState::default(),
+            action_sequence: Vec::new(),
+            after_state: state_with_entries(ownership, balances),
+            causality_chain: CausalChain::new(),
+            replay_instructions: ReplayInstructions::new(),
+            evidence: Evidence::new(),
+            invariant_violated: "inv-1".to_string(),
+            violation_details: ViolationDetails {
+                invariant_id: "inv-1".to_string(),
+                invariant_name: "no negative balance".to_string(),
+                category: InvariantCategory::Monetary,
+                message: "balance went negative".to_string(),
+                severity: Severity::Critical,
+                confidence: 1.0,
+                evidence: Vec::new(),
+            },
+            generated_at: "2024-01-01T00:00:00Z".parse().unwrap(),
+            is_deterministic: true,
+        }
+    }
+
+    #[test]
+    fn test_content_hash_is_stable_across_reordered_maps() {
+        let a = test_proof(
+            &[("obj1", "alice"), ("obj2", "bob")],
+            &[("acc1", 100), ("acc2", 200)],
+        );
+        let b = test_proof(
+            &[("obj2", "bob"), ("obj1", "alice")],
+            &[("acc2", 200), ("acc1", 100)],
+        );
+
+        assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+        assert_eq!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn test_content_hash_changes_when_a_field_is_mutated() {
+        let a = test_proof(&[("obj1", "alice")], &[("acc1", 100)]);
+        let b = test_proof(&[("obj1", "bob")], &[("acc1", 100)]);
+
+        assert_ne!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn test_canonical_bytes_round_trip_through_decode() {
+        let proof = test_proof(&[("obj1", "alice")], &[("acc1", 100)]);
+        let encoded = proof.canonical_bytes();
+
+        let decoded = canonical::canonical_decode(&encoded).unwrap();
+        assert_eq!(canonical::canonical_bytes(&decoded), encoded);
+    }
+
+    #[test]
+    fn test_attested_proof_hash_survives_in_the_ledger() {
+        use crate::state::StateLedger;
+
+        let ledger = StateLedger::new();
+        let transition = StateTransition {
+            id: "t1".to_string(),
+            from_state: ApplicationState::default(),
+            to_state: state_with_entries(&[("obj1", "alice")], &[]),
+            triggering_action: Action {
+                id: "a1".to_string(),
+                action_type: crate::state::ActionType::Custom("test".to_string()),
+                request: None,
+                parameters: HashMap::new(),
+                authentication: None,
+                timing: ActionTiming { start_time: Utc::now(), end_time: Utc::now(), duration_ms: 1 },
+            },
+            timestamp: Utc::now(),
+        };
+        let entry_id = ledger.record_transition(transition);
+        let entry_hash = ledger.get_entry(&entry_id).unwrap().state_hash;
+
+        let proof = test_proof(&[("obj1", "alice")], &[]);
+        assert!(ledger.attest_proof(&entry_hash, &proof.content_hash()));
+        assert!(ledger.verify_proof_attestation(&entry_hash, &proof.content_hash()));
+        assert!(ledger.verify_integrity());
+
+        let mutated = test_proof(&[("obj1", "mallory")], &[]);
+        assert!(!ledger.verify_proof_attestation(&entry_hash, &mutated.content_hash()));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,260 @@
+//! Causal Chain Provenance Export - W3C PROV profile for `CausalChain`
+//!
+//! `ProvenanceGraph` (see `provenance.rs`) tracks evidence-artifact custody
+//! by id only, which is enough there because the artifact's content lives
+//! in the `Evidence` collection itself. A `CausalChain` has no such side
+//! channel — its `Action`s and `StateChange`s *are* the payload — so this
+//! profile embeds them directly on each PROV node. That makes
+//! `CausalChain::to_prov`/`from_prov` an exact round trip: a document
+//! produced by another tool can be ingested and re-validated against this
+//! crate's `AttributionRule`s, not just displayed.
+
+use super::causal::StateChange;
+use crate::state::Action;
+use crate::types::UserId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A PROV Activity: one causal link's triggering `Action`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvActivity {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub action: Action,
+}
+
+/// A PROV Entity: one `StateChange`, qualified with the confidence and
+/// timestamp of the link that produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvEntity {
+    pub id: String,
+    pub generated_by: String,
+    pub confidence: f64,
+    pub timestamp: DateTime<Utc>,
+    pub change: StateChange,
+}
+
+/// A PROV Agent: the authenticated user behind one or more activities.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvAgent {
+    pub id: String,
+    pub user_id: UserId,
+}
+
+/// `activity_id` used `entity_id` as an input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvUsage {
+    pub activity_id: String,
+    pub entity_id: String,
+}
+
+/// `activity_id` `wasAssociatedWith` `agent_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvAssociation {
+    pub activity_id: String,
+    pub agent_id: String,
+}
+
+/// A complete W3C PROV document derived from a `CausalChain`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProvDocument {
+    pub activities: Vec<ProvActivity>,
+    pub entities: Vec<ProvEntity>,
+    pub agents: Vec<ProvAgent>,
+    pub used: Vec<ProvUsage>,
+    pub associated_with: Vec<ProvAssociation>,
+}
+
+/// Error decoding a `ProvDocument` from an externally-produced document.
+#[derive(Debug)]
+pub enum ProvError {
+    Decode(String),
+}
+
+impl fmt::Display for ProvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProvError::Decode(message) => write!(f, "prov decode error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ProvError {}
+
+/// Serializes a `ProvDocument` as PROV-JSON or a flat RDF/Turtle graph,
+/// and parses PROV-JSON back.
+pub struct ProvExporter;
+
+impl ProvExporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Serialize `document` as PROV-JSON
+    /// (<https://www.w3.org/Submission/prov-json/>), using this crate's
+    /// field names as the PROV extension attributes.
+    pub fn to_prov_json(&self, document: &ProvDocument) -> serde_json::Value {
+        serde_json::to_value(document).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// Parse a document previously produced by `to_prov_json`, or an
+    /// externally-produced document with the same shape.
+    pub fn from_prov_json(&self, value: &serde_json::Value) -> Result<ProvDocument, ProvError> {
+        serde_json::from_value(value.clone()).map_err(|e| ProvError::Decode(e.to_string()))
+    }
+
+    /// Serialize `document` as a flat RDF/Turtle graph.
+    pub fn to_turtle(&self, document: &ProvDocument) -> String {
+        let mut out = String::new();
+        out.push_str("@prefix prov: <http://www.w3.org/ns/prov#> .\n");
+        out.push_str("@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n");
+        out.push_str("@prefix ex: <urn:kali-mcp:causal:> .\n\n");
+
+        for activity in &document.activities {
+            out.push_str(&format!(
+                "ex:{0} a prov:Activity ;\n    prov:startedAtTime \"{1}\"^^xsd:dateTime ;\n    prov:endedAtTime \"{2}\"^^xsd:dateTime .\n\n",
+                activity.id,
+                activity.started_at.to_rfc3339(),
+                activity.ended_at.to_rfc3339(),
+            ));
+        }
+
+        for entity in &document.entities {
+            out.push_str(&format!(
+                "ex:{0} a prov:Entity ;\n    prov:wasGeneratedBy ex:{1} ;\n    ex:confidence \"{2}\" ;\n    prov:generatedAtTime \"{3}\"^^xsd:dateTime .\n\n",
+                entity.id, entity.generated_by, entity.confidence, entity.timestamp.to_rfc3339(),
+            ));
+        }
+
+        for agent in &document.agents {
+            out.push_str(&format!("ex:{0} a prov:Agent .\n\n", agent.id));
+        }
+
+        for association in &document.associated_with {
+            out.push_str(&format!(
+                "ex:{0} prov:wasAssociatedWith ex:{1} .\n\n",
+                association.activity_id, association.agent_id,
+            ));
+        }
+
+        for usage in &document.used {
+            out.push_str(&format!("ex:{0} prov:used ex:{1} .\n\n", usage.activity_id, usage.entity_id));
+        }
+
+        out
+    }
+}
+
+impl Default for ProvExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::causal::{CausalChain, CausalLink, StateChangeType};
+    use crate::state::ActionType;
+    use crate::types::ActionTiming;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn test_action(with_auth: bool) -> Action {
+        Action {
+            id: Uuid::new_v4().to_string(),
+            action_type: ActionType::HttpRequest,
+            request: None,
+            parameters: HashMap::new(),
+            authentication: if with_auth {
+                Some(crate::types::AuthToken {
+                    token_type: crate::types::TokenType::Bearer,
+                    value: "token".to_string(),
+                    user_id: Some(UserId("alice".to_string())),
+                    roles: Default::default(),
+                    expires_at: None,
+                })
+            } else {
+                None
+            },
+            timing: ActionTiming {
+                start_time: Utc::now(),
+                end_time: Utc::now(),
+                duration_ms: 10,
+            },
+        }
+    }
+
+    fn test_chain() -> CausalChain {
+        let mut chain = CausalChain::new();
+        chain.add_link(CausalLink {
+            action: test_action(true),
+            state_changes: vec![StateChange {
+                change_type: StateChangeType::BalanceChange,
+                field: "balances.acc1".to_string(),
+                old_value: Some(serde_json::json!(100)),
+                new_value: Some(serde_json::json!(50)),
+            }],
+            confidence: 0.9,
+            timestamp: Utc::now(),
+        });
+        chain
+    }
+
+    #[test]
+    fn test_to_prov_emits_one_activity_entity_and_agent() {
+        let document = test_chain().to_prov();
+        assert_eq!(document.activities.len(), 1);
+        assert_eq!(document.entities.len(), 1);
+        assert_eq!(document.agents.len(), 1);
+        assert_eq!(document.associated_with.len(), 1);
+    }
+
+    #[test]
+    fn test_to_prov_links_later_activities_to_the_prior_final_effect() {
+        let mut chain = test_chain();
+        chain.add_link(CausalLink {
+            action: test_action(false),
+            state_changes: vec![StateChange {
+                change_type: StateChangeType::OwnershipChange,
+                field: "ownership.obj1".to_string(),
+                old_value: Some(serde_json::json!("alice")),
+                new_value: Some(serde_json::json!("bob")),
+            }],
+            confidence: 0.8,
+            timestamp: Utc::now(),
+        });
+
+        let document = chain.to_prov();
+        assert_eq!(document.used.len(), 1);
+        assert_eq!(document.used[0].entity_id, document.entities[0].id);
+        assert_eq!(document.used[0].activity_id, document.activities[1].id);
+    }
+
+    #[test]
+    fn test_prov_json_round_trips_through_causal_chain() {
+        let chain = test_chain();
+        let exporter = ProvExporter::new();
+
+        let document = chain.to_prov();
+        let json = exporter.to_prov_json(&document);
+        let decoded = exporter.from_prov_json(&json).unwrap();
+        let rebuilt = CausalChain::from_prov(&decoded);
+
+        assert_eq!(rebuilt.len(), chain.len());
+        assert_eq!(rebuilt.links[0].state_changes[0].field, chain.links[0].state_changes[0].field);
+        assert!((rebuilt.confidence - chain.confidence).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_to_turtle_names_every_activity_and_entity() {
+        let document = test_chain().to_prov();
+        let turtle = ProvExporter::new().to_turtle(&document);
+
+        assert!(turtle.contains(&format!("ex:{}", document.activities[0].id)));
+        assert!(turtle.contains(&format!("ex:{}", document.entities[0].id)));
+        assert!(turtle.contains("prov:wasAssociatedWith"));
+    }
+}
@@ -0,0 +1,246 @@
+//! Provenance Graph - Models evidence artifacts as a W3C PROV graph
+//!
+//! The collector captures isolated artifacts but, on its own, loses the
+//! causal story: which scan step (`Activity`) produced which artifact
+//! (`Entity`), driven by which tool or operator (`Agent`). This module
+//! tracks that graph so analysts get a verifiable chain of custody and a
+//! replayable exploit narrative instead of a flat artifact list.
+
+use serde::{Deserialize, Serialize};
+
+/// A tool or operator responsible for one or more activities.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Agent {
+    pub id: String,
+    pub name: String,
+}
+
+/// A scan step that generated or used evidence entities.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: String,
+    pub name: String,
+}
+
+/// A PROV entity wrapping an `EvidenceArtifact`'s identity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entity {
+    pub id: String,
+}
+
+/// A W3C PROV graph over an evidence collection's artifacts: `Entity`,
+/// `Activity`, and `Agent` nodes joined by `wasGeneratedBy`,
+/// `wasAssociatedWith`, `used`, and `wasDerivedFrom` edges.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProvenanceGraph {
+    pub agents: Vec<Agent>,
+    pub activities: Vec<Activity>,
+    pub entities: Vec<Entity>,
+    /// entity id -> activity id
+    pub was_generated_by: Vec<(String, String)>,
+    /// activity id -> agent id
+    pub was_associated_with: Vec<(String, String)>,
+    /// activity id -> entity id (an input the activity used)
+    pub used: Vec<(String, String)>,
+    /// entity id -> entity id it was derived from
+    pub was_derived_from: Vec<(String, String)>,
+}
+
+impl ProvenanceGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `artifact_id`'s entity was generated by `activity`,
+    /// itself associated with `agent`. Registers any new agent/activity
+    /// node encountered along the way.
+    pub(crate) fn record_generation(
+        &mut self,
+        artifact_id: &str,
+        activity: Option<&Activity>,
+        agent: Option<&Agent>,
+    ) {
+        self.entities.push(Entity { id: artifact_id.to_string() });
+
+        let activity = match activity {
+            Some(activity) => activity,
+            None => return,
+        };
+        if !self.activities.iter().any(|a| a.id == activity.id) {
+            self.activities.push(activity.clone());
+        }
+        self.was_generated_by.push((artifact_id.to_string(), activity.id.clone()));
+
+        if let Some(agent) = agent {
+            if !self.agents.iter().any(|a| a.id == agent.id) {
+                self.agents.push(agent.clone());
+            }
+            let pair = (activity.id.clone(), agent.id.clone());
+            if !self.was_associated_with.contains(&pair) {
+                self.was_associated_with.push(pair);
+            }
+        }
+    }
+
+    /// Record that `activity_id` used `entity_id` as an input.
+    pub(crate) fn record_use(&mut self, activity_id: &str, entity_id: &str) {
+        self.used.push((activity_id.to_string(), entity_id.to_string()));
+    }
+
+    /// Record that `derived_entity_id` was derived from `source_entity_id`
+    /// (e.g. a DOM snapshot derived from the HTTP response that produced it).
+    pub(crate) fn record_derivation(&mut self, derived_entity_id: &str, source_entity_id: &str) {
+        self.was_derived_from.push((derived_entity_id.to_string(), source_entity_id.to_string()));
+    }
+
+    /// Serialize this graph as a PROV-JSON document
+    /// (<https://www.w3.org/Submission/prov-json/>).
+    pub fn to_prov_json(&self) -> String {
+        let entity: serde_json::Map<String, serde_json::Value> = self
+            .entities
+            .iter()
+            .map(|e| (e.id.clone(), serde_json::json!({})))
+            .collect();
+
+        let activity: serde_json::Map<String, serde_json::Value> = self
+            .activities
+            .iter()
+            .map(|a| (a.id.clone(), serde_json::json!({ "prov:label": a.name })))
+            .collect();
+
+        let agent: serde_json::Map<String, serde_json::Value> = self
+            .agents
+            .iter()
+            .map(|a| (a.id.clone(), serde_json::json!({ "prov:label": a.name })))
+            .collect();
+
+        let was_generated_by: serde_json::Map<String, serde_json::Value> = self
+            .was_generated_by
+            .iter()
+            .enumerate()
+            .map(|(i, (entity_id, activity_id))| {
+                (
+                    format!("_gen{i}"),
+                    serde_json::json!({ "prov:entity": entity_id, "prov:activity": activity_id }),
+                )
+            })
+            .collect();
+
+        let was_associated_with: serde_json::Map<String, serde_json::Value> = self
+            .was_associated_with
+            .iter()
+            .enumerate()
+            .map(|(i, (activity_id, agent_id))| {
+                (
+                    format!("_assoc{i}"),
+                    serde_json::json!({ "prov:activity": activity_id, "prov:agent": agent_id }),
+                )
+            })
+            .collect();
+
+        let used: serde_json::Map<String, serde_json::Value> = self
+            .used
+            .iter()
+            .enumerate()
+            .map(|(i, (activity_id, entity_id))| {
+                (
+                    format!("_used{i}"),
+                    serde_json::json!({ "prov:activity": activity_id, "prov:entity": entity_id }),
+                )
+            })
+            .collect();
+
+        let was_derived_from: serde_json::Map<String, serde_json::Value> = self
+            .was_derived_from
+            .iter()
+            .enumerate()
+            .map(|(i, (generated_entity, used_entity))| {
+                (
+                    format!("_der{i}"),
+                    serde_json::json!({
+                        "prov:generatedEntity": generated_entity,
+                        "prov:usedEntity": used_entity,
+                    }),
+                )
+            })
+            .collect();
+
+        let document = serde_json::json!({
+            "entity": entity,
+            "activity": activity,
+            "agent": agent,
+            "wasGeneratedBy": was_generated_by,
+            "wasAssociatedWith": was_associated_with,
+            "used": used,
+            "wasDerivedFrom": was_derived_from,
+        });
+
+        serde_json::to_string_pretty(&document).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generation_links_entity_activity_and_agent() {
+        let mut graph = ProvenanceGraph::new();
+        let activity = Activity { id: "act1".to_string(), name: "port-scan".to_string() };
+        let agent = Agent { id: "agent1".to_string(), name: "nmap".to_string() };
+
+        graph.record_generation("entity1", Some(&activity), Some(&agent));
+
+        assert_eq!(graph.entities.len(), 1);
+        assert_eq!(graph.activities.len(), 1);
+        assert_eq!(graph.agents.len(), 1);
+        assert_eq!(graph.was_generated_by, vec![("entity1".to_string(), "act1".to_string())]);
+        assert_eq!(graph.was_associated_with, vec![("act1".to_string(), "agent1".to_string())]);
+    }
+
+    #[test]
+    fn test_generation_without_context_only_registers_the_entity() {
+        let mut graph = ProvenanceGraph::new();
+        graph.record_generation("entity1", None, None);
+
+        assert_eq!(graph.entities.len(), 1);
+        assert!(graph.activities.is_empty());
+        assert!(graph.was_generated_by.is_empty());
+    }
+
+    #[test]
+    fn test_repeated_activity_and_agent_are_not_duplicated() {
+        let mut graph = ProvenanceGraph::new();
+        let activity = Activity { id: "act1".to_string(), name: "port-scan".to_string() };
+        let agent = Agent { id: "agent1".to_string(), name: "nmap".to_string() };
+
+        graph.record_generation("entity1", Some(&activity), Some(&agent));
+        graph.record_generation("entity2", Some(&activity), Some(&agent));
+
+        assert_eq!(graph.activities.len(), 1);
+        assert_eq!(graph.agents.len(), 1);
+        assert_eq!(graph.was_associated_with.len(), 1);
+        assert_eq!(graph.was_generated_by.len(), 2);
+    }
+
+    #[test]
+    fn test_to_prov_json_emits_all_node_and_edge_kinds() {
+        let mut graph = ProvenanceGraph::new();
+        let activity = Activity { id: "act1".to_string(), name: "port-scan".to_string() };
+        let agent = Agent { id: "agent1".to_string(), name: "nmap".to_string() };
+        graph.record_generation("entity1", Some(&activity), Some(&agent));
+        graph.record_generation("entity2", Some(&activity), Some(&agent));
+        graph.record_use("act1", "entity1");
+        graph.record_derivation("entity2", "entity1");
+
+        let json: serde_json::Value = serde_json::from_str(&graph.to_prov_json()).unwrap();
+
+        assert!(json["entity"]["entity1"].is_object());
+        assert!(json["activity"]["act1"].is_object());
+        assert!(json["agent"]["agent1"].is_object());
+        assert_eq!(json["wasGeneratedBy"].as_object().unwrap().len(), 2);
+        assert_eq!(json["wasAssociatedWith"].as_object().unwrap().len(), 1);
+        assert_eq!(json["used"].as_object().unwrap().len(), 1);
+        assert_eq!(json["wasDerivedFrom"].as_object().unwrap().len(), 1);
+    }
+}
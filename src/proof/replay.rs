@@ -1,10 +1,15 @@
 //! Replay Engine - Deterministically reproduces findings
 
+use super::assertion::{self, AssertionReport};
+use super::causal::{CausalEngine, StateChange, StateDiff};
 use crate::state::{Action, ApplicationState, StateTransition};
+use crate::telemetry::Telemetry;
 use crate::types::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 
 /// Instructions for replaying a finding
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,7 +89,7 @@ pub enum AssertionOperator {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StateRequirements {
     pub required_session: Option<SessionRequirement>,
-    pub required_balances: HashMap<String, i64>,
+    pub required_balances: HashMap<String, MonetaryValue>,
     pub required_ownership: HashMap<String, String>,
     pub required_workflow_position: Option<u32>,
 }
@@ -119,6 +124,59 @@ pub struct TimingConstraints {
     pub max_step_interval_ms: u64,
 }
 
+/// One node in a replay's execution trace, borrowing the per-transaction
+/// `Trace` model from Ethereum state application: the action that ran,
+/// the canonical [`StateDiff`] it produced (see
+/// `CausalEngine::diff_states`), whether it succeeded, and an ordered
+/// list of sub-action children - retries, redirects, or auth handshakes
+/// spawned while executing this node's action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayTrace {
+    pub action: Action,
+    pub diff: StateDiff,
+    pub success: bool,
+    pub children: Vec<ReplayTrace>,
+    /// The step's `expected_state_after` assertions, evaluated against the
+    /// state captured once this node's action ran. `None` when the step
+    /// carried no assertion.
+    #[serde(default)]
+    pub assertions: Option<AssertionReport>,
+}
+
+impl ReplayTrace {
+    pub fn new(action: Action, diff: StateDiff, success: bool) -> Self {
+        Self { action, diff, success, children: Vec::new(), assertions: None }
+    }
+
+    /// Attach the assertion report evaluated for this node's action.
+    pub fn with_assertions(mut self, report: AssertionReport) -> Self {
+        self.assertions = Some(report);
+        self
+    }
+
+    /// Attach a sub-action node spawned while executing this node's action.
+    pub fn push_child(&mut self, child: ReplayTrace) {
+        self.children.push(child);
+    }
+
+    /// This node's own result folded with every descendant's: a node is
+    /// failed if it failed outright or any child failed, transitively.
+    pub fn failed(&self) -> bool {
+        !self.success || self.children.iter().any(ReplayTrace::failed)
+    }
+
+    /// Pre-order walk of this node and every descendant, flattened so a
+    /// proof consumer can see exactly which nested action caused which
+    /// state change without re-implementing the tree walk.
+    pub fn flatten(&self) -> Vec<&ReplayTrace> {
+        let mut out = vec![self];
+        for child in &self.children {
+            out.extend(child.flatten());
+        }
+        out
+    }
+}
+
 /// Result of replay attempt
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReplayResult {
@@ -130,6 +188,11 @@ pub struct ReplayResult {
     pub error: Option<String>,
     pub duration_ms: u64,
     pub is_deterministic: bool,
+    /// Root trace node per replayed step, in step order. Empty when the
+    /// result wasn't built from an actual traced replay (e.g. the bare
+    /// `success`/`failure` constructors).
+    #[serde(default)]
+    pub trace: Vec<ReplayTrace>,
 }
 
 impl ReplayResult {
@@ -143,6 +206,7 @@ impl ReplayResult {
             error: None,
             duration_ms,
             is_deterministic: true,
+            trace: Vec::new(),
         }
     }
 
@@ -156,14 +220,97 @@ impl ReplayResult {
             error: Some(error),
             duration_ms: 0,
             is_deterministic: false,
+            trace: Vec::new(),
         }
     }
+
+    /// The root trace node for each replayed step, in step order. See
+    /// [`ReplayTrace::flatten`] for a flattened, drill-down view.
+    pub fn trace(&self) -> &[ReplayTrace] {
+        &self.trace
+    }
+
+    /// Every trace node across every step's tree, pre-order, flattened.
+    pub fn flatten_trace(&self) -> Vec<&ReplayTrace> {
+        self.trace.iter().flat_map(ReplayTrace::flatten).collect()
+    }
+}
+
+/// Coarse outcome recorded on a [`ReplayReceipt`], independent from a
+/// `ReplayResult`'s step-level `success` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayStatus {
+    Completed,
+    PartiallyCompleted,
+    Failed,
+}
+
+/// Receipt for a single replay run, following the `ApplyOutcome`/`Receipt`
+/// pattern Ethereum clients use to make state application provable rather
+/// than heuristic: the cumulative [`StateDiff`] from the run's initial
+/// state to its final state, the ordered log of every [`StateChange`]
+/// each step actually emitted (concatenated from that step's
+/// [`ReplayTrace`], in step order), and the run's coarse
+/// [`ReplayStatus`]. `content_hash` commits to all of it, so
+/// [`ReplayEngine::is_deterministic`] can compare full receipts instead of
+/// `ReplayResult`'s bare success/step-count summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayReceipt {
+    pub status: ReplayStatus,
+    pub cumulative_diff: StateDiff,
+    pub change_log: Vec<StateChange>,
+    pub final_state: Option<ApplicationState>,
+}
+
+impl ReplayReceipt {
+    pub fn new(
+        status: ReplayStatus,
+        cumulative_diff: StateDiff,
+        change_log: Vec<StateChange>,
+        final_state: Option<ApplicationState>,
+    ) -> Self {
+        Self { status, cumulative_diff, change_log, final_state }
+    }
+
+    /// Deterministic, versioned byte encoding of this receipt — see
+    /// `canonical::canonical_bytes`.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        super::canonical::canonical_bytes(self)
+    }
+
+    /// SHA-256 over `canonical_bytes`, hex-encoded: two receipts share this
+    /// only when their status, cumulative diff, change log, and final
+    /// state are all identical.
+    pub fn content_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(self.canonical_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// A live system capable of actually executing an `Action` against a
+/// `Target`, reporting the `ApplicationState` it observes immediately
+/// afterward. Kept as a trait object the same way `ViolationReporter` and
+/// `EvidenceSink` are: `ReplayEngine` depends only on this abstraction, so
+/// swapping in an HTTP client, an internal RPC channel, or a test double
+/// never touches the engine itself.
+pub trait ActionDispatcher: Send + Sync {
+    /// Dispatch `action` against `target`, resolving to the
+    /// `ApplicationState` observed right after, or an error describing why
+    /// dispatch failed.
+    fn dispatch(
+        &self,
+        action: &Action,
+        target: &Target,
+    ) -> Pin<Box<dyn Future<Output = Result<ApplicationState, String>> + Send>>;
 }
 
 /// Engine for replaying action sequences
 pub struct ReplayEngine {
     max_retries: u32,
     timeout_ms: u64,
+    telemetry: Telemetry,
 }
 
 impl ReplayEngine {
@@ -171,6 +318,7 @@ impl ReplayEngine {
         Self {
             max_retries: 3,
             timeout_ms: 30000,
+            telemetry: Telemetry::init(),
         }
     }
 
@@ -178,16 +326,28 @@ impl ReplayEngine {
         Self {
             max_retries,
             timeout_ms,
+            telemetry: Telemetry::init(),
         }
     }
 
+    /// Enable or disable this engine's tracing/metrics at runtime. See
+    /// [`Telemetry::set_enabled`].
+    pub fn set_tracing_enabled(&self, enabled: bool) {
+        self.telemetry.set_enabled(enabled);
+    }
+
     /// Generate replay instructions from a state transition
     pub fn generate_instructions(&self, transition: &StateTransition) -> ReplayInstructions {
+        let _span = self.telemetry.start_span(
+            "replay_engine.generate_instructions",
+            &[("transition_id", transition.id.clone())],
+        );
+
         let mut instructions = ReplayInstructions::new();
-        
+
         // Set initial state requirements based on before state
         instructions.initial_state_requirements = self.extract_requirements(&transition.from_state);
-        
+
         // Add the triggering action as a replay step
         instructions.add_step(ReplayStep {
             sequence: 1,
@@ -197,18 +357,23 @@ impl ReplayEngine {
             retry_on_failure: true,
             max_retries: self.max_retries,
         });
-        
+
         instructions
     }
 
     /// Generate instructions from multiple transitions
     pub fn generate_from_sequence(&self, transitions: &[StateTransition]) -> ReplayInstructions {
+        let _span = self.telemetry.start_span(
+            "replay_engine.generate_from_sequence",
+            &[("transition_count", transitions.len().to_string())],
+        );
+
         let mut instructions = ReplayInstructions::new();
-        
+
         if let Some(first) = transitions.first() {
             instructions.initial_state_requirements = self.extract_requirements(&first.from_state);
         }
-        
+
         for (i, transition) in transitions.iter().enumerate() {
             instructions.add_step(ReplayStep {
                 sequence: (i + 1) as u32,
@@ -219,10 +384,270 @@ impl ReplayEngine {
                 max_retries: self.max_retries,
             });
         }
-        
+
         instructions
     }
 
+    /// Replay `instructions`' steps against their paired, already-recorded
+    /// `transitions` (matched pairwise, in order), building a
+    /// [`ReplayTrace`] per step from the canonical diff each transition
+    /// actually produced (see `CausalEngine::diff_states`). A step whose
+    /// transition produced no observable diff at all is treated as having
+    /// failed to take effect; if the step is configured with
+    /// `retry_on_failure`, that is recorded as up to `max_retries` retry
+    /// children, and the step itself is only marked failed once every
+    /// retry is also a no-op. The overall result fails if any step's
+    /// trace - root or any descendant - failed.
+    pub fn replay(&self, instructions: &ReplayInstructions, transitions: &[StateTransition]) -> ReplayResult {
+        let started = std::time::Instant::now();
+        let _span = self.telemetry.start_span(
+            "replay_engine.replay",
+            &[("step_count", instructions.steps.len().to_string())],
+        );
+
+        let causal = CausalEngine::new();
+        let mut roots = Vec::with_capacity(instructions.steps.len());
+        let mut steps_completed = 0u32;
+
+        for (step, transition) in instructions.steps.iter().zip(transitions) {
+            let diff = causal.diff_states(&transition.from_state, &transition.to_state);
+            let took_effect = !diff.is_empty();
+            let report = step.expected_state_after.as_ref().map(|assertion| assertion::evaluate(&transition.to_state, assertion));
+            let assertion_passed = report.as_ref().map(AssertionReport::all_passed).unwrap_or(true);
+            let mut node = ReplayTrace::new(step.action.clone(), diff.clone(), took_effect && assertion_passed);
+            if let Some(report) = report {
+                node = node.with_assertions(report);
+            }
+
+            if !node.success && step.retry_on_failure {
+                for _ in 0..step.max_retries {
+                    // Replaying the same recorded transition again can't
+                    // discover an outcome the original capture didn't
+                    // have, so every retry attempt reproduces the same
+                    // no-op diff.
+                    node.push_child(ReplayTrace::new(step.action.clone(), diff.clone(), false));
+                }
+            }
+
+            steps_completed += 1;
+            roots.push(node);
+        }
+
+        let success = !roots.iter().any(ReplayTrace::failed);
+        let final_state = transitions.last().map(|t| t.to_state.clone());
+        let cumulative_diff = match (transitions.first(), transitions.last()) {
+            (Some(first), Some(last)) => causal.diff_states(&first.from_state, &last.to_state),
+            _ => StateDiff::default(),
+        };
+        let invariant_violated = assertion::confirms_expected_outcome(&cumulative_diff, &instructions.expected_outcome);
+
+        ReplayResult {
+            success,
+            steps_completed,
+            total_steps: instructions.steps.len() as u32,
+            final_state,
+            invariant_violated,
+            error: None,
+            duration_ms: started.elapsed().as_millis() as u64,
+            is_deterministic: true,
+            trace: roots,
+        }
+    }
+
+    /// Issue a [`ReplayReceipt`] for a completed `replay()` run, given the
+    /// same `transitions` it was built from. The cumulative diff spans the
+    /// first transition's `from_state` to the last transition's `to_state`;
+    /// the change log concatenates each root trace node's diff, in step
+    /// order.
+    pub fn issue_receipt(&self, result: &ReplayResult, transitions: &[StateTransition]) -> ReplayReceipt {
+        let causal = CausalEngine::new();
+        let cumulative_diff = match (transitions.first(), transitions.last()) {
+            (Some(first), Some(last)) => causal.diff_states(&first.from_state, &last.to_state),
+            _ => StateDiff::default(),
+        };
+        let change_log = result.trace.iter().flat_map(|root| root.diff.clone().into_changes()).collect();
+
+        let failed_steps = result.trace.iter().filter(|root| root.failed()).count();
+        let status = if failed_steps == 0 {
+            ReplayStatus::Completed
+        } else if failed_steps < result.trace.len() {
+            ReplayStatus::PartiallyCompleted
+        } else {
+            ReplayStatus::Failed
+        };
+
+        ReplayReceipt::new(status, cumulative_diff, change_log, result.final_state.clone())
+    }
+
+    /// Build a receipt directly from a run's trace and the `ApplicationState`
+    /// it started from, for callers (like `execute_repeated`) that never
+    /// had `StateTransition`s to hand `issue_receipt` in the first place.
+    fn receipt_from_run(&self, trace: &[ReplayTrace], initial_state: &ApplicationState, final_state: Option<ApplicationState>) -> ReplayReceipt {
+        let causal = CausalEngine::new();
+        let cumulative_diff = match &final_state {
+            Some(after) => causal.diff_states(initial_state, after),
+            None => StateDiff::default(),
+        };
+        let change_log = trace.iter().flat_map(|root| root.diff.clone().into_changes()).collect();
+
+        let failed_steps = trace.iter().filter(|root| root.failed()).count();
+        let status = if failed_steps == 0 {
+            ReplayStatus::Completed
+        } else if failed_steps < trace.len() {
+            ReplayStatus::PartiallyCompleted
+        } else {
+            ReplayStatus::Failed
+        };
+
+        ReplayReceipt::new(status, cumulative_diff, change_log, final_state)
+    }
+
+    /// Execute `instructions` for real against a live `target`, dispatching
+    /// each `ReplayStep`'s `Action` through `dispatcher` instead of
+    /// replaying an already-recorded transition. Honors `wait_before_ms`
+    /// between steps, and when `retry_on_failure` is set, re-dispatches a
+    /// step up to `max_retries` times if it fails or produces no
+    /// observable diff - each retry is a real call through `dispatcher`,
+    /// not a fabricated failure, so a transient error against the live
+    /// target can actually recover. The step is reported successful the
+    /// moment a retry succeeds, using that retry's real resulting state
+    /// and diff. Each dispatch (initial or retry) is bounded by this
+    /// engine's `timeout_ms`. If `instructions.timing_constraints` are set, the run
+    /// is reported as failed when the total duration or any inter-step gap
+    /// falls outside the configured bounds - the steps themselves still
+    /// run to completion so the trace reflects what actually happened.
+    pub async fn execute(&self, instructions: &ReplayInstructions, target: &Target, dispatcher: &dyn ActionDispatcher) -> ReplayResult {
+        let started = std::time::Instant::now();
+        let _span = self.telemetry.start_span(
+            "replay_engine.execute",
+            &[("step_count", instructions.steps.len().to_string())],
+        );
+
+        let causal = CausalEngine::new();
+        let mut roots = Vec::with_capacity(instructions.steps.len());
+        let mut steps_completed = 0u32;
+        let mut current_state = ApplicationState::default();
+        let mut last_step_started: Option<std::time::Instant> = None;
+        let mut timing_violated = false;
+
+        for step in &instructions.steps {
+            if let Some(wait_ms) = step.wait_before_ms {
+                tokio::time::sleep(std::time::Duration::from_millis(wait_ms)).await;
+            }
+
+            if let (Some(previous), Some(limits)) = (last_step_started, &instructions.timing_constraints) {
+                let gap_ms = previous.elapsed().as_millis() as u64;
+                if gap_ms < limits.min_step_interval_ms || gap_ms > limits.max_step_interval_ms {
+                    timing_violated = true;
+                }
+            }
+            last_step_started = Some(std::time::Instant::now());
+
+            let (after, mut node) = self.dispatch_step(step, &current_state, target, dispatcher, &causal).await;
+            current_state = after;
+
+            if !node.success && step.retry_on_failure {
+                for _ in 0..step.max_retries {
+                    let (retry_after, retry_node) = self.dispatch_step(step, &current_state, target, dispatcher, &causal).await;
+                    let retry_succeeded = retry_node.success;
+                    if retry_succeeded {
+                        current_state = retry_after;
+                        node.diff = retry_node.diff.clone();
+                        node.assertions = retry_node.assertions.clone();
+                        node.success = true;
+                        node.push_child(retry_node);
+                        break;
+                    }
+                    node.push_child(retry_node);
+                }
+            }
+
+            steps_completed += 1;
+            roots.push(node);
+        }
+
+        let duration_ms = started.elapsed().as_millis() as u64;
+        if let Some(limits) = &instructions.timing_constraints {
+            if duration_ms > limits.max_total_duration_ms {
+                timing_violated = true;
+            }
+        }
+
+        let success = !timing_violated && !roots.iter().any(ReplayTrace::failed);
+        let cumulative_diff = causal.diff_states(&ApplicationState::default(), &current_state);
+        let invariant_violated = assertion::confirms_expected_outcome(&cumulative_diff, &instructions.expected_outcome);
+
+        ReplayResult {
+            success,
+            steps_completed,
+            total_steps: instructions.steps.len() as u32,
+            final_state: Some(current_state),
+            invariant_violated,
+            error: if timing_violated {
+                Some("replay run violated its configured timing constraints".to_string())
+            } else {
+                None
+            },
+            duration_ms,
+            is_deterministic: true,
+            trace: roots,
+        }
+    }
+
+    /// Dispatch `step`'s action once against `target`, bounded by this
+    /// engine's `timeout_ms`. Returns the resulting state (`before`
+    /// unchanged if the dispatch failed or timed out) alongside the
+    /// `ReplayTrace` node describing what happened - shared by `execute`'s
+    /// initial attempt and its retry loop so a retry is a real dispatch,
+    /// not a fabricated failure.
+    async fn dispatch_step(
+        &self,
+        step: &ReplayStep,
+        before: &ApplicationState,
+        target: &Target,
+        dispatcher: &dyn ActionDispatcher,
+        causal: &CausalEngine,
+    ) -> (ApplicationState, ReplayTrace) {
+        let dispatched = tokio::time::timeout(
+            std::time::Duration::from_millis(self.timeout_ms),
+            dispatcher.dispatch(&step.action, target),
+        ).await;
+
+        match dispatched {
+            Ok(Ok(after)) => {
+                let diff = causal.diff_states(before, &after);
+                let took_effect = !diff.is_empty();
+                let report = step.expected_state_after.as_ref().map(|assertion| assertion::evaluate(&after, assertion));
+                let assertion_passed = report.as_ref().map(AssertionReport::all_passed).unwrap_or(true);
+                let mut node = ReplayTrace::new(step.action.clone(), diff, took_effect && assertion_passed);
+                if let Some(report) = report {
+                    node = node.with_assertions(report);
+                }
+                (after, node)
+            }
+            Ok(Err(_)) | Err(_) => (before.clone(), ReplayTrace::new(step.action.clone(), StateDiff::default(), false)),
+        }
+    }
+
+    /// Run `instructions` against `target` `n` times over, issuing a
+    /// [`ReplayReceipt`] per run, and report whether they all agree via
+    /// `is_deterministic` - the only way to actually establish determinism
+    /// for a live target, as opposed to a single `execute()` call.
+    pub async fn execute_repeated(
+        &self,
+        instructions: &ReplayInstructions,
+        target: &Target,
+        dispatcher: &dyn ActionDispatcher,
+        n: u32,
+    ) -> bool {
+        let mut receipts = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            let result = self.execute(instructions, target, dispatcher).await;
+            receipts.push(self.receipt_from_run(&result.trace, &ApplicationState::default(), result.final_state));
+        }
+        self.is_deterministic(&receipts)
+    }
+
     /// Extract state requirements from application state
     fn extract_requirements(&self, state: &ApplicationState) -> StateRequirements {
         let mut requirements = StateRequirements::default();
@@ -235,9 +660,11 @@ impl ReplayEngine {
             });
         }
         
-        // Extract balance requirements
+        // Extract balance requirements. Keeping the exact `MonetaryValue`
+        // (rather than truncating to `i64`) is what lets a BTC/ETH holding
+        // past `i64::MAX` minor units still round-trip through replay.
         for (acc_id, balance) in &state.balances {
-            requirements.required_balances.insert(acc_id.0.clone(), balance.amount);
+            requirements.required_balances.insert(acc_id.0.clone(), balance.amount.clone());
         }
         
         // Extract ownership requirements
@@ -276,7 +703,7 @@ impl ReplayEngine {
                 _ => {}
             }
         }
-        
+
         // Check ownership requirements
         for (obj_id, required_owner) in &requirements.required_ownership {
             let object_id = ObjectId(obj_id.clone());
@@ -291,18 +718,31 @@ impl ReplayEngine {
         true
     }
 
-    /// Check if replay result is deterministic
-    pub fn is_deterministic(&self, results: &[ReplayResult]) -> bool {
-        if results.len() < 2 {
+    /// Check whether a set of replay runs reached identical outcomes. Unlike
+    /// the coarse `success`/step-count comparison `ReplayResult` invites,
+    /// this compares each receipt's `content_hash` — which commits to the
+    /// final state and the ordered change log alike — so two runs that both
+    /// "succeed" but land on different final states are correctly reported
+    /// as non-deterministic.
+    pub fn is_deterministic(&self, receipts: &[ReplayReceipt]) -> bool {
+        if receipts.len() < 2 {
             return true;
         }
-        
-        // All results should have same success status and invariant violation
-        let first = &results[0];
-        results.iter().all(|r| {
-            r.success == first.success && 
-            r.invariant_violated == first.invariant_violated
-        })
+
+        let first_hash = receipts[0].content_hash();
+        receipts.iter().all(|r| r.content_hash() == first_hash)
+    }
+
+    /// Confirm that `receipt` actually reproduces `original`'s diff: the
+    /// change log a replay emitted must equal the diff the original
+    /// transition itself produced, in the same order. This is the
+    /// replay-fidelity check `is_deterministic` alone can't give, since two
+    /// receipts can agree with each other while both diverging from the
+    /// transition they were meant to reproduce.
+    pub fn verify_reproduces(&self, original: &StateTransition, receipt: &ReplayReceipt) -> bool {
+        let causal = CausalEngine::new();
+        let original_changes = causal.diff_states(&original.from_state, &original.to_state).into_changes();
+        original_changes == receipt.change_log
     }
 }
 
@@ -355,15 +795,337 @@ mod tests {
         assert!(engine.validate_requirements(&state, &requirements));
     }
 
+    #[test]
+    fn test_requirements_round_trip_a_balance_past_i64_max() {
+        let engine = ReplayEngine::new();
+        // 10x i64::MAX wei - the kind of ETH holding an i64 can't represent.
+        let wei = MonetaryValue::from_integer(i64::MAX) * MonetaryValue::from_integer(10);
+        let mut state = ApplicationState::default();
+        state.balances.insert(AccountId("whale".to_string()), Balance::new(wei.clone(), Currency::ETH));
+
+        let requirements = engine.generate_instructions(&StateTransition {
+            id: "t1".to_string(),
+            from_state: state.clone(),
+            to_state: state.clone(),
+            triggering_action: create_test_action(),
+            timestamp: Utc::now(),
+        }).initial_state_requirements;
+
+        assert_eq!(requirements.required_balances.get("whale"), Some(&wei));
+        assert!(engine.validate_requirements(&state, &requirements));
+    }
+
+    fn receipt_for(engine: &ReplayEngine, transitions: &[StateTransition]) -> ReplayReceipt {
+        let mut instructions = ReplayInstructions::new();
+        for (i, transition) in transitions.iter().enumerate() {
+            instructions.add_step(ReplayStep {
+                sequence: (i + 1) as u32,
+                action: transition.triggering_action.clone(),
+                expected_state_after: None,
+                wait_before_ms: None,
+                retry_on_failure: false,
+                max_retries: 0,
+            });
+        }
+        let result = engine.replay(&instructions, transitions);
+        engine.issue_receipt(&result, transitions)
+    }
+
     #[test]
     fn test_determinism_check() {
         let engine = ReplayEngine::new();
-        
-        let results = vec![
-            ReplayResult::success(ApplicationState::default(), 1, 100),
-            ReplayResult::success(ApplicationState::default(), 1, 150),
-        ];
-        
-        assert!(engine.is_deterministic(&results));
+
+        let transitions = vec![transition_with_balance_change("t1", 100)];
+        let a = receipt_for(&engine, &transitions);
+        let b = receipt_for(&engine, &transitions);
+
+        assert!(engine.is_deterministic(&[a, b]));
+    }
+
+    #[test]
+    fn test_determinism_check_rejects_receipts_that_reach_different_final_states() {
+        let engine = ReplayEngine::new();
+
+        let a = receipt_for(&engine, &[transition_with_balance_change("t1", 100)]);
+        let b = receipt_for(&engine, &[transition_with_balance_change("t1", 200)]);
+
+        assert!(!engine.is_deterministic(&[a, b]));
+    }
+
+    #[test]
+    fn test_verify_reproduces_accepts_a_receipt_matching_the_original_diff() {
+        let engine = ReplayEngine::new();
+        let transition = transition_with_balance_change("t1", 100);
+        let receipt = receipt_for(&engine, &[transition.clone()]);
+
+        assert!(engine.verify_reproduces(&transition, &receipt));
+    }
+
+    #[test]
+    fn test_verify_reproduces_rejects_a_receipt_from_a_different_transition() {
+        let engine = ReplayEngine::new();
+        let original = transition_with_balance_change("t1", 100);
+        let other_receipt = receipt_for(&engine, &[transition_with_balance_change("t1", 200)]);
+
+        assert!(!engine.verify_reproduces(&original, &other_receipt));
+    }
+
+    fn transition_with_balance_change(id: &str, amount: i64) -> StateTransition {
+        let mut after = ApplicationState::default();
+        after.balances.insert(AccountId("acc1".to_string()), Balance::new(amount, Currency::USD));
+
+        StateTransition {
+            id: id.to_string(),
+            from_state: ApplicationState::default(),
+            to_state: after,
+            triggering_action: create_test_action(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn no_op_transition(id: &str) -> StateTransition {
+        let state = ApplicationState::default();
+        StateTransition {
+            id: id.to_string(),
+            from_state: state.clone(),
+            to_state: state,
+            triggering_action: create_test_action(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_replay_builds_a_successful_trace_node_per_step_with_no_retries() {
+        let engine = ReplayEngine::new();
+        let mut instructions = ReplayInstructions::new();
+        instructions.add_step(ReplayStep {
+            sequence: 1,
+            action: create_test_action(),
+            expected_state_after: None,
+            wait_before_ms: None,
+            retry_on_failure: true,
+            max_retries: 3,
+        });
+
+        let transitions = vec![transition_with_balance_change("t1", 100)];
+        let result = engine.replay(&instructions, &transitions);
+
+        assert!(result.success);
+        assert_eq!(result.trace().len(), 1);
+        assert!(result.trace()[0].success);
+        assert!(result.trace()[0].children.is_empty());
+        assert!(!result.trace()[0].diff.is_empty());
+    }
+
+    #[test]
+    fn test_replay_retries_a_no_op_step_and_marks_it_failed_once_retries_are_exhausted() {
+        let engine = ReplayEngine::new();
+        let mut instructions = ReplayInstructions::new();
+        instructions.add_step(ReplayStep {
+            sequence: 1,
+            action: create_test_action(),
+            expected_state_after: None,
+            wait_before_ms: None,
+            retry_on_failure: true,
+            max_retries: 2,
+        });
+
+        let transitions = vec![no_op_transition("t1")];
+        let result = engine.replay(&instructions, &transitions);
+
+        assert!(!result.success);
+        assert_eq!(result.trace().len(), 1);
+        let root = &result.trace()[0];
+        assert!(!root.success);
+        assert_eq!(root.children.len(), 2);
+        assert!(root.failed());
+        assert_eq!(result.flatten_trace().len(), 3);
+    }
+
+    #[test]
+    fn test_engine_telemetry_toggle_does_not_affect_generated_instructions() {
+        let engine = ReplayEngine::new();
+        let transition = StateTransition {
+            id: "t1".to_string(),
+            from_state: ApplicationState::default(),
+            to_state: ApplicationState::default(),
+            triggering_action: create_test_action(),
+            timestamp: Utc::now(),
+        };
+
+        engine.set_tracing_enabled(false);
+        let instructions = engine.generate_instructions(&transition);
+        engine.set_tracing_enabled(true);
+
+        assert_eq!(instructions.steps.len(), 1);
+    }
+
+    fn test_target() -> Target {
+        Target {
+            domain: "example.test".to_string(),
+            subdomains: Vec::new(),
+            services: Vec::new(),
+            technology_stack: TechnologyProfile::default(),
+            authentication: None,
+        }
+    }
+
+    /// Dispatches every action by crediting `acc1` by a fixed amount,
+    /// starting from whatever state it's handed.
+    struct CreditingDispatcher {
+        amount: i64,
+    }
+
+    impl ActionDispatcher for CreditingDispatcher {
+        fn dispatch(
+            &self,
+            _action: &Action,
+            _target: &Target,
+        ) -> Pin<Box<dyn Future<Output = Result<ApplicationState, String>> + Send>> {
+            let amount = self.amount;
+            Box::pin(async move {
+                let mut after = ApplicationState::default();
+                after.balances.insert(AccountId("acc1".to_string()), Balance::new(amount, Currency::USD));
+                Ok(after)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_dispatches_each_step_and_captures_the_resulting_state() {
+        let engine = ReplayEngine::new();
+        let mut instructions = ReplayInstructions::new();
+        instructions.add_step(ReplayStep {
+            sequence: 1,
+            action: create_test_action(),
+            expected_state_after: None,
+            wait_before_ms: None,
+            retry_on_failure: false,
+            max_retries: 0,
+        });
+        let dispatcher = CreditingDispatcher { amount: 100 };
+
+        let result = engine.execute(&instructions, &test_target(), &dispatcher).await;
+
+        assert!(result.success);
+        assert_eq!(result.trace().len(), 1);
+        assert!(!result.trace()[0].diff.is_empty());
+        assert!(result.final_state.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_failure_when_total_duration_exceeds_timing_constraints() {
+        let engine = ReplayEngine::new();
+        let mut instructions = ReplayInstructions::new();
+        instructions.add_step(ReplayStep {
+            sequence: 1,
+            action: create_test_action(),
+            expected_state_after: None,
+            wait_before_ms: Some(20),
+            retry_on_failure: false,
+            max_retries: 0,
+        });
+        instructions.timing_constraints = Some(TimingConstraints {
+            max_total_duration_ms: 1,
+            min_step_interval_ms: 0,
+            max_step_interval_ms: u64::MAX,
+        });
+        let dispatcher = CreditingDispatcher { amount: 100 };
+
+        let result = engine.execute(&instructions, &test_target(), &dispatcher).await;
+
+        assert!(!result.success);
+        assert!(result.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_execute_repeated_is_deterministic_when_the_dispatcher_always_returns_the_same_state() {
+        let engine = ReplayEngine::new();
+        let mut instructions = ReplayInstructions::new();
+        instructions.add_step(ReplayStep {
+            sequence: 1,
+            action: create_test_action(),
+            expected_state_after: None,
+            wait_before_ms: None,
+            retry_on_failure: false,
+            max_retries: 0,
+        });
+        let dispatcher = CreditingDispatcher { amount: 100 };
+
+        assert!(engine.execute_repeated(&instructions, &test_target(), &dispatcher, 3).await);
+    }
+
+    /// Fails dispatch for its first `fail_times` calls, then credits
+    /// `acc1` by `amount` on every call after - models a transient failure
+    /// against a live target that a real retry should recover from.
+    struct FlakyDispatcher {
+        amount: i64,
+        fail_times: usize,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ActionDispatcher for FlakyDispatcher {
+        fn dispatch(
+            &self,
+            _action: &Action,
+            _target: &Target,
+        ) -> Pin<Box<dyn Future<Output = Result<ApplicationState, String>> + Send>> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let amount = self.amount;
+            let should_fail = call < self.fail_times;
+            Box::pin(async move {
+                if should_fail {
+                    return Err("transient failure".to_string());
+                }
+                let mut after = ApplicationState::default();
+                after.balances.insert(AccountId("acc1".to_string()), Balance::new(amount, Currency::USD));
+                Ok(after)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_recovers_a_failed_step_via_a_real_retry_dispatch() {
+        let engine = ReplayEngine::new();
+        let mut instructions = ReplayInstructions::new();
+        instructions.add_step(ReplayStep {
+            sequence: 1,
+            action: create_test_action(),
+            expected_state_after: None,
+            wait_before_ms: None,
+            retry_on_failure: true,
+            max_retries: 2,
+        });
+        let dispatcher = FlakyDispatcher { amount: 100, fail_times: 1, calls: std::sync::atomic::AtomicUsize::new(0) };
+
+        let result = engine.execute(&instructions, &test_target(), &dispatcher).await;
+
+        assert!(result.success);
+        assert_eq!(result.trace().len(), 1);
+        assert!(result.trace()[0].success);
+        assert_eq!(result.trace()[0].children.len(), 1);
+        assert!(!result.trace()[0].diff.is_empty());
+        assert_eq!(dispatcher.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_failure_once_every_retry_also_fails() {
+        let engine = ReplayEngine::new();
+        let mut instructions = ReplayInstructions::new();
+        instructions.add_step(ReplayStep {
+            sequence: 1,
+            action: create_test_action(),
+            expected_state_after: None,
+            wait_before_ms: None,
+            retry_on_failure: true,
+            max_retries: 2,
+        });
+        let dispatcher = FlakyDispatcher { amount: 100, fail_times: usize::MAX, calls: std::sync::atomic::AtomicUsize::new(0) };
+
+        let result = engine.execute(&instructions, &test_target(), &dispatcher).await;
+
+        assert!(!result.success);
+        assert_eq!(result.trace()[0].children.len(), 2);
+        assert_eq!(dispatcher.calls.load(std::sync::atomic::Ordering::SeqCst), 3);
     }
 }
\ No newline at end of file
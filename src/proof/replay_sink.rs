@@ -0,0 +1,261 @@
+//! Replay Sinks - Fan a `ReplayEngine` run out to a source→filter→sink
+//! pipeline instead of only handing the caller a `ReplayResult` to do
+//! something with. A long-running scanning session wants to both keep an
+//! append-only record of every run and alert on the ones that matter, so
+//! `SinkPipeline` lets each registered `ReplaySink` carry its own optional
+//! `SinkFilter` - everything goes to a log file while only `Critical`
+//! findings also go to a webhook, say.
+
+use super::replay::{ReplayInstructions, ReplayResult};
+use super::sink::{SinkError, SinkResult};
+use crate::types::{FindingClassification, Severity};
+use std::collections::HashSet;
+use std::fs;
+use std::future::Future;
+use std::io::Write;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A destination a replay run's `ReplayResult` is streamed to as it
+/// completes.
+pub trait ReplaySink: Send + Sync {
+    /// Emit `result`, paired with the `instructions` it replayed. Called
+    /// once per completed run that passes this sink's `SinkFilter`, if it
+    /// has one.
+    fn emit<'a>(&'a self, result: &'a ReplayResult, instructions: &'a ReplayInstructions) -> BoxFuture<'a, SinkResult<()>>;
+}
+
+/// Criteria a finding must meet for a registered sink to receive it.
+#[derive(Debug, Clone, Default)]
+pub struct SinkFilter {
+    pub min_severity: Option<Severity>,
+    pub classifications: Option<HashSet<FindingClassification>>,
+}
+
+impl SinkFilter {
+    pub fn matches(&self, severity: Severity, classification: FindingClassification) -> bool {
+        self.min_severity.map(|min| severity >= min).unwrap_or(true)
+            && self.classifications.as_ref().map(|set| set.contains(&classification)).unwrap_or(true)
+    }
+}
+
+struct RegisteredSink {
+    sink: Box<dyn ReplaySink>,
+    filter: Option<SinkFilter>,
+}
+
+/// Fan-out over registered `ReplaySink`s, each optionally gated by its own
+/// `SinkFilter`.
+#[derive(Default)]
+pub struct SinkPipeline {
+    sinks: Vec<RegisteredSink>,
+}
+
+impl SinkPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a sink that receives every published result.
+    pub fn add_sink(&mut self, sink: Box<dyn ReplaySink>) {
+        self.sinks.push(RegisteredSink { sink, filter: None });
+    }
+
+    /// Register a sink that only receives results whose severity and
+    /// classification match `filter`.
+    pub fn add_filtered_sink(&mut self, sink: Box<dyn ReplaySink>, filter: SinkFilter) {
+        self.sinks.push(RegisteredSink { sink, filter: Some(filter) });
+    }
+
+    /// Publish `result` to every registered sink whose filter matches
+    /// `severity`/`classification` (or that has no filter at all). Stops
+    /// and returns the first error a sink raises.
+    pub async fn publish(
+        &self,
+        result: &ReplayResult,
+        instructions: &ReplayInstructions,
+        severity: Severity,
+        classification: FindingClassification,
+    ) -> SinkResult<()> {
+        for registered in &self.sinks {
+            let passes = registered.filter.as_ref().map(|f| f.matches(severity, classification)).unwrap_or(true);
+            if passes {
+                registered.sink.emit(result, instructions).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Appends one newline-delimited JSON line per result to `path`.
+pub struct NdjsonFileSink {
+    path: PathBuf,
+}
+
+impl NdjsonFileSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl ReplaySink for NdjsonFileSink {
+    fn emit<'a>(&'a self, result: &'a ReplayResult, _instructions: &'a ReplayInstructions) -> BoxFuture<'a, SinkResult<()>> {
+        Box::pin(async move {
+            let line = serde_json::to_string(result).map_err(|e| SinkError::Io(e.to_string()))?;
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .map_err(|e| SinkError::Io(e.to_string()))?;
+            writeln!(file, "{line}").map_err(|e| SinkError::Io(e.to_string()))
+        })
+    }
+}
+
+/// Writes one newline-delimited JSON line per result to stdout.
+#[derive(Default)]
+pub struct StdoutSink;
+
+impl StdoutSink {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ReplaySink for StdoutSink {
+    fn emit<'a>(&'a self, result: &'a ReplayResult, _instructions: &'a ReplayInstructions) -> BoxFuture<'a, SinkResult<()>> {
+        Box::pin(async move {
+            let line = serde_json::to_string(result).map_err(|e| SinkError::Io(e.to_string()))?;
+            println!("{line}");
+            Ok(())
+        })
+    }
+}
+
+/// Delivers a serialized payload somewhere outside this process - an HTTP
+/// client, a message broker's publish call, and so on. Kept as its own
+/// trait, rather than baked into `WebhookSink`/`MessageQueueSink`
+/// directly, so this crate never has to pick (or depend on) a specific
+/// HTTP client or queue library.
+pub trait PayloadTransport: Send + Sync {
+    fn send(&self, destination: &str, payload: String) -> BoxFuture<'_, SinkResult<()>>;
+}
+
+/// POSTs each result's serialized JSON to a webhook URL via a
+/// caller-supplied `PayloadTransport`.
+pub struct WebhookSink {
+    url: String,
+    transport: Box<dyn PayloadTransport>,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>, transport: Box<dyn PayloadTransport>) -> Self {
+        Self { url: url.into(), transport }
+    }
+}
+
+impl ReplaySink for WebhookSink {
+    fn emit<'a>(&'a self, result: &'a ReplayResult, _instructions: &'a ReplayInstructions) -> BoxFuture<'a, SinkResult<()>> {
+        Box::pin(async move {
+            let body = serde_json::to_string(result).map_err(|e| SinkError::Io(e.to_string()))?;
+            self.transport.send(&self.url, body).await
+        })
+    }
+}
+
+/// Publishes each result's serialized JSON to a named queue/topic via a
+/// caller-supplied `PayloadTransport`.
+pub struct MessageQueueSink {
+    topic: String,
+    transport: Box<dyn PayloadTransport>,
+}
+
+impl MessageQueueSink {
+    pub fn new(topic: impl Into<String>, transport: Box<dyn PayloadTransport>) -> Self {
+        Self { topic: topic.into(), transport }
+    }
+}
+
+impl ReplaySink for MessageQueueSink {
+    fn emit<'a>(&'a self, result: &'a ReplayResult, _instructions: &'a ReplayInstructions) -> BoxFuture<'a, SinkResult<()>> {
+        Box::pin(async move {
+            let body = serde_json::to_string(result).map_err(|e| SinkError::Io(e.to_string()))?;
+            self.transport.send(&self.topic, body).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ApplicationState;
+
+    fn sample_result() -> ReplayResult {
+        ReplayResult::success(ApplicationState::default(), 1, 10)
+    }
+
+    #[derive(Default)]
+    struct RecordingTransport {
+        sent: std::sync::Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl PayloadTransport for RecordingTransport {
+        fn send(&self, destination: &str, payload: String) -> BoxFuture<'_, SinkResult<()>> {
+            self.sent.lock().unwrap().push((destination.to_string(), payload));
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_sink_posts_the_serialized_result_to_its_transport() {
+        let transport = RecordingTransport::default();
+        let sent = transport.sent.clone();
+        let sink = WebhookSink::new("https://example.test/hook", Box::new(transport));
+
+        sink.emit(&sample_result(), &ReplayInstructions::new()).await.unwrap();
+
+        let recorded = sent.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].0, "https://example.test/hook");
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_skips_a_filtered_sink_below_the_minimum_severity() {
+        let mut pipeline = SinkPipeline::new();
+        let path = std::env::temp_dir().join(format!("replay-sink-test-{}.ndjson", uuid::Uuid::new_v4()));
+        pipeline.add_filtered_sink(
+            Box::new(NdjsonFileSink::new(&path)),
+            SinkFilter { min_severity: Some(Severity::Critical), classifications: None },
+        );
+
+        pipeline
+            .publish(&sample_result(), &ReplayInstructions::new(), Severity::Low, FindingClassification::Bug)
+            .await
+            .unwrap();
+
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_delivers_to_a_filtered_sink_that_matches() {
+        let mut pipeline = SinkPipeline::new();
+        let path = std::env::temp_dir().join(format!("replay-sink-test-{}.ndjson", uuid::Uuid::new_v4()));
+        pipeline.add_filtered_sink(
+            Box::new(NdjsonFileSink::new(&path)),
+            SinkFilter { min_severity: Some(Severity::Critical), classifications: None },
+        );
+
+        pipeline
+            .publish(&sample_result(), &ReplayInstructions::new(), Severity::Critical, FindingClassification::Bug)
+            .await
+            .unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 1);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+//! Replay Bundle Signing - Detached ed25519 signatures over a
+//! `ReplayInstructions` bundle, paired with the `FindingId` it belongs to,
+//! so a finding shared between a researcher and a triager can be proven
+//! untampered and attributed to whoever signed it. Mirrors the
+//! `EvidenceCollector::sign`/`verify_signature` convention, but hashes the
+//! bundle via `canonical::canonical_bytes` rather than a hand-rolled
+//! field-by-field digest, since that's what every proof-bearing type
+//! added since has standardized on.
+
+use super::canonical::canonical_bytes;
+use super::replay::ReplayInstructions;
+use crate::types::FindingId;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Everything a signature commits to: the finding it was produced for and
+/// the replay steps (which, via `ReplayInstructions::expected_outcome`,
+/// already carries the outcome the bundle claims to reproduce).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayBundle {
+    pub finding_id: FindingId,
+    pub instructions: ReplayInstructions,
+}
+
+impl ReplayBundle {
+    pub fn new(finding_id: FindingId, instructions: ReplayInstructions) -> Self {
+        Self { finding_id, instructions }
+    }
+
+    /// SHA-256 over the bundle's canonical byte encoding - the exact
+    /// digest a signature is taken over and re-verified against.
+    fn signing_digest(&self) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(canonical_bytes(self));
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Detached signature over a `ReplayBundle`, carrying the signer's public
+/// key alongside the raw signature bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signature {
+    pub bytes: Vec<u8>,
+    /// ed25519 signatures aren't cryptographically recoverable the way a
+    /// secp256k1 ECDSA signature is, so the signer's public key travels
+    /// with the signature rather than being derived from it. `verify`
+    /// still checks this key against the bundle's digest before trusting
+    /// it - an attacker can swap in a different key, but then the
+    /// signature simply fails to verify.
+    pub public_key: Vec<u8>,
+}
+
+/// Generate a fresh ed25519 operator keypair.
+pub fn generate_keypair() -> SigningKey {
+    use rand::rngs::OsRng;
+    SigningKey::generate(&mut OsRng)
+}
+
+/// Deterministically derive a signing key from a brain-phrase, so a lost
+/// key can be regenerated by whoever remembers it. Not a BIP-39 mnemonic
+/// (no wordlist is vendored here) - the passphrase is hashed directly into
+/// the ed25519 seed, which still gives the "same phrase in, same key out"
+/// property researchers need to regenerate a key on a new machine.
+pub fn keypair_from_passphrase(passphrase: &str) -> SigningKey {
+    let mut hasher = Sha256::new();
+    hasher.update(passphrase.as_bytes());
+    let seed: [u8; 32] = hasher.finalize().into();
+    SigningKey::from_bytes(&seed)
+}
+
+/// Sign `bundle`, attributing it to `signing_key`.
+pub fn sign(bundle: &ReplayBundle, signing_key: &SigningKey) -> Signature {
+    let digest = bundle.signing_digest();
+    let signature = signing_key.sign(&digest);
+    Signature {
+        bytes: signature.to_bytes().to_vec(),
+        public_key: signing_key.verifying_key().to_bytes().to_vec(),
+    }
+}
+
+/// Verify that `signature` was produced over `bundle`'s current contents
+/// by the holder of `public_key`. Rejects the signature if either the
+/// embedded public key doesn't match `public_key`, or `bundle` has been
+/// mutated since signing (its recomputed digest no longer matches).
+pub fn verify(bundle: &ReplayBundle, signature: &Signature, public_key: &VerifyingKey) -> bool {
+    if signature.public_key != public_key.to_bytes() {
+        return false;
+    }
+
+    let signature_bytes: [u8; 64] = match signature.bytes.as_slice().try_into() {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    let ed25519_signature = ed25519_dalek::Signature::from_bytes(&signature_bytes);
+
+    public_key.verify(&bundle.signing_digest(), &ed25519_signature).is_ok()
+}
+
+/// Recover the public key embedded in `signature`, without verifying it
+/// against any particular bundle. Callers that need tamper-evidence
+/// should call `verify` instead - this is for attribution bookkeeping
+/// (e.g. deciding whose key to even try `verify`-ing with).
+pub fn recover_public_key(signature: &Signature) -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = signature.public_key.as_slice().try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+/// A short attribution fingerprint for a public key: the hex-encoded first
+/// 20 bytes of its SHA-256 hash, loosely modeled on an Ethereum address's
+/// `keccak256(pubkey)[12..]` derivation (substituting SHA-256, already a
+/// dependency here, for keccak).
+pub fn address(public_key: &VerifyingKey) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(public_key.as_bytes());
+    hex::encode(&hasher.finalize()[..20])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Action;
+    use uuid::Uuid;
+
+    fn sample_bundle() -> ReplayBundle {
+        ReplayBundle::new(FindingId(Uuid::new_v4()), ReplayInstructions::new())
+    }
+
+    #[test]
+    fn test_verify_accepts_a_signature_from_the_signing_key() {
+        let key = generate_keypair();
+        let bundle = sample_bundle();
+
+        let signature = sign(&bundle, &key);
+
+        assert!(verify(&bundle, &signature, &key.verifying_key()));
+    }
+
+    #[test]
+    fn test_verify_rejects_a_signature_once_the_bundle_is_mutated() {
+        let key = generate_keypair();
+        let mut bundle = sample_bundle();
+        let signature = sign(&bundle, &key);
+
+        bundle.instructions.add_step(crate::proof::replay::ReplayStep {
+            sequence: 1,
+            action: Action {
+                id: "a1".to_string(),
+                action_type: crate::state::ActionType::HttpRequest,
+                request: None,
+                parameters: std::collections::HashMap::new(),
+                authentication: None,
+                timing: crate::types::ActionTiming {
+                    start_time: chrono::Utc::now(),
+                    end_time: chrono::Utc::now(),
+                    duration_ms: 0,
+                },
+            },
+            expected_state_after: None,
+            wait_before_ms: None,
+            retry_on_failure: false,
+            max_retries: 0,
+        });
+
+        assert!(!verify(&bundle, &signature, &key.verifying_key()));
+    }
+
+    #[test]
+    fn test_verify_rejects_a_signature_checked_against_the_wrong_key() {
+        let key = generate_keypair();
+        let impostor = generate_keypair();
+        let bundle = sample_bundle();
+
+        let signature = sign(&bundle, &key);
+
+        assert!(!verify(&bundle, &signature, &impostor.verifying_key()));
+    }
+
+    #[test]
+    fn test_keypair_from_passphrase_is_deterministic() {
+        let a = keypair_from_passphrase("correct horse battery staple");
+        let b = keypair_from_passphrase("correct horse battery staple");
+
+        assert_eq!(a.verifying_key().to_bytes(), b.verifying_key().to_bytes());
+    }
+
+    #[test]
+    fn test_recover_public_key_returns_the_embedded_signer() {
+        let key = generate_keypair();
+        let bundle = sample_bundle();
+        let signature = sign(&bundle, &key);
+
+        let recovered = recover_public_key(&signature).unwrap();
+        assert_eq!(recovered.to_bytes(), key.verifying_key().to_bytes());
+    }
+
+    #[test]
+    fn test_address_is_stable_for_the_same_public_key() {
+        let key = generate_keypair();
+        assert_eq!(address(&key.verifying_key()), address(&key.verifying_key()));
+    }
+}
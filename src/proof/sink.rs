@@ -0,0 +1,197 @@
+//! Evidence Sinks - Stream captured artifacts out as they arrive
+//!
+//! `EvidenceCollector` otherwise buffers every artifact in memory until
+//! `finalize()`, which doesn't scale for long engagements capturing large
+//! screenshots/network dumps and offers no way to ship evidence live to
+//! external storage. An `EvidenceSink` turns the collector into a
+//! durable, backpressure-aware streaming source: each `add_*` call fans
+//! its artifact out to every configured sink, following the
+//! observer-pipeline pattern used by blockchain chain-watchers.
+
+use super::evidence::EvidenceArtifact;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Error emitting an artifact to a sink.
+#[derive(Debug)]
+pub enum SinkError {
+    Io(String),
+    /// A non-filesystem transport (an HTTP webhook, a message queue)
+    /// failed to deliver the emitted payload.
+    Transport(String),
+}
+
+impl fmt::Display for SinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SinkError::Io(message) => write!(f, "sink io error: {message}"),
+            SinkError::Transport(message) => write!(f, "sink transport error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SinkError {}
+
+pub type SinkResult<T> = Result<T, SinkError>;
+
+/// A destination artifacts are streamed to as they are captured.
+pub trait EvidenceSink: Send + Sync {
+    /// Emit `artifact`. Called once per artifact, in capture order.
+    fn emit(&self, artifact: &EvidenceArtifact) -> SinkResult<()>;
+
+    /// The `content_hash` of the last artifact this sink has
+    /// acknowledged, if it tracks one. Keyed by content rather than the
+    /// randomly generated artifact id so a crashed run resumes by
+    /// skipping artifacts up to and including this cursor, even though
+    /// the replayed run assigns its artifacts fresh ids.
+    fn cursor(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Writes one content-addressed file per artifact, named by its
+/// `content_hash`, under `directory`.
+pub struct FilesystemSink {
+    directory: PathBuf,
+    cursor: Mutex<Option<String>>,
+}
+
+impl FilesystemSink {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into(), cursor: Mutex::new(None) }
+    }
+
+    fn artifact_path(&self, artifact: &EvidenceArtifact) -> PathBuf {
+        self.directory.join(&artifact.content_hash)
+    }
+}
+
+impl EvidenceSink for FilesystemSink {
+    fn emit(&self, artifact: &EvidenceArtifact) -> SinkResult<()> {
+        fs::create_dir_all(&self.directory).map_err(|e| SinkError::Io(e.to_string()))?;
+        fs::write(self.artifact_path(artifact), &artifact.content)
+            .map_err(|e| SinkError::Io(e.to_string()))?;
+        *self.cursor.lock().unwrap() = Some(artifact.content_hash.clone());
+        Ok(())
+    }
+
+    fn cursor(&self) -> Option<String> {
+        self.cursor.lock().unwrap().clone()
+    }
+}
+
+/// Appends each artifact as one line of JSON to a newline-delimited JSON
+/// stream file, for tailing or bulk ingestion.
+pub struct NdjsonStreamSink {
+    path: PathBuf,
+    cursor: Mutex<Option<String>>,
+}
+
+impl NdjsonStreamSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), cursor: Mutex::new(None) }
+    }
+}
+
+impl EvidenceSink for NdjsonStreamSink {
+    fn emit(&self, artifact: &EvidenceArtifact) -> SinkResult<()> {
+        let line = serde_json::to_string(artifact).map_err(|e| SinkError::Io(e.to_string()))?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| SinkError::Io(e.to_string()))?;
+        writeln!(file, "{line}").map_err(|e| SinkError::Io(e.to_string()))?;
+        *self.cursor.lock().unwrap() = Some(artifact.content_hash.clone());
+        Ok(())
+    }
+
+    fn cursor(&self) -> Option<String> {
+        self.cursor.lock().unwrap().clone()
+    }
+}
+
+/// Buffers emitted artifacts in memory, for tests and short-lived runs.
+#[derive(Default)]
+pub struct MemorySink {
+    artifacts: Mutex<VecDeque<EvidenceArtifact>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every artifact emitted to this sink so far, in emission order.
+    pub fn artifacts(&self) -> Vec<EvidenceArtifact> {
+        self.artifacts.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+impl EvidenceSink for MemorySink {
+    fn emit(&self, artifact: &EvidenceArtifact) -> SinkResult<()> {
+        self.artifacts.lock().unwrap().push_back(artifact.clone());
+        Ok(())
+    }
+
+    fn cursor(&self) -> Option<String> {
+        self.artifacts.lock().unwrap().back().map(|a| a.content_hash.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::evidence::EvidenceType;
+
+    #[test]
+    fn test_memory_sink_records_artifacts_in_order() {
+        let sink = MemorySink::new();
+        let first = EvidenceArtifact::new(EvidenceType::HttpRequest, b"first".to_vec());
+        let second = EvidenceArtifact::new(EvidenceType::HttpResponse, b"second".to_vec());
+
+        sink.emit(&first).unwrap();
+        sink.emit(&second).unwrap();
+
+        let recorded = sink.artifacts();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[0].id, first.id);
+        assert_eq!(recorded[1].id, second.id);
+        assert_eq!(sink.cursor(), Some(second.content_hash));
+    }
+
+    #[test]
+    fn test_filesystem_sink_writes_content_addressed_files() {
+        let dir = std::env::temp_dir().join(format!("evidence-sink-test-{}", uuid::Uuid::new_v4()));
+        let sink = FilesystemSink::new(&dir);
+        let artifact = EvidenceArtifact::new(EvidenceType::ExploitOutput, b"payload".to_vec());
+
+        sink.emit(&artifact).unwrap();
+
+        let written = fs::read(dir.join(&artifact.content_hash)).unwrap();
+        assert_eq!(written, b"payload");
+        assert_eq!(sink.cursor(), Some(artifact.content_hash.clone()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_ndjson_sink_appends_one_line_per_artifact() {
+        let path = std::env::temp_dir().join(format!("evidence-stream-test-{}.ndjson", uuid::Uuid::new_v4()));
+        let sink = NdjsonStreamSink::new(&path);
+        let first = EvidenceArtifact::new(EvidenceType::HttpRequest, b"first".to_vec());
+        let second = EvidenceArtifact::new(EvidenceType::HttpResponse, b"second".to_vec());
+
+        sink.emit(&first).unwrap();
+        sink.emit(&second).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
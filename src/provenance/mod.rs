@@ -0,0 +1,394 @@
+//! Provenance Graph - W3C PROV lineage over `Action`s and the state they
+//! touch
+//!
+//! Turns the flat `authorization_events`/`financial_transactions` history
+//! into a navigable DAG: an `Entity` is a mutable piece of state (an owned
+//! object, an account, a session), an `Activity` is one applied `Action`,
+//! and an `Agent` is the `UserId` it ran under. Recording a
+//! `StateTransition` derives the edges automatically rather than requiring
+//! a caller to hand-build them: the write set comes from
+//! `ApplicationState::diff`, and the read set is every entity in a
+//! [`StateField`](crate::state::StateField) subsystem the caller reports
+//! as touched (`StateDiff::touched_fields` granularity doesn't go finer
+//! than "this subsystem was examined", so every entity it held in
+//! `from_state` is conservatively treated as read).
+//!
+//! This is the same read/write/associated-with vocabulary as the W3C PROV
+//! data model (`used`, `wasGeneratedBy`, `wasAssociatedWith`), so the
+//! graph exports directly to PROV-JSON for external tooling.
+
+use crate::state::{ApplicationState, SessionChange, StateDiff, StateField, StateTransition};
+use crate::types::{AccountId, ObjectId, SessionId, UserId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A mutable piece of `ApplicationState` a provenance edge can reference.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EntityId {
+    Object(ObjectId),
+    Account(AccountId),
+    Session(SessionId),
+}
+
+/// One applied `Action`, identified by its own id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityNode {
+    pub id: String,
+    pub action_type: crate::state::ActionType,
+    pub timing: crate::types::ActionTiming,
+}
+
+/// Any node in the provenance DAG - used by the traversal APIs so
+/// ancestry/descendants queries aren't restricted to one node kind.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProvNode {
+    Entity(EntityId),
+    Activity(String),
+    Agent(UserId),
+}
+
+/// A directed provenance edge, named after the W3C PROV relation it
+/// represents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProvEdge {
+    /// `activity` read `entity`'s value as it stood before the transition.
+    Used { activity: String, entity: EntityId },
+    /// `entity`'s new value was produced by `activity`.
+    WasGeneratedBy { entity: EntityId, activity: String },
+    /// `activity` was performed under `agent`'s authority.
+    WasAssociatedWith { activity: String, agent: UserId },
+}
+
+/// Queryable lineage DAG of entities, activities, and agents, built up one
+/// `StateTransition` at a time.
+#[derive(Debug, Clone, Default)]
+pub struct ProvenanceGraph {
+    entities: HashSet<EntityId>,
+    activities: HashMap<String, ActivityNode>,
+    agents: HashSet<UserId>,
+    edges: Vec<ProvEdge>,
+}
+
+impl ProvenanceGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `transition`'s activity node, its agent (if its action
+    /// carried an authenticated `UserId`), and the `used`/`wasGeneratedBy`
+    /// edges derived from `touched` (the subsystems its invariants
+    /// examined) and the actual before/after diff.
+    pub fn record_transition(&mut self, transition: &StateTransition, touched: &HashSet<StateField>) {
+        let action = &transition.triggering_action;
+
+        self.activities.insert(
+            action.id.clone(),
+            ActivityNode {
+                id: action.id.clone(),
+                action_type: action.action_type.clone(),
+                timing: action.timing.clone(),
+            },
+        );
+
+        if let Some(user_id) = action.authentication.as_ref().and_then(|auth| auth.user_id.clone()) {
+            self.agents.insert(user_id.clone());
+            self.edges.push(ProvEdge::WasAssociatedWith { activity: action.id.clone(), agent: user_id });
+        }
+
+        for entity in Self::read_entities(&transition.from_state, touched) {
+            self.entities.insert(entity.clone());
+            self.edges.push(ProvEdge::Used { activity: action.id.clone(), entity });
+        }
+
+        let diff = transition.from_state.diff(&transition.to_state);
+        for entity in Self::write_entities(&diff) {
+            self.entities.insert(entity.clone());
+            self.edges.push(ProvEdge::WasGeneratedBy { entity, activity: action.id.clone() });
+        }
+    }
+
+    fn read_entities(state: &ApplicationState, touched: &HashSet<StateField>) -> Vec<EntityId> {
+        let mut out = Vec::new();
+        if touched.contains(&StateField::Ownership) {
+            out.extend(state.ownership.keys().cloned().map(EntityId::Object));
+        }
+        if touched.contains(&StateField::Balances) {
+            out.extend(state.balances.keys().cloned().map(EntityId::Account));
+        }
+        if touched.contains(&StateField::Session) {
+            if let Some(session) = &state.current_session {
+                out.push(EntityId::Session(session.session_id.clone()));
+            }
+        }
+        out
+    }
+
+    fn write_entities(diff: &StateDiff) -> Vec<EntityId> {
+        let mut out = Vec::new();
+        out.extend(diff.ownership_changes.iter().map(|c| EntityId::Object(c.object_id.clone())));
+        out.extend(diff.balance_changes.iter().map(|c| EntityId::Account(c.account_id.clone())));
+        out.extend(diff.session_changes.iter().map(|change| {
+            EntityId::Session(
+                match change {
+                    SessionChange::LoggedIn(id) => id,
+                    SessionChange::LoggedOut(id) => id,
+                    SessionChange::RoleAdded(id, _) => id,
+                    SessionChange::RoleRemoved(id, _) => id,
+                    SessionChange::Rotated(_, new_id) => new_id,
+                    SessionChange::UserChanged(id, _, _) => id,
+                    SessionChange::Authenticated(id) => id,
+                    SessionChange::Deauthenticated(id) => id,
+                }
+                .clone(),
+            )
+        }));
+        out
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = &EntityId> {
+        self.entities.iter()
+    }
+
+    pub fn activities(&self) -> impl Iterator<Item = &ActivityNode> {
+        self.activities.values()
+    }
+
+    pub fn agents(&self) -> impl Iterator<Item = &UserId> {
+        self.agents.iter()
+    }
+
+    /// Every directed edge, oriented in causal order (cause, then effect) -
+    /// e.g. `Used { activity, entity }` means `entity` precedes `activity`.
+    fn causal_edges(&self) -> Vec<(ProvNode, ProvNode)> {
+        self.edges
+            .iter()
+            .map(|edge| match edge {
+                ProvEdge::Used { activity, entity } => {
+                    (ProvNode::Entity(entity.clone()), ProvNode::Activity(activity.clone()))
+                }
+                ProvEdge::WasGeneratedBy { entity, activity } => {
+                    (ProvNode::Activity(activity.clone()), ProvNode::Entity(entity.clone()))
+                }
+                ProvEdge::WasAssociatedWith { activity, agent } => {
+                    (ProvNode::Agent(agent.clone()), ProvNode::Activity(activity.clone()))
+                }
+            })
+            .collect()
+    }
+
+    /// Breadth-first walk of `causal_edges` in `direction` (forward for
+    /// descendants, reversed for ancestors), starting from - but not
+    /// including - `start`.
+    fn traverse(&self, start: &ProvNode, forward: bool) -> HashSet<ProvNode> {
+        let edges = self.causal_edges();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(start.clone());
+
+        while let Some(node) = queue.pop_front() {
+            for (from, to) in &edges {
+                let (source, dest) = if forward { (from, to) } else { (to, from) };
+                if source == &node && visited.insert(dest.clone()) {
+                    queue.push_back(dest.clone());
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// Every node that causally precedes `node` - entities it read,
+    /// activities that generated those entities, and the agents those
+    /// activities ran under, transitively.
+    pub fn ancestors(&self, node: &ProvNode) -> HashSet<ProvNode> {
+        self.traverse(node, false)
+    }
+
+    /// Every node that causally follows `node` - activities that used it
+    /// (if `node` is an entity or agent) and the entities those
+    /// activities generated, transitively.
+    pub fn descendants(&self, node: &ProvNode) -> HashSet<ProvNode> {
+        self.traverse(node, true)
+    }
+
+    fn entity_qname(entity: &EntityId) -> String {
+        match entity {
+            EntityId::Object(id) => format!("object:{}", id.0),
+            EntityId::Account(id) => format!("account:{}", id.0),
+            EntityId::Session(id) => format!("session:{}", id.0),
+        }
+    }
+
+    fn agent_qname(agent: &UserId) -> String {
+        format!("user:{}", agent.0)
+    }
+
+    /// Export the graph as a PROV-JSON document: `entity`/`activity`/
+    /// `agent` node maps plus `used`/`wasGeneratedBy`/`wasAssociatedWith`
+    /// relation maps, per the W3C PROV-JSON structure.
+    pub fn to_prov_json(&self) -> serde_json::Value {
+        let mut entity = serde_json::Map::new();
+        for id in &self.entities {
+            entity.insert(Self::entity_qname(id), serde_json::json!({ "prov:type": id }));
+        }
+
+        let mut activity = serde_json::Map::new();
+        for node in self.activities.values() {
+            activity.insert(
+                node.id.clone(),
+                serde_json::json!({
+                    "prov:type": node.action_type,
+                    "prov:startTime": node.timing.start_time,
+                    "prov:endTime": node.timing.end_time,
+                }),
+            );
+        }
+
+        let mut agent = serde_json::Map::new();
+        for id in &self.agents {
+            agent.insert(Self::agent_qname(id), serde_json::json!({ "prov:type": "agent" }));
+        }
+
+        let mut used = serde_json::Map::new();
+        let mut was_generated_by = serde_json::Map::new();
+        let mut was_associated_with = serde_json::Map::new();
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            match edge {
+                ProvEdge::Used { activity, entity } => {
+                    used.insert(
+                        format!("_:used{index}"),
+                        serde_json::json!({
+                            "prov:activity": activity,
+                            "prov:entity": Self::entity_qname(entity),
+                        }),
+                    );
+                }
+                ProvEdge::WasGeneratedBy { entity, activity } => {
+                    was_generated_by.insert(
+                        format!("_:wgb{index}"),
+                        serde_json::json!({
+                            "prov:entity": Self::entity_qname(entity),
+                            "prov:activity": activity,
+                        }),
+                    );
+                }
+                ProvEdge::WasAssociatedWith { activity, agent } => {
+                    was_associated_with.insert(
+                        format!("_:waw{index}"),
+                        serde_json::json!({
+                            "prov:activity": activity,
+                            "prov:agent": Self::agent_qname(agent),
+                        }),
+                    );
+                }
+            }
+        }
+
+        serde_json::json!({
+            "entity": entity,
+            "activity": activity,
+            "agent": agent,
+            "used": used,
+            "wasGeneratedBy": was_generated_by,
+            "wasAssociatedWith": was_associated_with,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Action, ActionType};
+    use crate::types::{ActionTiming, AuthToken, Balance, Currency, TokenType};
+
+    fn action(id: &str, user_id: Option<UserId>) -> Action {
+        let now = chrono::Utc::now();
+        Action {
+            id: id.to_string(),
+            action_type: ActionType::Payment,
+            request: None,
+            parameters: Default::default(),
+            authentication: user_id.map(|user_id| AuthToken {
+                token_type: TokenType::Bearer,
+                value: "tok".to_string(),
+                user_id: Some(user_id),
+                roles: Default::default(),
+                expires_at: None,
+            }),
+            timing: ActionTiming { start_time: now, end_time: now, duration_ms: 0 },
+        }
+    }
+
+    #[test]
+    fn test_recording_a_transition_links_reader_writer_and_agent() {
+        let mut before = ApplicationState::default();
+        before.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let mut after = before.clone();
+        after.balances.insert(AccountId("acc1".to_string()), Balance::new(50, Currency::USD));
+
+        let transition = StateTransition {
+            id: "t1".to_string(),
+            from_state: before,
+            to_state: after,
+            triggering_action: action("act1", Some(UserId("alice".to_string()))),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let mut graph = ProvenanceGraph::new();
+        let mut touched = HashSet::new();
+        touched.insert(StateField::Balances);
+        graph.record_transition(&transition, &touched);
+
+        let account = EntityId::Account(AccountId("acc1".to_string()));
+        assert!(graph.entities().any(|e| e == &account));
+        assert!(graph.activities().any(|a| a.id == "act1"));
+        assert!(graph.agents().any(|a| a == &UserId("alice".to_string())));
+
+        let descendants = graph.descendants(&ProvNode::Agent(UserId("alice".to_string())));
+        assert!(descendants.contains(&ProvNode::Activity("act1".to_string())));
+        assert!(descendants.contains(&ProvNode::Entity(account.clone())));
+
+        let ancestors = graph.ancestors(&ProvNode::Entity(account));
+        assert!(ancestors.contains(&ProvNode::Activity("act1".to_string())));
+        assert!(ancestors.contains(&ProvNode::Agent(UserId("alice".to_string()))));
+    }
+
+    #[test]
+    fn test_prov_json_export_includes_every_node_and_relation_kind() {
+        let mut before = ApplicationState::default();
+        before.ownership.insert(ObjectId("obj1".to_string()), UserId("bob".to_string()));
+
+        let mut after = before.clone();
+        after.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+
+        let transition = StateTransition {
+            id: "t2".to_string(),
+            from_state: before,
+            to_state: after,
+            triggering_action: action("act2", Some(UserId("alice".to_string()))),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let mut graph = ProvenanceGraph::new();
+        let mut touched = HashSet::new();
+        touched.insert(StateField::Ownership);
+        graph.record_transition(&transition, &touched);
+
+        let prov = graph.to_prov_json();
+        assert!(!prov["entity"].as_object().unwrap().is_empty());
+        assert!(!prov["activity"].as_object().unwrap().is_empty());
+        assert!(!prov["agent"].as_object().unwrap().is_empty());
+        assert!(!prov["used"].as_object().unwrap().is_empty());
+        assert!(!prov["wasGeneratedBy"].as_object().unwrap().is_empty());
+        assert!(!prov["wasAssociatedWith"].as_object().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ancestors_of_an_untouched_entity_is_empty() {
+        let graph = ProvenanceGraph::new();
+        let ancestors = graph.ancestors(&ProvNode::Entity(EntityId::Object(ObjectId("nowhere".to_string()))));
+        assert!(ancestors.is_empty());
+    }
+}
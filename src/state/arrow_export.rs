@@ -0,0 +1,147 @@
+//! Arrow Export - Columnar export of ledger entries for analytics.
+//!
+//! Companion to `proof::arrow_export`, which flattens `StateChange`s found
+//! inside a `Proof`'s causality chain; this half covers the ledger
+//! entries those proofs were attested against. Kept as a narrow,
+//! independent schema (rather than joining the two into one table)
+//! because a `LedgerEntry` exists whether or not any proof was ever
+//! attested to it — exporting it shouldn't require one.
+
+#![cfg(feature = "arrow_export")]
+
+use super::LedgerEntry;
+use arrow::array::{ArrayRef, StringArray, TimestampMillisecondArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::error::ArrowError;
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Error exporting to, or reading back from, Arrow.
+#[derive(Debug)]
+pub enum LedgerArrowError {
+    Arrow(ArrowError),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for LedgerArrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerArrowError::Arrow(e) => write!(f, "arrow error: {e}"),
+            LedgerArrowError::Io(e) => write!(f, "io error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LedgerArrowError {}
+
+impl From<ArrowError> for LedgerArrowError {
+    fn from(e: ArrowError) -> Self {
+        LedgerArrowError::Arrow(e)
+    }
+}
+
+impl From<std::io::Error> for LedgerArrowError {
+    fn from(e: std::io::Error) -> Self {
+        LedgerArrowError::Io(e)
+    }
+}
+
+/// The stable schema `LedgerEntry` batches are written with.
+pub fn schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("sequence", DataType::UInt64, false),
+        Field::new("state_hash", DataType::Utf8, false),
+        Field::new("previous_hash", DataType::Utf8, true),
+        Field::new("timestamp", DataType::Timestamp(TimeUnit::Millisecond, None), false),
+    ]))
+}
+
+/// Build one `RecordBatch` from `entries`, against `schema()`.
+pub fn entries_to_batch(entries: &[LedgerEntry]) -> Result<RecordBatch, LedgerArrowError> {
+    let id: ArrayRef = Arc::new(StringArray::from_iter_values(entries.iter().map(|e| e.id.clone())));
+    let sequence: ArrayRef = Arc::new(UInt64Array::from_iter_values(entries.iter().map(|e| e.sequence)));
+    let state_hash: ArrayRef = Arc::new(StringArray::from_iter_values(entries.iter().map(|e| e.state_hash.clone())));
+    let previous_hash: ArrayRef = Arc::new(StringArray::from_iter(entries.iter().map(|e| e.previous_hash.clone())));
+    let timestamp: ArrayRef = Arc::new(TimestampMillisecondArray::from_iter_values(
+        entries.iter().map(|e| e.timestamp.timestamp_millis()),
+    ));
+
+    Ok(RecordBatch::try_new(schema(), vec![id, sequence, state_hash, previous_hash, timestamp])?)
+}
+
+/// Writes/reads `LedgerEntry` batches as Arrow IPC files.
+pub struct LedgerArrowExporter;
+
+impl LedgerArrowExporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write every entry in `entries` to `path` as a single Arrow IPC file.
+    pub fn write_batches(&self, path: &Path, entries: &[LedgerEntry]) -> Result<(), LedgerArrowError> {
+        let batch = entries_to_batch(entries)?;
+        let file = File::create(path)?;
+        let mut writer = FileWriter::try_new(file, &schema())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Read back every batch in an Arrow IPC file written by `write_batches`.
+    pub fn read_batches(&self, path: &Path) -> Result<Vec<RecordBatch>, LedgerArrowError> {
+        let file = File::open(path)?;
+        let reader = FileReader::try_new(file, None)?;
+        reader.collect::<Result<Vec<_>, _>>().map_err(LedgerArrowError::from)
+    }
+}
+
+impl Default for LedgerArrowExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Action, ActionType, ApplicationState, StateLedger, StateTransition};
+    use crate::types::ActionTiming;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_entries_round_trip_through_arrow_ipc() {
+        let ledger = StateLedger::new();
+        let transition = StateTransition {
+            id: "t1".to_string(),
+            from_state: ApplicationState::default(),
+            to_state: ApplicationState::new(),
+            triggering_action: Action {
+                id: "a1".to_string(),
+                action_type: ActionType::Custom("test".to_string()),
+                request: None,
+                parameters: HashMap::new(),
+                authentication: None,
+                timing: ActionTiming { start_time: Utc::now(), end_time: Utc::now(), duration_ms: 1 },
+            },
+            timestamp: Utc::now(),
+        };
+        ledger.record_transition(transition);
+
+        let entries = ledger.get_range(Utc::now() - chrono::Duration::hours(1), Utc::now() + chrono::Duration::hours(1));
+        let path = std::env::temp_dir().join(format!("ledger-arrow-test-{}.arrow", uuid::Uuid::new_v4()));
+        let exporter = LedgerArrowExporter::new();
+        exporter.write_batches(&path, &entries).unwrap();
+
+        let batches = exporter.read_batches(&path).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), entries.len());
+        std::fs::remove_file(&path).ok();
+    }
+}
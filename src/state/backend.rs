@@ -0,0 +1,107 @@
+//! State Backend - Pluggable storage seam for `ApplicationState` lookups
+//!
+//! Mirrors the split `StateLedger` already has between itself and
+//! `LedgerStore` (see `store.rs`): `ApplicationState` today holds every
+//! `ownership`/`balances`/`workflow_positions` entry fully materialized in
+//! its own `HashMap`s, which is fine until a state is too large to keep in
+//! RAM. `StateBackend` is the seam a database- or snapshot-backed store
+//! could sit behind, faulting in only the accounts/objects an invariant
+//! actually reads instead of requiring the whole state resident.
+//!
+//! `InMemoryBackend` is the only implementation provided here, and it
+//! reproduces today's behavior exactly - it just forwards to the
+//! `ApplicationState` it wraps. `InvariantValidator` and `CoverageTracker`
+//! are not made generic over `StateBackend` in this pass: neither type
+//! currently does lookups keyed by individual `ObjectId`/`AccountId` (they
+//! hand a whole `ApplicationState` to each invariant closure), so widening
+//! their signatures ahead of a real out-of-core backend to validate the
+//! split against would just be unused generic parameter noise on every
+//! call site in the crate. `InMemoryBackend` lets an out-of-core backend
+//! be written and tested against this trait first.
+
+use super::ApplicationState;
+use crate::types::*;
+
+/// Lazily-resolvable access to one `ApplicationState`'s subsystems, keyed
+/// by id rather than requiring the whole state materialized. Every method
+/// returns an owned value so a backend is free to reconstruct it on
+/// demand (from a database row, a snapshot page, etc.) rather than
+/// holding a live reference into something already in memory.
+pub trait StateBackend: Send + Sync {
+    /// The owner of `object_id`, if it has one.
+    fn get_ownership(&self, object_id: &ObjectId) -> Option<UserId>;
+
+    /// The balance held by `account_id`, if it has one.
+    fn get_balance(&self, account_id: &AccountId) -> Option<Balance>;
+
+    /// The workflow step `session_id` currently sits at, if any.
+    fn workflow_position(&self, session_id: &SessionId) -> Option<WorkflowStep>;
+
+    /// The currently active session, if one is set.
+    fn current_session(&self) -> Option<SessionState>;
+}
+
+/// A `StateBackend` over a fully materialized `ApplicationState`. This is
+/// the default backend: every method is a direct `HashMap` lookup, so it
+/// preserves exactly the behavior callers get today from touching
+/// `ApplicationState`'s fields straight.
+pub struct InMemoryBackend {
+    state: ApplicationState,
+}
+
+impl InMemoryBackend {
+    pub fn new(state: ApplicationState) -> Self {
+        Self { state }
+    }
+
+    /// The full state this backend wraps, for callers that still need it
+    /// materialized (e.g. to hand to `InvariantValidator::validate_transition`).
+    pub fn state(&self) -> &ApplicationState {
+        &self.state
+    }
+}
+
+impl StateBackend for InMemoryBackend {
+    fn get_ownership(&self, object_id: &ObjectId) -> Option<UserId> {
+        self.state.ownership.get(object_id).cloned()
+    }
+
+    fn get_balance(&self, account_id: &AccountId) -> Option<Balance> {
+        self.state.balances.get(account_id).cloned()
+    }
+
+    fn workflow_position(&self, session_id: &SessionId) -> Option<WorkflowStep> {
+        self.state.workflow_positions.get(session_id).cloned()
+    }
+
+    fn current_session(&self) -> Option<SessionState> {
+        self.state.current_session.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_memory_backend_resolves_materialized_entries() {
+        let mut state = ApplicationState::new();
+        state.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        state.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let backend = InMemoryBackend::new(state);
+
+        assert_eq!(backend.get_ownership(&ObjectId("obj1".to_string())), Some(UserId("alice".to_string())));
+        assert_eq!(backend.get_balance(&AccountId("acc1".to_string())), Some(Balance::new(100, Currency::USD)));
+    }
+
+    #[test]
+    fn test_in_memory_backend_returns_none_for_missing_entries() {
+        let backend = InMemoryBackend::new(ApplicationState::new());
+
+        assert_eq!(backend.get_ownership(&ObjectId("missing".to_string())), None);
+        assert_eq!(backend.get_balance(&AccountId("missing".to_string())), None);
+        assert_eq!(backend.workflow_position(&SessionId("missing".to_string())), None);
+        assert_eq!(backend.current_session(), None);
+    }
+}
@@ -0,0 +1,198 @@
+//! Deterministic content digest for `StateTransition`
+//!
+//! `StateTransition::id` is a randomly generated identifier and its
+//! `timestamp` is wall-clock noise — neither is safe to dedupe or
+//! cross-reference transitions by. `transition_digest` instead folds a
+//! BLAKE2b-256 hash over the keyed maps of `from_state`/`to_state`
+//! (`ownership`, `balances`, `workflow_positions`, `data_objects`; their
+//! `timestamp`, session and history vectors are skipped) plus the `triggering_action`'s
+//! `action_type` and `parameters` (skipping `authentication`, `request`,
+//! and its own `id`), domain-separated per region so no input area can be
+//! mistaken for another. Map entries are folded in order of their
+//! serialized key bytes rather than `HashMap` iteration order, so the
+//! result is invariant to it — the same idea as `transition_root` in
+//! `crate::invariant::audit`, applied to a single transition instead of a
+//! before/after pair of invariant-category slices.
+
+use super::{Action, ApplicationState, StateTransition};
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+use std::collections::HashMap;
+
+/// A 32-byte BLAKE2b content digest of a `StateTransition`'s semantic
+/// contents, stable across re-serialization and independent of map
+/// iteration order.
+pub type TransitionDigest = [u8; 32];
+
+/// The digest an empty region folds to, so "no entries" never collapses
+/// onto whatever bit pattern a real leaf might also hash to.
+const EMPTY_LEAF: TransitionDigest = [0u8; 32];
+
+fn blake2b_256(parts: &[&[u8]]) -> TransitionDigest {
+    let mut hasher = Blake2bVar::new(32).expect("32 is a valid BLAKE2b-256 output size");
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut out = [0u8; 32];
+    hasher
+        .finalize_variable(&mut out)
+        .expect("hasher was created with a 32-byte output size");
+    out
+}
+
+/// Fold every `(key, value)` pair of `entries` into a single region root,
+/// personalized with `tag` so identical bytes hashed in two different
+/// regions never collide. Entries are ordered by their serialized key
+/// bytes - not `K`'s own `Ord`, which several id newtypes here don't
+/// implement - so the root never depends on `HashMap` iteration order.
+fn region_root<K: serde::Serialize, V: serde::Serialize>(tag: &[u8], entries: &HashMap<K, V>) -> TransitionDigest {
+    if entries.is_empty() {
+        return EMPTY_LEAF;
+    }
+
+    let mut leaves: Vec<(Vec<u8>, Vec<u8>)> = entries
+        .iter()
+        .map(|(k, v)| (serde_json::to_vec(k).unwrap_or_default(), serde_json::to_vec(v).unwrap_or_default()))
+        .collect();
+    leaves.sort();
+
+    let mut acc = EMPTY_LEAF;
+    for (key_bytes, value_bytes) in &leaves {
+        let leaf = blake2b_256(&[tag, key_bytes, value_bytes]);
+        acc = blake2b_256(&[&acc, &leaf]);
+    }
+    acc
+}
+
+/// Fold `state`'s dedupe-relevant maps into one content digest, skipping
+/// `timestamp` (wall-clock noise) along with everything that isn't a
+/// keyed map of durable facts - `current_session`, `financial_transactions`,
+/// `trust_decisions`, and the rest are transition history rather than part
+/// of the snapshot two equal transitions would agree on.
+fn state_digest(state: &ApplicationState) -> TransitionDigest {
+    let ownership = region_root(b"KMCP_own", &state.ownership);
+    let balances = region_root(b"KMCP_bal", &state.balances);
+    let workflow_positions = region_root(b"KMCP_wfp", &state.workflow_positions);
+    let data_objects = region_root(b"KMCP_dat", &state.data_objects);
+    blake2b_256(&[b"KMCP_state", &ownership, &balances, &workflow_positions, &data_objects])
+}
+
+/// Hash `action`'s `action_type` and sorted `parameters`. `authentication`
+/// is deliberately excluded: a bearer token or signature is attached after
+/// the fact and varies per call without changing what the action did;
+/// `request`/`id` are likewise incidental to the action's semantics.
+fn action_digest(action: &Action) -> TransitionDigest {
+    let action_type_bytes = serde_json::to_vec(&action.action_type).unwrap_or_default();
+    let params_root = region_root(b"KMCP_param", &action.parameters);
+    blake2b_256(&[b"KMCP_action", &action_type_bytes, &params_root])
+}
+
+/// Deterministic, serialization-stable content digest for `transition`,
+/// excluding its own random `id`/`timestamp`, those of `from_state` and
+/// `to_state`, and `triggering_action.authentication`. Two transitions
+/// with the same semantic before state, after state, and action always
+/// digest identically regardless of `HashMap` iteration order, so this
+/// can dedupe transitions or give a proof a stable identifier to commit
+/// to before non-deterministic authorization data is attached.
+pub fn transition_digest(transition: &StateTransition) -> TransitionDigest {
+    let from = state_digest(&transition.from_state);
+    let to = state_digest(&transition.to_state);
+    let action = action_digest(&transition.triggering_action);
+    blake2b_256(&[b"KMCP_transition", &from, &to, &action])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Action, ActionType, ApplicationState, StateTransition};
+    use crate::types::{AccountId, ActionTiming, Balance, Currency, ObjectId, UserId};
+
+    fn action(parameters: HashMap<String, serde_json::Value>) -> Action {
+        let now = chrono::Utc::now();
+        Action {
+            id: "act-1".to_string(),
+            action_type: ActionType::Payment,
+            request: None,
+            parameters,
+            authentication: None,
+            timing: ActionTiming { start_time: now, end_time: now, duration_ms: 0 },
+        }
+    }
+
+    fn transition(from: ApplicationState, to: ApplicationState, parameters: HashMap<String, serde_json::Value>) -> StateTransition {
+        StateTransition {
+            id: "txn-1".to_string(),
+            from_state: from,
+            to_state: to,
+            triggering_action: action(parameters),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_digest_is_invariant_to_hashmap_insertion_order() {
+        let mut a = ApplicationState::default();
+        a.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        a.ownership.insert(ObjectId("obj2".to_string()), UserId("bob".to_string()));
+
+        let mut b = ApplicationState::default();
+        b.ownership.insert(ObjectId("obj2".to_string()), UserId("bob".to_string()));
+        b.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+
+        let t1 = transition(ApplicationState::default(), a, HashMap::new());
+        let t2 = transition(ApplicationState::default(), b, HashMap::new());
+        assert_eq!(transition_digest(&t1), transition_digest(&t2));
+    }
+
+    #[test]
+    fn test_digest_ignores_id_and_timestamp() {
+        let mut t1 = transition(ApplicationState::default(), ApplicationState::default(), HashMap::new());
+        let mut t2 = t1.clone();
+        t2.id = "a-totally-different-id".to_string();
+        t2.timestamp = t1.timestamp + chrono::Duration::hours(1);
+        t2.from_state.timestamp = Some(chrono::Utc::now());
+
+        assert_eq!(transition_digest(&t1), transition_digest(&t2));
+
+        t1.from_state.balances.insert(AccountId("acc".to_string()), Balance::new(100, Currency::USD));
+        assert_ne!(transition_digest(&t1), transition_digest(&t2));
+    }
+
+    #[test]
+    fn test_digest_ignores_authentication() {
+        let mut t1 = transition(ApplicationState::default(), ApplicationState::default(), HashMap::new());
+        let mut t2 = t1.clone();
+        t2.triggering_action.authentication = Some(crate::types::AuthToken {
+            token_type: crate::types::TokenType::Bearer,
+            value: "secret".to_string(),
+            user_id: Some(UserId("alice".to_string())),
+            roles: Default::default(),
+            expires_at: None,
+        });
+
+        t1.triggering_action.id = "different".to_string();
+
+        assert_eq!(transition_digest(&t1), transition_digest(&t2));
+    }
+
+    #[test]
+    fn test_empty_maps_hash_to_a_fixed_zero_leaf() {
+        let empty = ApplicationState::default();
+        // Two independently-built empty states must agree exactly, since
+        // an empty map always folds to the fixed `EMPTY_LEAF`.
+        assert_eq!(state_digest(&empty), state_digest(&ApplicationState::default()));
+        assert_eq!(region_root(b"KMCP_own", &empty.ownership), EMPTY_LEAF);
+    }
+
+    #[test]
+    fn test_digest_is_sensitive_to_action_parameters() {
+        let mut params_a = HashMap::new();
+        params_a.insert("amount".to_string(), serde_json::json!(100));
+        let mut params_b = HashMap::new();
+        params_b.insert("amount".to_string(), serde_json::json!(200));
+
+        let t1 = transition(ApplicationState::default(), ApplicationState::default(), params_a);
+        let t2 = transition(ApplicationState::default(), ApplicationState::default(), params_b);
+        assert_ne!(transition_digest(&t1), transition_digest(&t2));
+    }
+}
@@ -1,13 +1,211 @@
-//! State Ledger - Immutable record of all state transitions
+//! State Ledger - Hash-chained, branching record of all state transitions
 
-use super::{ApplicationState, StateTransition, Action};
+use super::{ApplicationState, DataObject, StateDiff, StateTransition, Action};
+use super::store::{InMemoryStore, LedgerStore};
+use crate::types::{AccountId, Balance, ObjectId, SessionId, UserId, WorkflowStep};
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+/// Identifies a single leaf in a `StateSnapshot`'s Merkle tree, for
+/// requesting an inclusion proof via `StateSnapshot::prove`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateLeafKey {
+    Ownership(ObjectId),
+    Balance(AccountId),
+    Session(&'static str),
+    WorkflowPosition(SessionId),
+    DataObject(ObjectId),
+    OverdraftPermission(AccountId),
+    FinancialTransaction(String),
+    AuthorizationEvent(usize),
+    TrustDecision(usize),
+    WorkflowCompletion(usize),
+    ExchangeRate(usize),
+    Timestamp,
+}
+
+/// The sibling hashes and position needed to recompute a `StateSnapshot`'s
+/// Merkle root from a single leaf, without shipping the whole state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub leaf_index: usize,
+    pub leaf_hash: String,
+    /// Sibling hashes from the leaf's level up to (not including) the root,
+    /// hex-encoded.
+    pub siblings: Vec<String>,
+}
+
+fn leaf_hash(domain: &[u8], key_bytes: &[u8], value_bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(domain);
+    hasher.update(key_bytes);
+    hasher.update(value_bytes);
+    hasher.finalize().into()
+}
+
+fn merkle_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Canonicalize `state` into its labeled, sorted Merkle leaves, in a fixed
+/// order: one per ownership entry (sorted by object id), one per balance
+/// entry (sorted by account id), one per active session field, one per
+/// workflow position (sorted by session id), one per data object (sorted
+/// by object id), one per overdraft permission (sorted by account id),
+/// one per financial transaction (in recorded order, keyed by transaction
+/// id), one per authorization event/trust decision/workflow
+/// completion/exchange rate (in recorded order, keyed by position - none
+/// of these carry a natural unique id), and finally the top-level
+/// timestamp. Every `ApplicationState` field maps to at least one leaf, so
+/// tampering with any of them changes `StateSnapshot::hash`.
+fn state_leaves(state: &ApplicationState) -> Vec<(StateLeafKey, [u8; 32])> {
+    let mut leaves = Vec::new();
+
+    let mut ownership: Vec<(&ObjectId, &UserId)> = state.ownership.iter().collect();
+    ownership.sort_by(|a, b| (a.0).0.cmp(&(b.0).0));
+    for (object_id, user_id) in ownership {
+        let hash = leaf_hash(b"ownership", object_id.0.as_bytes(), user_id.0.as_bytes());
+        leaves.push((StateLeafKey::Ownership(object_id.clone()), hash));
+    }
+
+    let mut balances: Vec<(&AccountId, &Balance)> = state.balances.iter().collect();
+    balances.sort_by(|a, b| (a.0).0.cmp(&(b.0).0));
+    for (account_id, balance) in balances {
+        let value = serde_json::to_vec(balance).unwrap_or_default();
+        let hash = leaf_hash(b"balance", account_id.0.as_bytes(), &value);
+        leaves.push((StateLeafKey::Balance(account_id.clone()), hash));
+    }
+
+    if let Some(session) = &state.current_session {
+        let mut roles: Vec<&str> = session.roles.iter().map(|r| r.0.as_str()).collect();
+        roles.sort();
+
+        let fields: [(&'static str, Vec<u8>); 3] = [
+            ("session_id", session.session_id.0.as_bytes().to_vec()),
+            ("user_id", session.user_id.0.as_bytes().to_vec()),
+            ("authenticated", serde_json::to_vec(&session.authenticated).unwrap_or_default()),
+        ];
+        for (field, value) in fields {
+            let hash = leaf_hash(b"session", field.as_bytes(), &value);
+            leaves.push((StateLeafKey::Session(field), hash));
+        }
+        let roles_hash = leaf_hash(b"session", b"roles", &serde_json::to_vec(&roles).unwrap_or_default());
+        leaves.push((StateLeafKey::Session("roles"), roles_hash));
+    }
+
+    let mut workflow_positions: Vec<(&SessionId, &WorkflowStep)> = state.workflow_positions.iter().collect();
+    workflow_positions.sort_by(|a, b| (a.0).0.cmp(&(b.0).0));
+    for (session_id, step) in workflow_positions {
+        let value = serde_json::to_vec(step).unwrap_or_default();
+        let hash = leaf_hash(b"workflow_position", session_id.0.as_bytes(), &value);
+        leaves.push((StateLeafKey::WorkflowPosition(session_id.clone()), hash));
+    }
+
+    let mut data_objects: Vec<(&ObjectId, &DataObject)> = state.data_objects.iter().collect();
+    data_objects.sort_by(|a, b| (a.0).0.cmp(&(b.0).0));
+    for (object_id, data_object) in data_objects {
+        let value = serde_json::to_vec(data_object).unwrap_or_default();
+        let hash = leaf_hash(b"data_object", object_id.0.as_bytes(), &value);
+        leaves.push((StateLeafKey::DataObject(object_id.clone()), hash));
+    }
+
+    let mut overdraft_permissions: Vec<&AccountId> = state.overdraft_permissions.iter().collect();
+    overdraft_permissions.sort_by(|a, b| a.0.cmp(&b.0));
+    for account_id in overdraft_permissions {
+        let hash = leaf_hash(b"overdraft_permission", account_id.0.as_bytes(), &[]);
+        leaves.push((StateLeafKey::OverdraftPermission(account_id.clone()), hash));
+    }
+
+    for tx in &state.financial_transactions {
+        let value = serde_json::to_vec(tx).unwrap_or_default();
+        let hash = leaf_hash(b"financial_transaction", tx.id.as_bytes(), &value);
+        leaves.push((StateLeafKey::FinancialTransaction(tx.id.clone()), hash));
+    }
+
+    for (index, event) in state.authorization_events.iter().enumerate() {
+        let value = serde_json::to_vec(event).unwrap_or_default();
+        let hash = leaf_hash(b"authorization_event", index.to_string().as_bytes(), &value);
+        leaves.push((StateLeafKey::AuthorizationEvent(index), hash));
+    }
+
+    for (index, decision) in state.trust_decisions.iter().enumerate() {
+        let value = serde_json::to_vec(decision).unwrap_or_default();
+        let hash = leaf_hash(b"trust_decision", index.to_string().as_bytes(), &value);
+        leaves.push((StateLeafKey::TrustDecision(index), hash));
+    }
+
+    for (index, completion) in state.workflow_completions.iter().enumerate() {
+        let value = serde_json::to_vec(completion).unwrap_or_default();
+        let hash = leaf_hash(b"workflow_completion", index.to_string().as_bytes(), &value);
+        leaves.push((StateLeafKey::WorkflowCompletion(index), hash));
+    }
+
+    for (index, rate) in state.exchange_rates.iter().enumerate() {
+        let value = serde_json::to_vec(rate).unwrap_or_default();
+        let hash = leaf_hash(b"exchange_rate", index.to_string().as_bytes(), &value);
+        leaves.push((StateLeafKey::ExchangeRate(index), hash));
+    }
+
+    let timestamp_value = serde_json::to_vec(&state.timestamp).unwrap_or_default();
+    let timestamp_hash = leaf_hash(b"timestamp", b"", &timestamp_value);
+    leaves.push((StateLeafKey::Timestamp, timestamp_hash));
+
+    leaves
+}
+
+/// Build every level of the binary Merkle tree over `leaves`, bottom-up,
+/// duplicating the last node of a level when its count is odd. An empty
+/// input produces a single all-zero root.
+fn build_merkle_levels(leaves: &[[u8; 32]]) -> Vec<Vec<[u8; 32]>> {
+    if leaves.is_empty() {
+        return vec![vec![[0u8; 32]]];
+    }
+
+    let mut levels = vec![leaves.to_vec()];
+    while levels.last().unwrap().len() > 1 {
+        let current = levels.last().unwrap();
+        let mut next = Vec::with_capacity(current.len().div_ceil(2));
+        for pair in current.chunks(2) {
+            let left = &pair[0];
+            let right = pair.get(1).unwrap_or(left);
+            next.push(merkle_parent(left, right));
+        }
+        levels.push(next);
+    }
+    levels
+}
+
+/// Verify that `leaf` (hex-encoded) is included under `root` (hex-encoded)
+/// according to `proof`.
+pub fn verify_proof(root: &str, leaf: &str, proof: &MerkleProof) -> bool {
+    let Some(mut current) = decode_hash(leaf) else { return false };
+    let mut index = proof.leaf_index;
+
+    for sibling_hex in &proof.siblings {
+        let Some(sibling) = decode_hash(sibling_hex) else { return false };
+        current = if index % 2 == 0 {
+            merkle_parent(&current, &sibling)
+        } else {
+            merkle_parent(&sibling, &current)
+        };
+        index /= 2;
+    }
+
+    hex::encode(current) == root
+}
+
+fn decode_hash(hex_str: &str) -> Option<[u8; 32]> {
+    let bytes = hex::decode(hex_str).ok()?;
+    bytes.try_into().ok()
+}
+
 /// Immutable snapshot of application state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateSnapshot {
@@ -23,12 +221,43 @@ impl StateSnapshot {
         }
     }
 
-    /// Calculate hash of the state for integrity verification
+    /// Calculate hash of the state for integrity verification: a Merkle
+    /// root over its individual components rather than a single hash of
+    /// the serialized whole, so later transitions can be verified with a
+    /// compact inclusion proof instead of rehashing everything.
     pub fn hash(&self) -> String {
-        let serialized = serde_json::to_string(&self.state).unwrap_or_default();
-        let mut hasher = Sha256::new();
-        hasher.update(serialized.as_bytes());
-        hex::encode(hasher.finalize())
+        self.merkle_root()
+    }
+
+    /// The root of this snapshot's state Merkle tree.
+    pub fn merkle_root(&self) -> String {
+        let leaves: Vec<[u8; 32]> = state_leaves(&self.state).into_iter().map(|(_, h)| h).collect();
+        let levels = build_merkle_levels(&leaves);
+        hex::encode(levels.last().unwrap()[0])
+    }
+
+    /// An inclusion proof for `key`, if it names a leaf present in this
+    /// snapshot.
+    pub fn prove(&self, key: &StateLeafKey) -> Option<MerkleProof> {
+        let labeled = state_leaves(&self.state);
+        let index = labeled.iter().position(|(k, _)| k == key)?;
+        let leaves: Vec<[u8; 32]> = labeled.iter().map(|(_, h)| *h).collect();
+        let levels = build_merkle_levels(&leaves);
+
+        let mut siblings = Vec::new();
+        let mut idx = index;
+        for level in &levels[..levels.len() - 1] {
+            let sibling_index = if idx % 2 == 0 { idx + 1 } else { idx - 1 };
+            let sibling = level.get(sibling_index).unwrap_or(&level[idx]);
+            siblings.push(hex::encode(sibling));
+            idx /= 2;
+        }
+
+        Some(MerkleProof {
+            leaf_index: index,
+            leaf_hash: hex::encode(leaves[index]),
+            siblings,
+        })
     }
 }
 
@@ -43,142 +272,311 @@ pub struct LedgerEntry {
     pub timestamp: DateTime<Utc>,
 }
 
-/// Immutable ledger of all state transitions
+/// The entries to undo and apply to move the tracked state from one head
+/// to another, as computed by `StateLedger::tree_route`.
+#[derive(Debug, Clone)]
+pub struct TreeRoute {
+    /// Entries to roll back, nearest-to-`from` first.
+    pub undo: Vec<LedgerEntry>,
+    /// Entries to roll forward, nearest-to-the-common-ancestor first.
+    pub apply: Vec<LedgerEntry>,
+}
+
+/// A branching DAG of state transitions keyed by `state_hash`/
+/// `previous_hash`, so alternate histories (e.g. speculative attack-path
+/// branches) can coexist and the canonical branch can be reorganized onto.
+/// Entries are held behind a pluggable `LedgerStore` rather than an
+/// in-process collection directly, so a long-running capture session can
+/// swap in a pruning, disk-backed store without this type changing.
 pub struct StateLedger {
-    entries: RwLock<Vec<LedgerEntry>>,
+    store: Box<dyn LedgerStore>,
     snapshots: RwLock<HashMap<String, StateSnapshot>>,
-    current_sequence: RwLock<u64>,
+    /// `state_hash` of the tip of the canonical branch.
+    canonical_head: RwLock<Option<String>>,
+    /// The `ApplicationState` materialized at `canonical_head`, rolled
+    /// backward/forward by `reorganize_to`.
+    tracked_state: RwLock<ApplicationState>,
+    /// `Proof::content_hash()` attested against the ledger entry it was
+    /// built from, keyed by that entry's `state_hash`. Lets a caller who
+    /// re-hashes a stored proof later confirm it hasn't been mutated
+    /// since it was filed; see `attest_proof`/`verify_proof_attestation`.
+    attested_proofs: RwLock<HashMap<String, String>>,
 }
 
 impl StateLedger {
+    /// A ledger backed by the default `InMemoryStore`.
     pub fn new() -> Self {
+        Self::with_store(Box::new(InMemoryStore::new()))
+    }
+
+    /// A ledger backed by a caller-supplied `LedgerStore`, e.g. a
+    /// `JournaledPruningStore` for long-running capture sessions.
+    pub fn with_store(store: Box<dyn LedgerStore>) -> Self {
         Self {
-            entries: RwLock::new(Vec::new()),
+            store,
             snapshots: RwLock::new(HashMap::new()),
-            current_sequence: RwLock::new(0),
+            canonical_head: RwLock::new(None),
+            tracked_state: RwLock::new(ApplicationState::default()),
+            attested_proofs: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record that a `Proof` built from the entry at `entry_hash` hashed
+    /// to `proof_content_hash` (see `Proof::content_hash`), so later
+    /// mutation of that stored proof can be detected. Returns `false`
+    /// (and records nothing) if `entry_hash` doesn't name an entry this
+    /// ledger holds.
+    pub fn attest_proof(&self, entry_hash: &str, proof_content_hash: &str) -> bool {
+        if self.store.get_by_hash(entry_hash).is_none() {
+            return false;
         }
+        self.attested_proofs.write().insert(entry_hash.to_string(), proof_content_hash.to_string());
+        true
+    }
+
+    /// Whether `proof_content_hash` still matches what was attested for
+    /// `entry_hash`. `false` if nothing was ever attested for that entry,
+    /// or the stored proof has since been mutated.
+    pub fn verify_proof_attestation(&self, entry_hash: &str, proof_content_hash: &str) -> bool {
+        self.attested_proofs.read().get(entry_hash).map(|h| h.as_str()) == Some(proof_content_hash)
     }
 
-    /// Record a state transition in the ledger
+    /// Record a state transition onto the current canonical head.
     pub fn record_transition(&self, transition: StateTransition) -> String {
-        let mut entries = self.entries.write();
-        let mut sequence = self.current_sequence.write();
-        
-        *sequence += 1;
+        let parent_hash = self.canonical_head.read().clone();
+        self.record_transition_on(parent_hash.as_deref(), transition)
+    }
+
+    /// Record a state transition onto an arbitrary parent, identified by
+    /// its `state_hash`. `parent_hash` of `None` starts a new root branch.
+    /// Only extends the canonical chain (and advances `tracked_state`) when
+    /// `parent_hash` is the current canonical head; otherwise this opens or
+    /// extends a non-canonical branch that `reorganize_to` can later switch
+    /// onto.
+    pub fn record_transition_on(&self, parent_hash: Option<&str>, transition: StateTransition) -> String {
+        let parent_sequence = match parent_hash {
+            Some(hash) => self.store.get_by_hash(hash).map(|e| e.sequence).unwrap_or(0),
+            None => 0,
+        };
+
         let entry_id = Uuid::new_v4().to_string();
-        
         let snapshot = StateSnapshot::new(transition.to_state.clone());
         let state_hash = snapshot.hash();
-        
-        let previous_hash = entries.last().map(|e| e.state_hash.clone());
-        
+
         let entry = LedgerEntry {
             id: entry_id.clone(),
-            sequence: *sequence,
+            sequence: parent_sequence + 1,
             transition,
             state_hash: state_hash.clone(),
-            previous_hash,
+            previous_hash: parent_hash.map(|h| h.to_string()),
             timestamp: Utc::now(),
         };
-        
-        entries.push(entry);
-        
-        // Store snapshot for quick access
-        self.snapshots.write().insert(state_hash, snapshot);
-        
+
+        let to_state = entry.transition.to_state.clone();
+        self.store.append(entry);
+        self.snapshots.write().insert(state_hash.clone(), snapshot);
+
+        if parent_hash == self.canonical_head.read().as_deref() {
+            *self.canonical_head.write() = Some(state_hash);
+            *self.tracked_state.write() = to_state;
+        }
+
         entry_id
     }
 
-    /// Get a specific entry by ID
+    /// Every ancestor of `state_hash`, including its own entry, nearest
+    /// first, walking `previous_hash` links back to the root. Empty if
+    /// `state_hash` names no recorded entry.
+    fn ancestors(&self, state_hash: &str) -> Vec<LedgerEntry> {
+        let mut chain = Vec::new();
+        let mut current = Some(state_hash.to_string());
+        while let Some(hash) = current {
+            match self.store.get_by_hash(&hash) {
+                Some(entry) => {
+                    current = entry.previous_hash.clone();
+                    chain.push(entry);
+                }
+                None => break,
+            }
+        }
+        chain
+    }
+
+    /// Walk `from_hash` and `to_hash` back to their lowest common ancestor,
+    /// returning the entries to undo (from side) and apply (to side) to
+    /// move from one to the other.
+    pub fn tree_route(&self, from_hash: &str, to_hash: &str) -> TreeRoute {
+        let from_chain = self.ancestors(from_hash);
+        let to_chain = self.ancestors(to_hash);
+
+        let to_hashes: HashSet<&str> = to_chain.iter().map(|e| e.state_hash.as_str()).collect();
+        let lca_hash = from_chain.iter()
+            .find(|e| to_hashes.contains(e.state_hash.as_str()))
+            .map(|e| e.state_hash.clone());
+
+        let undo = match &lca_hash {
+            Some(lca) => from_chain.into_iter().take_while(|e| e.state_hash != *lca).collect(),
+            None => from_chain,
+        };
+
+        let mut apply: Vec<LedgerEntry> = match &lca_hash {
+            Some(lca) => to_chain.into_iter().take_while(|e| e.state_hash != *lca).collect(),
+            None => to_chain,
+        };
+        apply.reverse();
+
+        TreeRoute { undo, apply }
+    }
+
+    /// Switch the canonical branch to `head_hash`, rolling the tracked
+    /// `ApplicationState` backward to the common ancestor with the current
+    /// head and then forward onto the new branch. Returns the route taken,
+    /// or `None` if `head_hash` names no recorded entry.
+    pub fn reorganize_to(&self, head_hash: &str) -> Option<TreeRoute> {
+        self.store.get_by_hash(head_hash)?;
+
+        let current_head = self.canonical_head.read().clone().unwrap_or_default();
+        let route = self.tree_route(&current_head, head_hash);
+
+        let base_hash = if route.undo.is_empty() {
+            Some(current_head)
+        } else {
+            route.undo.last().unwrap().previous_hash.clone()
+        };
+        let mut state = base_hash
+            .and_then(|hash| self.store.get_by_hash(&hash).map(|e| e.transition.to_state))
+            .unwrap_or_default();
+
+        for entry in &route.apply {
+            state = entry.transition.to_state.clone();
+        }
+
+        *self.tracked_state.write() = state;
+        *self.canonical_head.write() = Some(head_hash.to_string());
+
+        Some(route)
+    }
+
+    /// The `ApplicationState` materialized at the canonical head.
+    pub fn tracked_state(&self) -> ApplicationState {
+        self.tracked_state.read().clone()
+    }
+
+    /// The `state_hash` of the canonical head, if the ledger has any entries.
+    pub fn canonical_head(&self) -> Option<String> {
+        self.canonical_head.read().clone()
+    }
+
+    /// Get a specific entry by ID, on any branch.
     pub fn get_entry(&self, id: &str) -> Option<LedgerEntry> {
-        self.entries.read()
-            .iter()
-            .find(|e| e.id == id)
-            .cloned()
+        self.store.get_by_id(id)
     }
 
-    /// Get entry by sequence number
+    /// Get an entry by its `state_hash`, on any branch.
+    pub fn get_by_hash(&self, state_hash: &str) -> Option<LedgerEntry> {
+        self.store.get_by_hash(state_hash)
+    }
+
+    /// Get the entry at `sequence` on the canonical branch.
     pub fn get_by_sequence(&self, sequence: u64) -> Option<LedgerEntry> {
-        self.entries.read()
-            .iter()
-            .find(|e| e.sequence == sequence)
-            .cloned()
+        let head = self.canonical_head.read().clone()?;
+        self.ancestors(&head).into_iter().find(|e| e.sequence == sequence)
     }
 
-    /// Get all entries in a time range
+    /// Get all entries, on any branch, in a time range.
     pub fn get_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<LedgerEntry> {
-        self.entries.read()
-            .iter()
-            .filter(|e| e.timestamp >= start && e.timestamp <= end)
-            .cloned()
-            .collect()
+        self.store.iter_range(start, end)
     }
 
-    /// Get the latest state
+    /// Get the latest state on the canonical branch.
     pub fn get_latest_state(&self) -> Option<ApplicationState> {
-        self.entries.read()
-            .last()
-            .map(|e| e.transition.to_state.clone())
+        let head = self.canonical_head.read().clone()?;
+        self.store.get_by_hash(&head).map(|e| e.transition.to_state)
     }
 
-    /// Get state at a specific sequence
+    /// Get state at a specific sequence on the canonical branch.
     pub fn get_state_at(&self, sequence: u64) -> Option<ApplicationState> {
         self.get_by_sequence(sequence)
             .map(|e| e.transition.to_state)
     }
 
-    /// Verify ledger integrity
+    /// Semantic delta between the states recorded at two sequences on the
+    /// canonical branch, for auditing what actually changed across an
+    /// arbitrary span of the ledger rather than just one transition at a
+    /// time.
+    pub fn diff_between(&self, seq_a: u64, seq_b: u64) -> Option<StateDiff> {
+        let state_a = self.get_state_at(seq_a)?;
+        let state_b = self.get_state_at(seq_b)?;
+        Some(state_a.diff(&state_b))
+    }
+
+    /// Answer "what was `key`'s value at `sequence`?" with a compact Merkle
+    /// inclusion proof against the recorded `state_hash`, instead of
+    /// requiring callers to fetch and hash the whole `ApplicationState`.
+    pub fn prove_at(&self, sequence: u64, key: &StateLeafKey) -> Option<(String, MerkleProof)> {
+        let entry = self.get_by_sequence(sequence)?;
+        let snapshot = StateSnapshot::new(entry.transition.to_state);
+        let proof = snapshot.prove(key)?;
+        Some((entry.state_hash, proof))
+    }
+
+    /// Verify integrity of the canonical branch: contiguous sequence
+    /// numbers, an unbroken hash chain, a recomputable state hash at every
+    /// entry, and that every attested proof (`attest_proof`) still names
+    /// an entry this ledger actually holds.
     pub fn verify_integrity(&self) -> bool {
-        let entries = self.entries.read();
-        
-        for (i, entry) in entries.iter().enumerate() {
-            // Verify sequence
+        let head = match self.canonical_head.read().clone() {
+            Some(head) => head,
+            None => return true,
+        };
+
+        let mut chain = self.ancestors(&head);
+        chain.reverse();
+
+        for (i, entry) in chain.iter().enumerate() {
             if entry.sequence != (i + 1) as u64 {
                 return false;
             }
-            
-            // Verify hash chain
-            if i > 0 {
-                if entry.previous_hash != Some(entries[i - 1].state_hash.clone()) {
-                    return false;
-                }
+
+            let expected_previous = if i == 0 { None } else { Some(chain[i - 1].state_hash.clone()) };
+            if entry.previous_hash != expected_previous {
+                return false;
             }
-            
-            // Verify state hash
+
             let snapshot = StateSnapshot::new(entry.transition.to_state.clone());
             if snapshot.hash() != entry.state_hash {
                 return false;
             }
         }
-        
-        true
+
+        let attested: Vec<String> = self.attested_proofs.read().keys().cloned().collect();
+        attested.iter().all(|entry_hash| self.store.get_by_hash(entry_hash).is_some())
     }
 
-    /// Get total number of entries
+    /// Number of entries on the canonical branch.
     pub fn len(&self) -> usize {
-        self.entries.read().len()
+        match self.canonical_head.read().clone() {
+            Some(head) => self.ancestors(&head).len(),
+            None => 0,
+        }
     }
 
-    /// Check if ledger is empty
+    /// Check if the canonical branch is empty.
     pub fn is_empty(&self) -> bool {
-        self.entries.read().is_empty()
+        self.canonical_head.read().is_none()
     }
 
-    /// Get entries for replay
+    /// Get entries for replay, in chronological order, from the canonical
+    /// branch.
     pub fn get_replay_sequence(&self, from: u64, to: u64) -> Vec<LedgerEntry> {
-        self.entries.read()
-            .iter()
-            .filter(|e| e.sequence >= from && e.sequence <= to)
-            .cloned()
-            .collect()
-    }
+        let head = match self.canonical_head.read().clone() {
+            Some(head) => head,
+            None => return Vec::new(),
+        };
 
-    /// Clear all entries (for testing)
-    #[cfg(test)]
-    pub fn clear(&self) {
-        self.entries.write().clear();
-        self.snapshots.write().clear();
-        *self.current_sequence.write() = 0;
+        let mut chain = self.ancestors(&head);
+        chain.reverse();
+        chain.into_iter().filter(|e| e.sequence >= from && e.sequence <= to).collect()
     }
 }
 
@@ -259,12 +657,214 @@ mod tests {
     #[test]
     fn test_replay_sequence() {
         let ledger = StateLedger::new();
-        
+
         for _ in 0..10 {
             ledger.record_transition(create_test_transition());
         }
-        
+
         let replay = ledger.get_replay_sequence(3, 7);
         assert_eq!(replay.len(), 5);
     }
+
+    #[test]
+    fn test_diff_between_reflects_ownership_change_across_sequences() {
+        use crate::types::{ObjectId, UserId};
+
+        let ledger = StateLedger::new();
+
+        let mut first_transition = create_test_transition();
+        first_transition.to_state.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        ledger.record_transition(first_transition);
+
+        let mut second_transition = create_test_transition();
+        second_transition.to_state.ownership.insert(ObjectId("obj1".to_string()), UserId("bob".to_string()));
+        ledger.record_transition(second_transition);
+
+        let diff = ledger.diff_between(1, 2).unwrap();
+        assert_eq!(diff.ownership_changes.len(), 1);
+        assert_eq!(diff.ownership_changes[0].new_owner, Some(UserId("bob".to_string())));
+
+        assert!(ledger.diff_between(1, 99).is_none());
+    }
+
+    #[test]
+    fn test_merkle_inclusion_proof_verifies_against_the_root() {
+        use crate::types::{AccountId, Balance, Currency, ObjectId, UserId};
+
+        let mut state = ApplicationState::new();
+        state.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        state.ownership.insert(ObjectId("obj2".to_string()), UserId("bob".to_string()));
+        state.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let snapshot = StateSnapshot::new(state);
+        let root = snapshot.merkle_root();
+
+        let key = StateLeafKey::Ownership(ObjectId("obj1".to_string()));
+        let proof = snapshot.prove(&key).unwrap();
+
+        assert!(verify_proof(&root, &proof.leaf_hash, &proof));
+    }
+
+    #[test]
+    fn test_merkle_proof_rejects_a_tampered_leaf() {
+        use crate::types::{AccountId, Balance, Currency, ObjectId, UserId};
+
+        let mut state = ApplicationState::new();
+        state.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        state.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let snapshot = StateSnapshot::new(state);
+        let root = snapshot.merkle_root();
+
+        let key = StateLeafKey::Balance(AccountId("acc1".to_string()));
+        let proof = snapshot.prove(&key).unwrap();
+
+        let forged_leaf = hex::encode([0u8; 32]);
+        assert!(!verify_proof(&root, &forged_leaf, &proof));
+    }
+
+    #[test]
+    fn test_merkle_root_changes_when_state_changes() {
+        use crate::types::{ObjectId, UserId};
+
+        let mut state = ApplicationState::new();
+        state.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        let snapshot_a = StateSnapshot::new(state.clone());
+
+        state.ownership.insert(ObjectId("obj1".to_string()), UserId("bob".to_string()));
+        let snapshot_b = StateSnapshot::new(state);
+
+        assert_ne!(snapshot_a.merkle_root(), snapshot_b.merkle_root());
+    }
+
+    #[test]
+    fn test_merkle_root_changes_when_a_financial_transaction_is_tampered_with() {
+        use crate::state::FinancialTransaction;
+        use crate::types::{AccountId, Currency, MonetaryValue};
+
+        let tx = FinancialTransaction {
+            id: "tx1".to_string(),
+            from_account: Some(AccountId("a".to_string())),
+            to_account: Some(AccountId("b".to_string())),
+            amount: MonetaryValue::from_integer(100),
+            currency: Currency::USD,
+            converted_amount: None,
+            converted_currency: None,
+            is_external: false,
+            timestamp: Utc::now(),
+        };
+
+        let mut state = ApplicationState::new();
+        state.financial_transactions.push(tx.clone());
+        let snapshot_a = StateSnapshot::new(state.clone());
+
+        let mut forged_tx = tx;
+        forged_tx.amount = MonetaryValue::from_integer(1_000_000);
+        state.financial_transactions[0] = forged_tx;
+        let snapshot_b = StateSnapshot::new(state);
+
+        assert_ne!(snapshot_a.merkle_root(), snapshot_b.merkle_root());
+    }
+
+    #[test]
+    fn test_prove_at_answers_ownership_at_a_sequence_with_a_compact_proof() {
+        use crate::types::{ObjectId, UserId};
+
+        let ledger = StateLedger::new();
+        let mut transition = create_test_transition();
+        transition.to_state.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        ledger.record_transition(transition);
+
+        let key = StateLeafKey::Ownership(ObjectId("obj1".to_string()));
+        let (root, proof) = ledger.prove_at(1, &key).unwrap();
+
+        assert!(verify_proof(&root, &proof.leaf_hash, &proof));
+        assert!(ledger.prove_at(99, &key).is_none());
+    }
+
+    #[test]
+    fn test_record_transition_on_opens_a_non_canonical_branch() {
+        let ledger = StateLedger::new();
+        let common_id = ledger.record_transition(create_test_transition());
+        let common_hash = ledger.get_entry(&common_id).unwrap().state_hash;
+
+        ledger.record_transition(create_test_transition());
+        assert_eq!(ledger.len(), 2);
+
+        // Fork off the common ancestor instead of the canonical head.
+        ledger.record_transition_on(Some(&common_hash), create_test_transition());
+
+        // The fork doesn't touch the canonical branch.
+        assert_eq!(ledger.len(), 2);
+    }
+
+    #[test]
+    fn test_tree_route_finds_the_common_ancestor_of_two_branches() {
+        let ledger = StateLedger::new();
+        let root_id = ledger.record_transition(create_test_transition());
+        let root_hash = ledger.get_entry(&root_id).unwrap().state_hash;
+
+        let branch_a_id = ledger.record_transition_on(Some(&root_hash), create_test_transition());
+        let branch_a_hash = ledger.get_entry(&branch_a_id).unwrap().state_hash;
+        let branch_b_id = ledger.record_transition_on(Some(&root_hash), create_test_transition());
+        let branch_b_hash = ledger.get_entry(&branch_b_id).unwrap().state_hash;
+
+        let route = ledger.tree_route(&branch_a_hash, &branch_b_hash);
+
+        assert_eq!(route.undo.len(), 1);
+        assert_eq!(route.undo[0].state_hash, branch_a_hash);
+        assert_eq!(route.apply.len(), 1);
+        assert_eq!(route.apply[0].state_hash, branch_b_hash);
+    }
+
+    #[test]
+    fn test_reorganize_to_switches_the_canonical_branch_and_tracked_state() {
+        use crate::types::{ObjectId, UserId};
+
+        let ledger = StateLedger::new();
+        let root_id = ledger.record_transition(create_test_transition());
+        let root_hash = ledger.get_entry(&root_id).unwrap().state_hash;
+
+        let mut branch_a_transition = create_test_transition();
+        branch_a_transition.to_state.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        let branch_a_id = ledger.record_transition_on(Some(&root_hash), branch_a_transition);
+        let branch_a_hash = ledger.get_entry(&branch_a_id).unwrap().state_hash;
+
+        let mut branch_b_transition = create_test_transition();
+        branch_b_transition.to_state.ownership.insert(ObjectId("obj1".to_string()), UserId("bob".to_string()));
+        let branch_b_id = ledger.record_transition_on(Some(&root_hash), branch_b_transition);
+        let branch_b_hash = ledger.get_entry(&branch_b_id).unwrap().state_hash;
+
+        // The first fork recorded (branch A) became canonical automatically.
+        assert_eq!(ledger.canonical_head(), Some(branch_a_hash.clone()));
+        assert_eq!(
+            ledger.tracked_state().ownership.get(&ObjectId("obj1".to_string())),
+            Some(&UserId("alice".to_string()))
+        );
+
+        let route = ledger.reorganize_to(&branch_b_hash).unwrap();
+
+        assert_eq!(route.undo[0].state_hash, branch_a_hash);
+        assert_eq!(route.apply[0].state_hash, branch_b_hash);
+        assert_eq!(ledger.canonical_head(), Some(branch_b_hash));
+        assert_eq!(
+            ledger.tracked_state().ownership.get(&ObjectId("obj1".to_string())),
+            Some(&UserId("bob".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_ledger_with_journaled_pruning_store_behaves_like_the_default() {
+        use crate::state::JournaledPruningStore;
+
+        let ledger = StateLedger::with_store(Box::new(JournaledPruningStore::new(2)));
+
+        for _ in 0..5 {
+            ledger.record_transition(create_test_transition());
+        }
+
+        assert_eq!(ledger.len(), 5);
+        assert!(ledger.verify_integrity());
+        assert_eq!(ledger.get_by_sequence(3).unwrap().sequence, 3);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,138 @@
+//! StateManager - top-of-stack checkpoint/revert/commit over ApplicationState
+//!
+//! `ApplicationState::checkpoint`/`revert_to`/`commit` already provide a
+//! fully general, id-addressed journal stack. A caller that just wants to
+//! speculatively apply one `Action`, run
+//! [`InvariantValidator::validate_transition`](crate::invariant::InvariantValidator::validate_transition),
+//! and either unwind or keep the result doesn't need to track that id
+//! through its own control flow though - it only ever operates on whatever
+//! frame is on top. `StateManager` is that top-of-stack convenience,
+//! mirroring the one `StateTracker` already offers over its own id-based
+//! checkpoint stack.
+
+use super::ApplicationState;
+
+/// Wraps an `ApplicationState`, exposing its checkpoint stack through
+/// stack-discipline `checkpoint`/`revert`/`commit` calls instead of
+/// `CheckpointId`s.
+#[derive(Debug, Clone, Default)]
+pub struct StateManager {
+    state: ApplicationState,
+}
+
+impl StateManager {
+    pub fn new(state: ApplicationState) -> Self {
+        Self { state }
+    }
+
+    /// The wrapped state as of right now.
+    pub fn state(&self) -> &ApplicationState {
+        &self.state
+    }
+
+    /// Mutable access for applying a speculative action before validating
+    /// it; call `checkpoint()` first so the mutation can be undone.
+    pub fn state_mut(&mut self) -> &mut ApplicationState {
+        &mut self.state
+    }
+
+    /// Unwrap back into the plain `ApplicationState`.
+    pub fn into_state(self) -> ApplicationState {
+        self.state
+    }
+
+    /// Push a new speculative frame. Every mutation made through
+    /// `state_mut()` after this is undone by the next `revert()` and kept
+    /// by the next `commit()`.
+    pub fn checkpoint(&mut self) {
+        self.state.checkpoint();
+    }
+
+    /// Undo every mutation recorded since the most recently pushed
+    /// checkpoint and pop it. A no-op if no checkpoint is open.
+    pub fn revert(&mut self) {
+        if let Some(id) = self.state.top_checkpoint() {
+            self.state.revert_to(id);
+        }
+    }
+
+    /// Fold the most recently pushed checkpoint's mutations into its
+    /// parent frame, or make them permanent if it was outermost. A no-op
+    /// if no checkpoint is open.
+    pub fn commit(&mut self) {
+        if let Some(id) = self.state.top_checkpoint() {
+            self.state.commit(id);
+        }
+    }
+
+    /// Number of open (uncommitted, unreverted) checkpoints.
+    pub fn checkpoint_depth(&self) -> usize {
+        self.state.checkpoint_depth()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AccountId, Balance, Currency};
+
+    #[test]
+    fn test_revert_undoes_mutations_since_the_last_checkpoint() {
+        let mut manager = StateManager::new(ApplicationState::new());
+        manager.state_mut().set_balance(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        manager.checkpoint();
+        manager.state_mut().set_balance(AccountId("acc1".to_string()), Balance::new(0, Currency::USD));
+        manager.revert();
+
+        assert_eq!(
+            manager.state().get_balance(&AccountId("acc1".to_string())),
+            Some(&Balance::new(100, Currency::USD))
+        );
+        assert_eq!(manager.checkpoint_depth(), 0);
+    }
+
+    #[test]
+    fn test_commit_keeps_mutations_and_pops_the_frame() {
+        let mut manager = StateManager::new(ApplicationState::new());
+
+        manager.checkpoint();
+        manager.state_mut().set_balance(AccountId("acc1".to_string()), Balance::new(50, Currency::USD));
+        manager.commit();
+
+        assert_eq!(
+            manager.state().get_balance(&AccountId("acc1".to_string())),
+            Some(&Balance::new(50, Currency::USD))
+        );
+        assert_eq!(manager.checkpoint_depth(), 0);
+    }
+
+    #[test]
+    fn test_revert_with_no_open_checkpoint_is_a_no_op() {
+        let mut manager = StateManager::new(ApplicationState::new());
+        manager.state_mut().set_balance(AccountId("acc1".to_string()), Balance::new(50, Currency::USD));
+        manager.revert();
+
+        assert_eq!(
+            manager.state().get_balance(&AccountId("acc1".to_string())),
+            Some(&Balance::new(50, Currency::USD))
+        );
+    }
+
+    #[test]
+    fn test_nested_checkpoints_revert_and_commit_in_stack_order() {
+        let mut manager = StateManager::new(ApplicationState::new());
+
+        manager.checkpoint();
+        manager.state_mut().set_balance(AccountId("acc1".to_string()), Balance::new(10, Currency::USD));
+
+        manager.checkpoint();
+        manager.state_mut().set_balance(AccountId("acc1".to_string()), Balance::new(20, Currency::USD));
+        manager.commit();
+        assert_eq!(manager.checkpoint_depth(), 1);
+
+        manager.revert();
+        assert_eq!(manager.state().get_balance(&AccountId("acc1".to_string())), None);
+        assert_eq!(manager.checkpoint_depth(), 0);
+    }
+}
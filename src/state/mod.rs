@@ -3,16 +3,32 @@
 //! This module implements the state ledger and tracking systems for
 //! ownership, balances, roles, and workflow positions.
 
+mod backend;
+mod digest;
 mod ledger;
+mod manager;
+mod store;
 mod tracker;
+#[cfg(feature = "arrow_export")]
+mod arrow_export;
 
-pub use ledger::{StateLedger, StateSnapshot};
-pub use tracker::{StateTracker, OwnershipTracker, BalanceMonitor, SessionTracker, AccessType};
+pub use backend::{StateBackend, InMemoryBackend};
+pub use digest::{transition_digest, TransitionDigest};
+pub use manager::StateManager;
+pub use ledger::{StateLedger, StateSnapshot, StateLeafKey, MerkleProof, verify_proof, TreeRoute, LedgerEntry};
+pub use store::{LedgerStore, InMemoryStore, JournaledPruningStore};
+#[cfg(feature = "arrow_export")]
+pub use arrow_export::{LedgerArrowError, LedgerArrowExporter, entries_to_batch};
+pub use tracker::{
+    StateTracker, OwnershipTracker, BalanceMonitor, SessionTracker, AccessType, CheckpointId,
+    BalanceInvariant, InvariantCheck, InvariantViolation,
+};
 
 use crate::types::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
 /// Complete application state at a point in time
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -28,6 +44,76 @@ pub struct ApplicationState {
     pub overdraft_permissions: HashSet<AccountId>,
     pub trust_decisions: Vec<TrustDecision>,
     pub workflow_completions: Vec<WorkflowCompletion>,
+    /// Exchange rates declared as available for this transition's
+    /// cross-asset conversions.
+    pub exchange_rates: Vec<ExchangeRate>,
+    /// Stack of open `checkpoint()` frames, innermost last. Not part of
+    /// the logical state a transition validates — skipped by
+    /// serialization and ignored by `diff`/`apply_diff`.
+    #[serde(skip)]
+    checkpoints: Vec<CheckpointFrame>,
+}
+
+/// One in-place mutation a journaled `ApplicationState` setter recorded,
+/// holding whatever is needed to undo it: the prior value (or `None` for
+/// a tombstone, meaning the key didn't exist before) for map entries, and
+/// the prior length for append-only vectors.
+#[derive(Debug, Clone)]
+enum JournalEntry {
+    Ownership(ObjectId, Option<UserId>),
+    Balance(AccountId, Option<Balance>),
+    WorkflowPosition(SessionId, Option<WorkflowStep>),
+    CurrentSession(Option<SessionState>),
+    FinancialTransactionsLen(usize),
+    TrustDecisionsLen(usize),
+}
+
+impl JournalEntry {
+    fn undo(self, state: &mut ApplicationState) {
+        match self {
+            JournalEntry::Ownership(object_id, prior) => match prior {
+                Some(owner) => {
+                    state.ownership.insert(object_id, owner);
+                }
+                None => {
+                    state.ownership.remove(&object_id);
+                }
+            },
+            JournalEntry::Balance(account_id, prior) => match prior {
+                Some(balance) => {
+                    state.balances.insert(account_id, balance);
+                }
+                None => {
+                    state.balances.remove(&account_id);
+                }
+            },
+            JournalEntry::WorkflowPosition(session_id, prior) => match prior {
+                Some(step) => {
+                    state.workflow_positions.insert(session_id, step);
+                }
+                None => {
+                    state.workflow_positions.remove(&session_id);
+                }
+            },
+            JournalEntry::CurrentSession(prior) => {
+                state.current_session = prior;
+            }
+            JournalEntry::FinancialTransactionsLen(len) => {
+                state.financial_transactions.truncate(len);
+            }
+            JournalEntry::TrustDecisionsLen(len) => {
+                state.trust_decisions.truncate(len);
+            }
+        }
+    }
+}
+
+/// Every journal entry recorded between a `checkpoint()` call and the
+/// point it's reverted or committed.
+#[derive(Debug, Clone)]
+struct CheckpointFrame {
+    id: CheckpointId,
+    entries: Vec<JournalEntry>,
 }
 
 /// Session state information
@@ -67,8 +153,13 @@ pub struct FinancialTransaction {
     pub id: String,
     pub from_account: Option<AccountId>,
     pub to_account: Option<AccountId>,
-    pub amount: i64,
+    pub amount: MonetaryValue,
     pub currency: Currency,
+    /// Present only for cross-asset transfers: the amount and currency
+    /// actually credited to `to_account`, converted via a declared
+    /// `ExchangeRate` from `currency`.
+    pub converted_amount: Option<MonetaryValue>,
+    pub converted_currency: Option<Currency>,
     pub is_external: bool,
     pub timestamp: DateTime<Utc>,
 }
@@ -156,4 +247,616 @@ impl ApplicationState {
     pub fn get_workflow_step(&self, session_id: &SessionId) -> Option<&WorkflowStep> {
         self.workflow_positions.get(session_id)
     }
+
+    /// Push a new checkpoint frame. Every journaled setter called after
+    /// this (`set_ownership`, `set_balance`, `set_workflow_position`,
+    /// `set_current_session`, `push_financial_transaction`,
+    /// `push_trust_decision`) records the value it overwrote into this
+    /// frame, so `revert_to` can undo it in O(changes) rather than
+    /// requiring the caller to have cloned the whole state up front.
+    /// Checkpoints nest: reverting an outer one also unwinds any inner
+    /// ones taken after it.
+    pub fn checkpoint(&mut self) -> CheckpointId {
+        let id = CheckpointId(Uuid::new_v4());
+        self.checkpoints.push(CheckpointFrame { id, entries: Vec::new() });
+        id
+    }
+
+    /// Undo every journaled mutation recorded since `id` was checkpointed,
+    /// in reverse order, restoring this state to exactly how it looked at
+    /// that point. Also unwinds any checkpoint taken after `id`. A no-op
+    /// if `id` is not on the open checkpoint stack (e.g. it was already
+    /// committed or reverted).
+    pub fn revert_to(&mut self, id: CheckpointId) {
+        let pos = match self.checkpoints.iter().position(|frame| frame.id == id) {
+            Some(pos) => pos,
+            None => return,
+        };
+        let frames: Vec<CheckpointFrame> = self.checkpoints.split_off(pos);
+        for frame in frames.into_iter().rev() {
+            for entry in frame.entries.into_iter().rev() {
+                entry.undo(self);
+            }
+        }
+    }
+
+    /// Discard the journal for `id` without undoing it: the enclosed
+    /// mutations become permanent. If `id` was nested inside another open
+    /// checkpoint, its entries fold into that parent frame instead of
+    /// being dropped (canonicalization), so reverting the parent still
+    /// undoes the committed child's changes too. A no-op if `id` is not
+    /// on the open checkpoint stack.
+    pub fn commit(&mut self, id: CheckpointId) {
+        let pos = match self.checkpoints.iter().position(|frame| frame.id == id) {
+            Some(pos) => pos,
+            None => return,
+        };
+        let frame = self.checkpoints.remove(pos);
+        if let Some(parent) = self.checkpoints.last_mut() {
+            parent.entries.extend(frame.entries);
+        }
+    }
+
+    /// Number of open (uncommitted, unreverted) checkpoints.
+    pub fn checkpoint_depth(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// The id of the most recently pushed, still-open checkpoint, if any.
+    /// Lets a caller operate on "whatever checkpoint is on top" without
+    /// having to thread the id it got back from `checkpoint()` through its
+    /// own control flow - see [`StateManager`](super::StateManager).
+    pub fn top_checkpoint(&self) -> Option<CheckpointId> {
+        self.checkpoints.last().map(|frame| frame.id)
+    }
+
+    fn journal(&mut self, entry: JournalEntry) {
+        if let Some(frame) = self.checkpoints.last_mut() {
+            frame.entries.push(entry);
+        }
+    }
+
+    /// Set an object's owner, journaling the entry it replaces (or lack
+    /// thereof) if a checkpoint is currently open.
+    pub fn set_ownership(&mut self, object_id: ObjectId, user_id: UserId) {
+        let prior = self.ownership.get(&object_id).cloned();
+        self.journal(JournalEntry::Ownership(object_id.clone(), prior));
+        self.ownership.insert(object_id, user_id);
+    }
+
+    /// Remove an object's owner, journaling its prior value if a
+    /// checkpoint is currently open.
+    pub fn remove_ownership(&mut self, object_id: &ObjectId) {
+        if let Some(prior) = self.ownership.remove(object_id) {
+            self.journal(JournalEntry::Ownership(object_id.clone(), Some(prior)));
+        }
+    }
+
+    /// Set an account's balance, journaling the entry it replaces (or
+    /// lack thereof) if a checkpoint is currently open.
+    pub fn set_balance(&mut self, account_id: AccountId, balance: Balance) {
+        let prior = self.balances.get(&account_id).cloned();
+        self.journal(JournalEntry::Balance(account_id.clone(), prior));
+        self.balances.insert(account_id, balance);
+    }
+
+    /// Remove an account's balance, journaling its prior value if a
+    /// checkpoint is currently open.
+    pub fn remove_balance(&mut self, account_id: &AccountId) {
+        if let Some(prior) = self.balances.remove(account_id) {
+            self.journal(JournalEntry::Balance(account_id.clone(), Some(prior)));
+        }
+    }
+
+    /// Set a session's workflow position, journaling the entry it
+    /// replaces (or lack thereof) if a checkpoint is currently open.
+    pub fn set_workflow_position(&mut self, session_id: SessionId, step: WorkflowStep) {
+        let prior = self.workflow_positions.get(&session_id).cloned();
+        self.journal(JournalEntry::WorkflowPosition(session_id.clone(), prior));
+        self.workflow_positions.insert(session_id, step);
+    }
+
+    /// Replace the active session, journaling the one it replaces if a
+    /// checkpoint is currently open.
+    pub fn set_current_session(&mut self, session: Option<SessionState>) {
+        let prior = self.current_session.clone();
+        self.journal(JournalEntry::CurrentSession(prior));
+        self.current_session = session;
+    }
+
+    /// Append a financial transaction, journaling the prior vector length
+    /// if a checkpoint is currently open so it can be truncated back.
+    pub fn push_financial_transaction(&mut self, transaction: FinancialTransaction) {
+        self.journal(JournalEntry::FinancialTransactionsLen(self.financial_transactions.len()));
+        self.financial_transactions.push(transaction);
+    }
+
+    /// Append a trust decision, journaling the prior vector length if a
+    /// checkpoint is currently open so it can be truncated back.
+    pub fn push_trust_decision(&mut self, decision: TrustDecision) {
+        self.journal(JournalEntry::TrustDecisionsLen(self.trust_decisions.len()));
+        self.trust_decisions.push(decision);
+    }
+
+    /// Compute a structured delta between this state and `other`, with
+    /// entries in sorted key order so the result is deterministic
+    /// regardless of the two states' `HashMap` iteration order.
+    pub fn diff(&self, other: &ApplicationState) -> StateDiff {
+        let mut ownership_changes = Vec::new();
+        let mut object_ids: Vec<&ObjectId> = self.ownership.keys().chain(other.ownership.keys()).collect();
+        object_ids.sort_by(|a, b| a.0.cmp(&b.0));
+        object_ids.dedup();
+        for object_id in object_ids {
+            let old_owner = self.ownership.get(object_id);
+            let new_owner = other.ownership.get(object_id);
+            if old_owner != new_owner {
+                ownership_changes.push(OwnershipChange {
+                    object_id: object_id.clone(),
+                    old_owner: old_owner.cloned(),
+                    new_owner: new_owner.cloned(),
+                });
+            }
+        }
+
+        let mut balance_changes = Vec::new();
+        let mut account_ids: Vec<&AccountId> = self.balances.keys().chain(other.balances.keys()).collect();
+        account_ids.sort_by(|a, b| a.0.cmp(&b.0));
+        account_ids.dedup();
+        for account_id in account_ids {
+            let old_balance = self.balances.get(account_id);
+            let new_balance = other.balances.get(account_id);
+            if old_balance != new_balance {
+                balance_changes.push(BalanceChange {
+                    account_id: account_id.clone(),
+                    currency: new_balance.or(old_balance).map(|b| b.currency),
+                    old_amount: old_balance.map(|b| b.amount.clone()),
+                    new_amount: new_balance.map(|b| b.amount.clone()),
+                });
+            }
+        }
+
+        let mut session_changes = Vec::new();
+        match (&self.current_session, &other.current_session) {
+            (None, Some(session)) => {
+                session_changes.push(SessionChange::LoggedIn(session.session_id.clone()));
+            }
+            (Some(session), None) => {
+                session_changes.push(SessionChange::LoggedOut(session.session_id.clone()));
+            }
+            (Some(old_session), Some(new_session)) => {
+                if old_session.session_id != new_session.session_id {
+                    session_changes.push(SessionChange::Rotated(
+                        old_session.session_id.clone(),
+                        new_session.session_id.clone(),
+                    ));
+                }
+
+                if old_session.user_id != new_session.user_id {
+                    session_changes.push(SessionChange::UserChanged(
+                        new_session.session_id.clone(),
+                        old_session.user_id.clone(),
+                        new_session.user_id.clone(),
+                    ));
+                }
+
+                if !old_session.authenticated && new_session.authenticated {
+                    session_changes.push(SessionChange::Authenticated(new_session.session_id.clone()));
+                } else if old_session.authenticated && !new_session.authenticated {
+                    session_changes.push(SessionChange::Deauthenticated(new_session.session_id.clone()));
+                }
+
+                let mut added: Vec<&Role> = new_session.roles.difference(&old_session.roles).collect();
+                added.sort_by(|a, b| a.0.cmp(&b.0));
+                for role in added {
+                    session_changes.push(SessionChange::RoleAdded(new_session.session_id.clone(), role.clone()));
+                }
+
+                let mut removed: Vec<&Role> = old_session.roles.difference(&new_session.roles).collect();
+                removed.sort_by(|a, b| a.0.cmp(&b.0));
+                for role in removed {
+                    session_changes.push(SessionChange::RoleRemoved(old_session.session_id.clone(), role.clone()));
+                }
+            }
+            (None, None) => {}
+        }
+
+        let mut workflow_position_changes = Vec::new();
+        let mut session_ids: Vec<&SessionId> =
+            self.workflow_positions.keys().chain(other.workflow_positions.keys()).collect();
+        session_ids.sort_by(|a, b| a.0.cmp(&b.0));
+        session_ids.dedup();
+        for session_id in session_ids {
+            let old_step = self.workflow_positions.get(session_id);
+            let new_step = other.workflow_positions.get(session_id);
+            if old_step != new_step {
+                workflow_position_changes.push(WorkflowPositionChange {
+                    session_id: session_id.clone(),
+                    old_step: old_step.cloned(),
+                    new_step: new_step.cloned(),
+                });
+            }
+        }
+
+        // Both vectors are append-only (see `push_financial_transaction`/
+        // `push_trust_decision`), so `self`'s entries are always a prefix
+        // of `other`'s whenever anything was appended between them.
+        let new_financial_transactions = if other.financial_transactions.len() > self.financial_transactions.len() {
+            other.financial_transactions[self.financial_transactions.len()..].to_vec()
+        } else {
+            Vec::new()
+        };
+        let new_trust_decisions = if other.trust_decisions.len() > self.trust_decisions.len() {
+            other.trust_decisions[self.trust_decisions.len()..].to_vec()
+        } else {
+            Vec::new()
+        };
+
+        StateDiff {
+            ownership_changes,
+            balance_changes,
+            session_changes,
+            workflow_position_changes,
+            new_financial_transactions,
+            new_trust_decisions,
+        }
+    }
+
+    /// Apply `diff`'s ownership/balance/session changes onto this state,
+    /// moving it from the "before" side of the diff to the "after" side.
+    /// Used to reconstruct a state from a preceding anchor plus a run of
+    /// diffs (see `JournaledPruningStore`) rather than storing every
+    /// intermediate state in full.
+    ///
+    /// Only those three subsystems are touched here. Everything else
+    /// (`workflow_positions`, `data_objects`, the audit vectors,
+    /// `exchange_rates`, `timestamp`) carries through unchanged from
+    /// wherever `self` started, including the workflow position changes
+    /// and appended transactions/trust decisions that `diff` does record. A `LoggedIn` entry also can't restore the full
+    /// `SessionState` it replaced, since the diff only names the session
+    /// id — reconstructing exactly onto a login boundary requires the
+    /// nearest anchor at or after that point.
+    pub fn apply_diff(&mut self, diff: &StateDiff) {
+        for change in &diff.ownership_changes {
+            match &change.new_owner {
+                Some(owner) => {
+                    self.ownership.insert(change.object_id.clone(), owner.clone());
+                }
+                None => {
+                    self.ownership.remove(&change.object_id);
+                }
+            }
+        }
+
+        for change in &diff.balance_changes {
+            match (&change.new_amount, change.currency) {
+                (Some(amount), Some(currency)) => {
+                    self.balances.insert(change.account_id.clone(), Balance::new(amount.clone(), currency));
+                }
+                _ => {
+                    self.balances.remove(&change.account_id);
+                }
+            }
+        }
+
+        for change in &diff.session_changes {
+            match change {
+                SessionChange::LoggedIn(_) => {}
+                SessionChange::LoggedOut(_) => {
+                    self.current_session = None;
+                }
+                SessionChange::RoleAdded(_, role) => {
+                    if let Some(session) = &mut self.current_session {
+                        session.roles.insert(role.clone());
+                    }
+                }
+                SessionChange::RoleRemoved(_, role) => {
+                    if let Some(session) = &mut self.current_session {
+                        session.roles.remove(role);
+                    }
+                }
+                SessionChange::Rotated(_, new_id) => {
+                    if let Some(session) = &mut self.current_session {
+                        session.session_id = new_id.clone();
+                    }
+                }
+                SessionChange::UserChanged(_, _, new_user) => {
+                    if let Some(session) = &mut self.current_session {
+                        session.user_id = new_user.clone();
+                    }
+                }
+                SessionChange::Authenticated(_) => {
+                    if let Some(session) = &mut self.current_session {
+                        session.authenticated = true;
+                    }
+                }
+                SessionChange::Deauthenticated(_) => {
+                    if let Some(session) = &mut self.current_session {
+                        session.authenticated = false;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Structured delta between two `ApplicationState`s, as produced by
+/// [`ApplicationState::diff`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StateDiff {
+    pub ownership_changes: Vec<OwnershipChange>,
+    pub balance_changes: Vec<BalanceChange>,
+    pub session_changes: Vec<SessionChange>,
+    pub workflow_position_changes: Vec<WorkflowPositionChange>,
+    pub new_financial_transactions: Vec<FinancialTransaction>,
+    pub new_trust_decisions: Vec<TrustDecision>,
+}
+
+impl StateDiff {
+    /// True if nothing changed between the two states this diff compares.
+    pub fn is_empty(&self) -> bool {
+        self.ownership_changes.is_empty()
+            && self.balance_changes.is_empty()
+            && self.session_changes.is_empty()
+            && self.workflow_position_changes.is_empty()
+            && self.new_financial_transactions.is_empty()
+            && self.new_trust_decisions.is_empty()
+    }
+
+    /// The subsystems this diff actually changed, as a set of
+    /// [`StateField`]s. `InvariantValidator::validate_transition` uses
+    /// this to skip any invariant whose declared `touched_fields` don't
+    /// intersect it.
+    pub fn touched_fields(&self) -> HashSet<StateField> {
+        let mut fields = HashSet::new();
+        if !self.ownership_changes.is_empty() {
+            fields.insert(StateField::Ownership);
+        }
+        if !self.balance_changes.is_empty() {
+            fields.insert(StateField::Balances);
+        }
+        if !self.session_changes.is_empty() {
+            fields.insert(StateField::Session);
+        }
+        if !self.workflow_position_changes.is_empty() {
+            fields.insert(StateField::WorkflowPositions);
+        }
+        if !self.new_financial_transactions.is_empty() {
+            fields.insert(StateField::FinancialTransactions);
+        }
+        if !self.new_trust_decisions.is_empty() {
+            fields.insert(StateField::TrustDecisions);
+        }
+        fields
+    }
+}
+
+/// A subsystem of `ApplicationState` that a `StateDiff` can report as
+/// touched, and a `SecurityInvariant` can declare it only depends on (see
+/// `SecurityInvariant::with_touched_fields`). Fields `StateDiff` doesn't
+/// yet track (`data_objects`, `authorization_events`,
+/// `overdraft_permissions`, `workflow_completions`, `exchange_rates`) are
+/// deliberately absent: an invariant that reads one of those must leave
+/// `touched_fields` empty so it keeps running on every transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StateField {
+    Ownership,
+    Balances,
+    WorkflowPositions,
+    Session,
+    FinancialTransactions,
+    TrustDecisions,
+}
+
+/// One session's workflow position changing, being added, or being
+/// removed. `old_step`/`new_step` are `None` when the session had no
+/// position in the respective state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowPositionChange {
+    pub session_id: SessionId,
+    pub old_step: Option<WorkflowStep>,
+    pub new_step: Option<WorkflowStep>,
+}
+
+/// One object's ownership changing, being added, or being removed.
+/// `old_owner`/`new_owner` are `None` when the object didn't exist in the
+/// respective state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnershipChange {
+    pub object_id: ObjectId,
+    pub old_owner: Option<UserId>,
+    pub new_owner: Option<UserId>,
+}
+
+/// One account's balance changing, being added, or being removed.
+/// `old_amount`/`new_amount` are `None` when the account didn't hold a
+/// balance in the respective state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    pub account_id: AccountId,
+    pub currency: Option<Currency>,
+    pub old_amount: Option<MonetaryValue>,
+    pub new_amount: Option<MonetaryValue>,
+}
+
+/// A single change to the active session between two states.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SessionChange {
+    LoggedIn(SessionId),
+    LoggedOut(SessionId),
+    RoleAdded(SessionId, Role),
+    RoleRemoved(SessionId, Role),
+    /// Session ID changed while a session stayed active (old, new) —
+    /// e.g. rotation on authentication, or a fixation attempt.
+    Rotated(SessionId, SessionId),
+    /// The user bound to the (unchanged) session ID changed (session id,
+    /// old user, new user) — a session transferred to a different user.
+    UserChanged(SessionId, UserId, UserId),
+    Authenticated(SessionId),
+    Deauthenticated(SessionId),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_reports_ownership_and_balance_changes() {
+        let mut before = ApplicationState::new();
+        before.ownership.insert(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        before.balances.insert(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let mut after = before.clone();
+        after.ownership.insert(ObjectId("obj1".to_string()), UserId("bob".to_string()));
+        after.balances.insert(AccountId("acc1".to_string()), Balance::new(50, Currency::USD));
+        after.ownership.insert(ObjectId("obj2".to_string()), UserId("carol".to_string()));
+
+        let diff = before.diff(&after);
+
+        assert_eq!(diff.ownership_changes.len(), 2);
+        assert_eq!(diff.ownership_changes[0].object_id, ObjectId("obj1".to_string()));
+        assert_eq!(diff.ownership_changes[0].old_owner, Some(UserId("alice".to_string())));
+        assert_eq!(diff.ownership_changes[0].new_owner, Some(UserId("bob".to_string())));
+        assert_eq!(diff.ownership_changes[1].old_owner, None);
+
+        assert_eq!(diff.balance_changes.len(), 1);
+        assert_eq!(diff.balance_changes[0].old_amount, Some(MonetaryValue::from(100)));
+        assert_eq!(diff.balance_changes[0].new_amount, Some(MonetaryValue::from(50)));
+    }
+
+    #[test]
+    fn test_diff_reports_session_login_and_role_changes() {
+        let mut before = ApplicationState::new();
+        let mut after = before.clone();
+
+        after.current_session = Some(SessionState {
+            session_id: SessionId("sess1".to_string()),
+            user_id: UserId("alice".to_string()),
+            roles: HashSet::from([Role("user".to_string())]),
+            authenticated: true,
+            created_at: Utc::now(),
+            last_activity: Utc::now(),
+        });
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.session_changes, vec![SessionChange::LoggedIn(SessionId("sess1".to_string()))]);
+
+        before.current_session = after.current_session.clone();
+        if let Some(session) = &mut after.current_session {
+            session.roles.insert(Role("admin".to_string()));
+        }
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.session_changes, vec![SessionChange::RoleAdded(SessionId("sess1".to_string()), Role("admin".to_string()))]);
+    }
+
+    #[test]
+    fn test_diff_is_empty_for_identical_states() {
+        let state = ApplicationState::new();
+        assert!(state.diff(&state.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_touched_fields_reports_only_the_subsystems_that_changed() {
+        let mut before = ApplicationState::new();
+        before.set_balance(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let mut after = before.clone();
+        after.set_balance(AccountId("acc1".to_string()), Balance::new(50, Currency::USD));
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.touched_fields(), HashSet::from([StateField::Balances]));
+    }
+
+    #[test]
+    fn test_touched_fields_is_empty_when_nothing_changed() {
+        let state = ApplicationState::new();
+        assert!(state.diff(&state.clone()).touched_fields().is_empty());
+    }
+
+    #[test]
+    fn test_revert_to_undoes_ownership_and_balance_changes() {
+        let mut state = ApplicationState::new();
+        state.set_ownership(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        state.set_balance(AccountId("acc1".to_string()), Balance::new(100, Currency::USD));
+
+        let checkpoint = state.checkpoint();
+        state.set_ownership(ObjectId("obj1".to_string()), UserId("mallory".to_string()));
+        state.set_ownership(ObjectId("obj2".to_string()), UserId("mallory".to_string()));
+        state.set_balance(AccountId("acc1".to_string()), Balance::new(0, Currency::USD));
+
+        state.revert_to(checkpoint);
+
+        assert_eq!(state.ownership.get(&ObjectId("obj1".to_string())), Some(&UserId("alice".to_string())));
+        assert_eq!(state.ownership.get(&ObjectId("obj2".to_string())), None);
+        assert_eq!(state.balances.get(&AccountId("acc1".to_string())), Some(&Balance::new(100, Currency::USD)));
+        assert_eq!(state.checkpoint_depth(), 0);
+    }
+
+    #[test]
+    fn test_commit_keeps_changes_and_drops_checkpoint() {
+        let mut state = ApplicationState::new();
+        let checkpoint = state.checkpoint();
+        state.set_ownership(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        state.commit(checkpoint);
+
+        assert_eq!(state.ownership.get(&ObjectId("obj1".to_string())), Some(&UserId("alice".to_string())));
+        assert_eq!(state.checkpoint_depth(), 0);
+    }
+
+    #[test]
+    fn test_revert_of_outer_checkpoint_unwinds_nested_ones() {
+        let mut state = ApplicationState::new();
+        let outer = state.checkpoint();
+        state.set_ownership(ObjectId("obj1".to_string()), UserId("alice".to_string()));
+        let _inner = state.checkpoint();
+        state.set_ownership(ObjectId("obj1".to_string()), UserId("bob".to_string()));
+
+        state.revert_to(outer);
+
+        assert_eq!(state.ownership.get(&ObjectId("obj1".to_string())), None);
+        assert_eq!(state.checkpoint_depth(), 0);
+    }
+
+    #[test]
+    fn test_top_checkpoint_tracks_the_most_recently_pushed_open_frame() {
+        let mut state = ApplicationState::new();
+        assert_eq!(state.top_checkpoint(), None);
+
+        let outer = state.checkpoint();
+        assert_eq!(state.top_checkpoint(), Some(outer));
+
+        let inner = state.checkpoint();
+        assert_eq!(state.top_checkpoint(), Some(inner));
+
+        state.commit(inner);
+        assert_eq!(state.top_checkpoint(), Some(outer));
+
+        state.revert_to(outer);
+        assert_eq!(state.top_checkpoint(), None);
+    }
+
+    #[test]
+    fn test_commit_of_nested_checkpoint_folds_into_parent() {
+        let mut state = ApplicationState::new();
+        let outer = state.checkpoint();
+        let inner = state.checkpoint();
+        state.push_financial_transaction(FinancialTransaction {
+            id: "tx1".to_string(),
+            from_account: None,
+            to_account: Some(AccountId("acc1".to_string())),
+            amount: MonetaryValue::from(50),
+            currency: Currency::USD,
+            converted_amount: None,
+            converted_currency: None,
+            is_external: true,
+            timestamp: Utc::now(),
+        });
+        state.commit(inner);
+        assert_eq!(state.checkpoint_depth(), 1);
+
+        state.revert_to(outer);
+
+        assert!(state.financial_transactions.is_empty());
+        assert_eq!(state.checkpoint_depth(), 0);
+    }
 }
\ No newline at end of file
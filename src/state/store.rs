@@ -0,0 +1,347 @@
+//! Ledger Stores - Pluggable backends for persisting `LedgerEntry` records
+//!
+//! `StateLedger` only needs to append, look up, and range-scan entries; it
+//! doesn't care whether they live in memory or are journaled to disk with
+//! old states pruned down to periodic anchors. `LedgerStore` is that seam:
+//! `StateLedger` holds a `Box<dyn LedgerStore>` instead of owning its
+//! storage directly, the same way `EvidenceCollector` fans out through
+//! `EvidenceSink` rather than hardcoding where artifacts go.
+
+use super::ApplicationState;
+use super::ledger::LedgerEntry;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+
+/// A backend `StateLedger` can append entries to and query back out.
+/// Implementations are free to store entries verbatim (`InMemoryStore`) or
+/// reconstruct them on demand from a more compact representation
+/// (`JournaledPruningStore`).
+pub trait LedgerStore: Send + Sync {
+    /// Append `entry`. Entries are always appended in the order
+    /// `StateLedger` discovers their parent, but may belong to any branch.
+    fn append(&self, entry: LedgerEntry);
+
+    /// Look up an entry by its sequence number, on whichever branch it
+    /// belongs to.
+    fn get_by_sequence(&self, sequence: u64) -> Option<LedgerEntry>;
+
+    /// Look up an entry by its id.
+    fn get_by_id(&self, id: &str) -> Option<LedgerEntry>;
+
+    /// Look up an entry by its `state_hash`. `StateLedger`'s branching walk
+    /// (`ancestors`, `tree_route`) is driven entirely by `previous_hash`
+    /// links, so this is the lookup it relies on most.
+    fn get_by_hash(&self, state_hash: &str) -> Option<LedgerEntry>;
+
+    /// Every entry recorded in `[start, end]`, across all branches, in
+    /// insertion order.
+    fn iter_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<LedgerEntry>;
+
+    /// The most recently appended entry, if any.
+    fn latest(&self) -> Option<LedgerEntry>;
+}
+
+/// Keeps every entry in full, in memory, keyed by `state_hash` with a
+/// separate insertion-order index. This is `StateLedger`'s default
+/// backend and reproduces its original (pre-pluggable-store) behavior
+/// exactly.
+#[derive(Default)]
+pub struct InMemoryStore {
+    entries: RwLock<HashMap<String, LedgerEntry>>,
+    insertion_order: RwLock<Vec<String>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LedgerStore for InMemoryStore {
+    fn append(&self, entry: LedgerEntry) {
+        let state_hash = entry.state_hash.clone();
+        self.entries.write().insert(state_hash.clone(), entry);
+        self.insertion_order.write().push(state_hash);
+    }
+
+    fn get_by_sequence(&self, sequence: u64) -> Option<LedgerEntry> {
+        self.entries.read().values().find(|e| e.sequence == sequence).cloned()
+    }
+
+    fn get_by_id(&self, id: &str) -> Option<LedgerEntry> {
+        self.entries.read().values().find(|e| e.id == id).cloned()
+    }
+
+    fn get_by_hash(&self, state_hash: &str) -> Option<LedgerEntry> {
+        self.entries.read().get(state_hash).cloned()
+    }
+
+    fn iter_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<LedgerEntry> {
+        let entries = self.entries.read();
+        self.insertion_order.read()
+            .iter()
+            .filter_map(|hash| entries.get(hash))
+            .filter(|e| e.timestamp >= start && e.timestamp <= end)
+            .cloned()
+            .collect()
+    }
+
+    fn latest(&self) -> Option<LedgerEntry> {
+        let entries = self.entries.read();
+        self.insertion_order.read().last().and_then(|hash| entries.get(hash)).cloned()
+    }
+}
+
+/// What's retained for one entry in a `JournaledPruningStore`: either a
+/// full `ApplicationState` (an anchor) or a `StateDiff` against the
+/// entry's own `from_state` (everything in between two anchors).
+enum EntryBody {
+    Anchor(Box<ApplicationState>),
+    Diff(super::StateDiff),
+}
+
+/// One entry's metadata plus its (possibly pruned) body, as kept by
+/// `JournaledPruningStore`.
+struct StoredRecord {
+    id: String,
+    transition_id: String,
+    sequence: u64,
+    state_hash: String,
+    previous_hash: Option<String>,
+    triggering_action: super::Action,
+    timestamp: DateTime<Utc>,
+    /// `to_state.timestamp`, which a `StateDiff` body does not carry.
+    state_timestamp: Option<DateTime<Utc>>,
+    body: EntryBody,
+}
+
+/// A `LedgerStore` that bounds memory/disk growth over long-running
+/// capture sessions: rather than keeping a full `ApplicationState` per
+/// entry, it stores one every `anchor_interval` entries (an "anchor") and
+/// only the `StateDiff` from each entry's `from_state` to its `to_state`
+/// in between. Reads reconstruct the requested state by loading the
+/// nearest preceding anchor and replaying diffs forward.
+///
+/// Entry metadata (id, sequence, hashes, timestamp, triggering action) is
+/// always kept in full — it's small and every lookup needs it regardless
+/// of whether the body is an anchor or a diff.
+///
+/// Reconstruction replays records in storage order between an anchor and
+/// its target, so this store assumes the chain it is given is linear. A
+/// `StateLedger` that records onto non-canonical branches
+/// (`record_transition_on` with a parent other than the current head)
+/// will reconstruct incorrectly for entries on those branches; use
+/// `InMemoryStore` if branching reorgs need to be exact. On a linear chain,
+/// fields that `ApplicationState::apply_diff` does not apply are taken from
+/// the anchor, so an intermediate entry whose transition changed one of
+/// them reconstructs inexactly too.
+pub struct JournaledPruningStore {
+    anchor_interval: u64,
+    records: RwLock<Vec<StoredRecord>>,
+}
+
+impl JournaledPruningStore {
+    /// `anchor_interval` is how many entries separate two full-state
+    /// anchors; it's clamped to at least 1, since an interval of 0 would
+    /// never pin down a state to replay from.
+    pub fn new(anchor_interval: u64) -> Self {
+        Self {
+            anchor_interval: anchor_interval.max(1),
+            records: RwLock::new(Vec::new()),
+        }
+    }
+
+    fn reconstruct_to_state(records: &[StoredRecord], upto_index: usize) -> ApplicationState {
+        let mut anchor_index = upto_index;
+        while !matches!(records[anchor_index].body, EntryBody::Anchor(_)) {
+            anchor_index -= 1;
+        }
+
+        let mut state = match &records[anchor_index].body {
+            EntryBody::Anchor(state) => (**state).clone(),
+            EntryBody::Diff(_) => unreachable!("walked back to a non-anchor"),
+        };
+
+        for record in &records[anchor_index + 1..=upto_index] {
+            if let EntryBody::Diff(diff) = &record.body {
+                state.apply_diff(diff);
+            }
+        }
+        state.timestamp = records[upto_index].state_timestamp;
+
+        state
+    }
+
+    fn to_entry(records: &[StoredRecord], index: usize) -> LedgerEntry {
+        let record = &records[index];
+        let to_state = Self::reconstruct_to_state(records, index);
+        let from_state = if index == 0 {
+            ApplicationState::default()
+        } else {
+            Self::reconstruct_to_state(records, index - 1)
+        };
+
+        LedgerEntry {
+            id: record.id.clone(),
+            sequence: record.sequence,
+            transition: super::StateTransition {
+                id: record.transition_id.clone(),
+                from_state,
+                to_state,
+                triggering_action: record.triggering_action.clone(),
+                timestamp: record.timestamp,
+            },
+            state_hash: record.state_hash.clone(),
+            previous_hash: record.previous_hash.clone(),
+            timestamp: record.timestamp,
+        }
+    }
+}
+
+impl LedgerStore for JournaledPruningStore {
+    fn append(&self, entry: LedgerEntry) {
+        let mut records = self.records.write();
+        let is_anchor = (entry.sequence.saturating_sub(1)) % self.anchor_interval == 0;
+        let body = if is_anchor {
+            EntryBody::Anchor(Box::new(entry.transition.to_state.clone()))
+        } else {
+            EntryBody::Diff(entry.transition.from_state.diff(&entry.transition.to_state))
+        };
+
+        records.push(StoredRecord {
+            id: entry.id,
+            transition_id: entry.transition.id,
+            sequence: entry.sequence,
+            state_hash: entry.state_hash,
+            previous_hash: entry.previous_hash,
+            triggering_action: entry.transition.triggering_action,
+            timestamp: entry.timestamp,
+            state_timestamp: entry.transition.to_state.timestamp,
+            body,
+        });
+    }
+
+    fn get_by_sequence(&self, sequence: u64) -> Option<LedgerEntry> {
+        let records = self.records.read();
+        let index = records.iter().position(|r| r.sequence == sequence)?;
+        Some(Self::to_entry(&records, index))
+    }
+
+    fn get_by_id(&self, id: &str) -> Option<LedgerEntry> {
+        let records = self.records.read();
+        let index = records.iter().position(|r| r.id == id)?;
+        Some(Self::to_entry(&records, index))
+    }
+
+    fn get_by_hash(&self, state_hash: &str) -> Option<LedgerEntry> {
+        let records = self.records.read();
+        let index = records.iter().position(|r| r.state_hash == state_hash)?;
+        Some(Self::to_entry(&records, index))
+    }
+
+    fn iter_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<LedgerEntry> {
+        let records = self.records.read();
+        (0..records.len())
+            .filter(|&i| records[i].timestamp >= start && records[i].timestamp <= end)
+            .map(|i| Self::to_entry(&records, i))
+            .collect()
+    }
+
+    fn latest(&self) -> Option<LedgerEntry> {
+        let records = self.records.read();
+        if records.is_empty() {
+            None
+        } else {
+            Some(Self::to_entry(&records, records.len() - 1))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Action, ActionType, StateTransition};
+    use crate::types::{ActionTiming, ObjectId, UserId};
+    use std::collections::HashMap as StdHashMap;
+    use uuid::Uuid;
+
+    fn entry(sequence: u64, previous_hash: Option<String>, to_state: ApplicationState) -> LedgerEntry {
+        let from_state = ApplicationState::default();
+        let state_hash = format!("hash-{sequence}");
+        LedgerEntry {
+            id: Uuid::new_v4().to_string(),
+            sequence,
+            transition: StateTransition {
+                id: Uuid::new_v4().to_string(),
+                from_state,
+                to_state,
+                triggering_action: Action {
+                    id: Uuid::new_v4().to_string(),
+                    action_type: ActionType::HttpRequest,
+                    request: None,
+                    parameters: StdHashMap::new(),
+                    authentication: None,
+                    timing: ActionTiming {
+                        start_time: Utc::now(),
+                        end_time: Utc::now(),
+                        duration_ms: 10,
+                    },
+                },
+                timestamp: Utc::now(),
+            },
+            state_hash,
+            previous_hash,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_in_memory_store_round_trips_entries() {
+        let store = InMemoryStore::new();
+        let first = entry(1, None, ApplicationState::new());
+        let first_hash = first.state_hash.clone();
+        store.append(first);
+
+        let second = entry(2, Some(first_hash.clone()), ApplicationState::new());
+        store.append(second);
+
+        assert_eq!(store.get_by_sequence(1).unwrap().sequence, 1);
+        assert!(store.get_by_hash(&first_hash).is_some());
+        assert_eq!(store.latest().unwrap().sequence, 2);
+    }
+
+    #[test]
+    fn test_journaled_pruning_store_reconstructs_intermediate_ownership() {
+        let store = JournaledPruningStore::new(3);
+
+        let mut previous_hash = None;
+        let mut state = ApplicationState::new();
+        for (i, owner) in ["alice", "bob", "carol", "dave"].into_iter().enumerate() {
+            let sequence = (i + 1) as u64;
+            let mut next_state = state.clone();
+            next_state.ownership.insert(ObjectId("obj1".to_string()), UserId(owner.to_string()));
+
+            let mut e = entry(sequence, previous_hash.clone(), next_state.clone());
+            e.transition.from_state = state.clone();
+            previous_hash = Some(e.state_hash.clone());
+            store.append(e);
+
+            state = next_state;
+        }
+
+        // Sequence 3 falls between the anchors at 1 and 4 (interval 3), so
+        // it's stored as a diff and must be replayed to reconstruct.
+        let reconstructed = store.get_by_sequence(3).unwrap();
+        assert_eq!(
+            reconstructed.transition.to_state.ownership.get(&ObjectId("obj1".to_string())),
+            Some(&UserId("carol".to_string()))
+        );
+
+        let latest = store.latest().unwrap();
+        assert_eq!(
+            latest.transition.to_state.ownership.get(&ObjectId("obj1".to_string())),
+            Some(&UserId("dave".to_string()))
+        );
+    }
+}
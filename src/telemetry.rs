@@ -0,0 +1,275 @@
+//! Telemetry - Optional OpenTelemetry instrumentation
+//!
+//! Feature-gated behind `otel`. `StateTracker`, `BalanceMonitor`,
+//! `EvidenceCollector`, `CausalEngine`, `InvariantValidator`, and
+//! `ReplayEngine` call into `Telemetry` unconditionally; with the feature
+//! off every method is a zero-cost no-op, so call sites never need their
+//! own `#[cfg(feature = "otel")]`. Traces, metrics, and logs all flow
+//! through whatever exporter OTEL's global providers are configured
+//! with, rather than bolting on a separate tracing backend.
+//!
+//! `enabled` is a separate, always-compiled-in runtime toggle on top of
+//! the `otel` feature: the feature decides whether instrumentation exists
+//! at all, `enabled` decides whether it currently fires. This lets FFI
+//! callers (see `PyProofEngine`/`PyInvariantEngine`) silence tracing on a
+//! noisy validation run without recompiling the extension.
+//!
+//! Spans are always started from `opentelemetry::global::tracer`, never a
+//! tracer instance owned by one subsystem, so a `PyInvariantEngine` call
+//! that internally touches `InvariantValidator`, `CoverageTracker`, and
+//! `StateTracker` produces one continuous nested trace rather than three
+//! disconnected ones - there is nothing extra the PyO3 layer needs to do
+//! to stitch them back together.
+
+/// A span started by [`Telemetry::start_span`], ended when dropped.
+#[cfg(feature = "otel")]
+pub struct SpanGuard(Option<opentelemetry::global::BoxedSpan>);
+
+#[cfg(not(feature = "otel"))]
+pub struct SpanGuard;
+
+#[cfg(feature = "otel")]
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        use opentelemetry::trace::Span;
+        if let Some(mut span) = self.0.take() {
+            span.end();
+        }
+    }
+}
+
+impl SpanGuard {
+    /// Set an attribute on the span after it was started, for values (e.g.
+    /// a final count) not known until the call the span covers has finished.
+    /// A no-op on a guard from a disabled/no-`otel` build.
+    pub fn set_attribute(&mut self, key: &'static str, value: String) {
+        #[cfg(feature = "otel")]
+        {
+            use opentelemetry::trace::Span;
+            if let Some(span) = &mut self.0 {
+                span.set_attribute(opentelemetry::KeyValue::new(key, value));
+            }
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = (key, value);
+    }
+
+    /// Record a point-in-time event on the span, e.g. one per violation
+    /// detected during the call the span covers.
+    pub fn add_event(&mut self, name: &'static str, attributes: &[(&'static str, String)]) {
+        #[cfg(feature = "otel")]
+        {
+            use opentelemetry::trace::Span;
+            if let Some(span) = &mut self.0 {
+                let kvs: Vec<opentelemetry::KeyValue> = attributes
+                    .iter()
+                    .map(|(key, value)| opentelemetry::KeyValue::new(*key, value.clone()))
+                    .collect();
+                span.add_event(name, kvs);
+            }
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = (name, attributes);
+    }
+}
+
+/// Counters and histograms shared by the instrumented subsystems, plus a
+/// tracer for request-scoped spans. Cheap to construct: metric and
+/// tracer handles are fetched from OTEL's global providers, not owned.
+pub struct Telemetry {
+    enabled: std::sync::atomic::AtomicBool,
+    #[cfg(feature = "otel")]
+    artifacts_captured: opentelemetry::metrics::Counter<u64>,
+    #[cfg(feature = "otel")]
+    unauthorized_accesses: opentelemetry::metrics::Counter<u64>,
+    #[cfg(feature = "otel")]
+    conservation_failures: opentelemetry::metrics::Counter<u64>,
+    #[cfg(feature = "otel")]
+    http_duration_ms: opentelemetry::metrics::Histogram<u64>,
+    #[cfg(feature = "otel")]
+    invariants_evaluated: opentelemetry::metrics::Counter<u64>,
+    #[cfg(feature = "otel")]
+    violations_found: opentelemetry::metrics::Counter<u64>,
+    #[cfg(feature = "otel")]
+    chains_completed: opentelemetry::metrics::Counter<u64>,
+    #[cfg(feature = "otel")]
+    chain_length: opentelemetry::metrics::Histogram<u64>,
+    #[cfg(feature = "otel")]
+    build_latency_ms: opentelemetry::metrics::Histogram<u64>,
+    #[cfg(feature = "otel")]
+    coverage_percentage: opentelemetry::metrics::Gauge<f64>,
+    #[cfg(feature = "otel")]
+    unclassified_transitions: opentelemetry::metrics::Counter<u64>,
+}
+
+impl Telemetry {
+    #[cfg(feature = "otel")]
+    pub fn init() -> Self {
+        let meter = opentelemetry::global::meter("kali_mcp_core");
+        Self {
+            enabled: std::sync::atomic::AtomicBool::new(true),
+            artifacts_captured: meter.u64_counter("evidence.artifacts_captured").init(),
+            unauthorized_accesses: meter.u64_counter("ownership.unauthorized_accesses").init(),
+            conservation_failures: meter.u64_counter("balance.conservation_failures").init(),
+            http_duration_ms: meter.u64_histogram("http.duration_ms").init(),
+            invariants_evaluated: meter.u64_counter("invariant.invariants_evaluated").init(),
+            violations_found: meter.u64_counter("invariant.violations_found").init(),
+            chains_completed: meter.u64_counter("causal.chains_completed").init(),
+            chain_length: meter.u64_histogram("causal.chain_length").init(),
+            build_latency_ms: meter.u64_histogram("causal.build_latency_ms").init(),
+            coverage_percentage: meter.f64_gauge("coverage.percentage").init(),
+            unclassified_transitions: meter.u64_counter("coverage.unclassified_transitions").init(),
+        }
+    }
+
+    #[cfg(not(feature = "otel"))]
+    pub fn init() -> Self {
+        Self { enabled: std::sync::atomic::AtomicBool::new(true) }
+    }
+
+    /// Enable or disable tracing/metrics emission at runtime, independent
+    /// of whether the `otel` feature was compiled in. FFI callers use this
+    /// to quiet a noisy run without a recompile.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// One artifact of `evidence_type` was captured.
+    pub fn record_artifact_captured(&self, evidence_type: &str) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.artifacts_captured.add(
+                1,
+                &[opentelemetry::KeyValue::new("evidence_type", evidence_type.to_string())],
+            );
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = evidence_type;
+    }
+
+    /// An access attempt was denied.
+    pub fn record_unauthorized_access(&self) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.unauthorized_accesses.add(1, &[]);
+        }
+    }
+
+    /// A registered `BalanceInvariant` flipped from holding to violated.
+    pub fn record_conservation_failure(&self, invariant_name: &str) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.conservation_failures.add(
+                1,
+                &[opentelemetry::KeyValue::new("invariant_name", invariant_name.to_string())],
+            );
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = invariant_name;
+    }
+
+    /// An HTTP response artifact's `duration_ms`.
+    pub fn record_http_duration_ms(&self, duration_ms: u64) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.http_duration_ms.record(duration_ms, &[]);
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = duration_ms;
+    }
+
+    /// One invariant from the catalog was evaluated against a transition.
+    pub fn record_invariant_evaluated(&self, invariant_id: &str) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.invariants_evaluated.add(
+                1,
+                &[opentelemetry::KeyValue::new("invariant_id", invariant_id.to_string())],
+            );
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = invariant_id;
+    }
+
+    /// `invariant_id` was found violated.
+    pub fn record_violation_found(&self, invariant_id: &str) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.violations_found.add(
+                1,
+                &[opentelemetry::KeyValue::new("invariant_id", invariant_id.to_string())],
+            );
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = invariant_id;
+    }
+
+    /// A `CausalChain` finished building, with its final `links.len()` and
+    /// wall-clock build time.
+    pub fn record_chain_completed(&self, chain_length: usize, build_latency_ms: u64) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.chains_completed.add(1, &[]);
+            self.chain_length.record(chain_length as u64, &[]);
+            self.build_latency_ms.record(build_latency_ms, &[]);
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = (chain_length, build_latency_ms);
+    }
+
+    /// `category`'s coverage as of the most recent `generate_report` call.
+    pub fn record_coverage_percentage(&self, category: &str, percentage: f64) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.coverage_percentage.record(
+                percentage,
+                &[opentelemetry::KeyValue::new("category", category.to_string())],
+            );
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = (category, percentage);
+    }
+
+    /// A state transition could not be classified under any invariant.
+    pub fn record_unclassified_transition(&self) {
+        #[cfg(feature = "otel")]
+        if self.is_enabled() {
+            self.unclassified_transitions.add(1, &[]);
+        }
+    }
+
+    /// Start a span named `name` carrying `attributes`, ended when the
+    /// returned guard is dropped. A no-op (no span ever starts) while
+    /// `enabled` is false.
+    pub fn start_span(&self, name: &'static str, attributes: &[(&'static str, String)]) -> SpanGuard {
+        #[cfg(feature = "otel")]
+        {
+            if !self.is_enabled() {
+                return SpanGuard(None);
+            }
+            use opentelemetry::trace::Tracer;
+            let tracer = opentelemetry::global::tracer("kali_mcp_core");
+            let kvs: Vec<opentelemetry::KeyValue> = attributes
+                .iter()
+                .map(|(key, value)| opentelemetry::KeyValue::new(*key, value.clone()))
+                .collect();
+            let span = tracer.span_builder(name).with_attributes(kvs).start(&tracer);
+            SpanGuard(Some(span))
+        }
+        #[cfg(not(feature = "otel"))]
+        {
+            let _ = (name, attributes);
+            SpanGuard
+        }
+    }
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self::init()
+    }
+}
@@ -1,8 +1,13 @@
 //! Core type definitions for the Kali MCP Toolkit
 
 use chrono::{DateTime, Utc};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
 use uuid::Uuid;
 
 /// Unique identifier for findings
@@ -41,23 +46,201 @@ pub struct SessionId(pub String);
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Role(pub String);
 
+/// Exact-precision monetary amount, backed by an arbitrary-precision rational.
+///
+/// Integer `i64` amounts cannot represent fractional subunits, interest, or
+/// exchange-rate conversions without rounding error, which makes conservation
+/// checks either spuriously fail or silently pass. `MonetaryValue` keeps every
+/// amount as an exact fraction so sums and comparisons never lose precision.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MonetaryValue(pub BigRational);
+
+impl MonetaryValue {
+    pub fn zero() -> Self {
+        Self(BigRational::zero())
+    }
+
+    pub fn from_integer(value: i64) -> Self {
+        Self(BigRational::from_integer(BigInt::from(value)))
+    }
+
+    /// Construct from minor units (e.g. cents) at a given number of decimal places.
+    pub fn from_minor_units(minor: i64, decimals: u32) -> Self {
+        let scale = BigInt::from(10u64).pow(decimals);
+        Self(BigRational::new(BigInt::from(minor), scale))
+    }
+
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl Default for MonetaryValue {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl From<i64> for MonetaryValue {
+    fn from(value: i64) -> Self {
+        Self::from_integer(value)
+    }
+}
+
+impl Add for MonetaryValue {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for MonetaryValue {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Neg for MonetaryValue {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Mul for MonetaryValue {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl std::iter::Sum for MonetaryValue {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, v| acc + v)
+    }
+}
+
+// Ergonomic comparison against plain integers so call sites that compared
+// `Balance.amount` to an `i64` literal before this type existed keep working.
+impl PartialEq<i64> for MonetaryValue {
+    fn eq(&self, other: &i64) -> bool {
+        self.0 == BigRational::from_integer(BigInt::from(*other))
+    }
+}
+
+impl PartialOrd<i64> for MonetaryValue {
+    fn partial_cmp(&self, other: &i64) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&BigRational::from_integer(BigInt::from(*other)))
+    }
+}
+
+/// Lossless serde representation as a numerator/denominator pair so state
+/// snapshots round-trip exactly with no precision loss.
+#[derive(Serialize, Deserialize)]
+struct MonetaryValueRepr {
+    numer: String,
+    denom: String,
+}
+
+impl Serialize for MonetaryValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MonetaryValueRepr {
+            numer: self.0.numer().to_string(),
+            denom: self.0.denom().to_string(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MonetaryValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = MonetaryValueRepr::deserialize(deserializer)?;
+        let numer = BigInt::from_str(&repr.numer).map_err(serde::de::Error::custom)?;
+        let denom = BigInt::from_str(&repr.denom).map_err(serde::de::Error::custom)?;
+        if denom.is_zero() {
+            return Err(serde::de::Error::custom("MonetaryValue denominator must not be zero"));
+        }
+        Ok(Self(BigRational::new(numer, denom)))
+    }
+}
+
 /// Financial balance representation
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Balance {
-    pub amount: i64,
+    pub amount: MonetaryValue,
     pub currency: Currency,
 }
 
 impl Balance {
-    pub fn new(amount: i64, currency: Currency) -> Self {
-        Self { amount, currency }
+    pub fn new(amount: impl Into<MonetaryValue>, currency: Currency) -> Self {
+        Self { amount: amount.into(), currency }
     }
-    
+
     pub fn zero(currency: Currency) -> Self {
-        Self { amount: 0, currency }
+        Self { amount: MonetaryValue::zero(), currency }
+    }
+
+    /// Add `other`'s amount, rejecting a currency mismatch rather than
+    /// silently summing across denominations.
+    pub fn checked_add(&self, other: &Balance) -> Result<Balance, CurrencyMismatch> {
+        self.require_same_currency(other)?;
+        Ok(Balance::new(self.amount.clone() + other.amount.clone(), self.currency))
+    }
+
+    /// Subtract `other`'s amount, rejecting a currency mismatch rather
+    /// than silently differencing across denominations.
+    pub fn checked_sub(&self, other: &Balance) -> Result<Balance, CurrencyMismatch> {
+        self.require_same_currency(other)?;
+        Ok(Balance::new(self.amount.clone() - other.amount.clone(), self.currency))
+    }
+
+    fn require_same_currency(&self, other: &Balance) -> Result<(), CurrencyMismatch> {
+        if self.currency != other.currency {
+            return Err(CurrencyMismatch { expected: self.currency, found: other.currency });
+        }
+        Ok(())
+    }
+}
+
+/// Error combining two `Balance`s of different currencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrencyMismatch {
+    pub expected: Currency,
+    pub found: Currency,
+}
+
+impl std::fmt::Display for CurrencyMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "currency mismatch: expected {:?}, found {:?}", self.expected, self.found)
     }
 }
 
+impl std::error::Error for CurrencyMismatch {}
+
+/// A declared conversion rate between two currencies, applicable to the
+/// state transition that carries it.
+///
+/// Cross-asset transactions must reference a rate from this table rather
+/// than asserting their own conversion, so a conversion invariant can
+/// confirm the credited leg was actually computed from an agreed-upon
+/// rate instead of one the attacker chose after the fact.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub from: Currency,
+    pub to: Currency,
+    /// Units of `to` per one unit of `from`, as an exact rational.
+    pub rate: MonetaryValue,
+}
+
 /// Supported currencies
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Currency {
@@ -71,6 +254,21 @@ pub enum Currency {
     Custom(u32),
 }
 
+impl Currency {
+    /// Number of decimal places this currency's conventional minor unit
+    /// (e.g. cents) represents. Used to detect amounts finer than any
+    /// ledger denominated in this currency would ever display - see
+    /// `MON_006` ("salami slicing" detection).
+    pub fn minor_unit_decimals(&self) -> u32 {
+        match self {
+            Currency::USD | Currency::EUR | Currency::GBP => 2,
+            Currency::BTC => 8,
+            Currency::ETH => 18,
+            Currency::Points | Currency::Credits | Currency::Custom(_) => 0,
+        }
+    }
+}
+
 /// Workflow step identifier
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WorkflowStep {
@@ -389,8 +389,10 @@ mod property_test_2_3 {
             id: "tx_001".to_string(),
             from_account: Some(acc1.clone()),
             to_account: Some(acc2.clone()),
-            amount: 200,
+            amount: MonetaryValue::from_integer(200),
             currency: Currency::USD,
+            converted_amount: None,
+            converted_currency: None,
             is_external: false,
             timestamp: chrono::Utc::now(),
         });
@@ -625,8 +627,10 @@ mod integration_tests {
             id: "tx_001".to_string(),
             from_account: Some(create_account_id(1)),
             to_account: Some(create_account_id(2)),
-            amount: 200,
+            amount: MonetaryValue::from_integer(200),
             currency: Currency::USD,
+            converted_amount: None,
+            converted_currency: None,
             is_external: false,
             timestamp: chrono::Utc::now(),
         });